@@ -1,4 +1,17 @@
 use crate::system_monitor;
+use ebb_db::db::models::{
+    blocking_preference::BlockableApp,
+    recurrence_rule::RecurrenceRule,
+    tide_template::{parse_date, TideTemplate},
+    tide_types::{GoalDirection, MetricsType, TideFrequency},
+};
+use ebb_db::db_manager::DbManager;
+use ebb_db::services::blocking_service::BlockingService;
+use ebb_tide_manager::focus_scheduler::ScheduledFlowStart;
+use ebb_tide_manager::tide_history::{HistoryEntry, HistoryRollup, RollupPeriod};
+use ebb_tide_manager::tide_streak::{StreakPeriod, StreakSummary};
+use ebb_tide_manager::tide_suggestion::GoalSuggestion;
+use ebb_tide_manager::TideManager;
 use log::info;
 use os_monitor::{
     get_application_icon_data, has_accessibility_permissions, request_accessibility_permissions,
@@ -6,7 +19,7 @@ use os_monitor::{
 };
 use std::fs;
 use std::path::PathBuf;
-use std::sync::Mutex;
+use std::sync::{Arc, Mutex};
 use tauri::command;
 use tauri::{AppHandle, Emitter};
 use tokio::time::{sleep, Duration};
@@ -40,6 +53,44 @@ pub fn start_blocking(blocking_apps: Vec<BlockingApp>, is_block_list: bool) {
     os_start_blocking(&apps, "https://ebb.cool/vibes", is_block_list);
 }
 
+/// Start blocking with a workflow's saved preferences, resolving category tags to their apps
+/// Usable from Rust so blocking doesn't depend on the frontend being open
+pub async fn start_blocking_for_workflow(workflow_id: &str) -> Result<(), String> {
+    let ebb_db = DbManager::get_shared_ebb().await.map_err(|e| e.to_string())?;
+    let codeclimbers_db = DbManager::get_shared_codeclimbers()
+        .await
+        .map_err(|e| e.to_string())?;
+    let blocking = BlockingService::new_with_pools(ebb_db.pool.clone(), codeclimbers_db.pool.clone())
+        .resolve_workflow_blocking(workflow_id)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let apps: Vec<BlockableItem> = blocking.apps.into_iter().map(to_blockable_item).collect();
+    info!(
+        "Starting blocking for workflow {}: {} apps, block list: {}",
+        workflow_id,
+        apps.len(),
+        blocking.is_block_list
+    );
+
+    *BLOCKING_STATE.lock().map_err(|e| e.to_string())? = Some((apps.clone(), blocking.is_block_list));
+
+    os_start_blocking(&apps, "https://ebb.cool/vibes", blocking.is_block_list);
+    Ok(())
+}
+
+fn to_blockable_item(app: BlockableApp) -> BlockableItem {
+    BlockableItem {
+        app_external_id: app.app_external_id,
+        is_browser: app.is_browser,
+    }
+}
+
+#[command]
+pub async fn start_workflow_blocking(workflow_id: String) -> Result<(), String> {
+    start_blocking_for_workflow(&workflow_id).await
+}
+
 #[command]
 pub fn stop_blocking() {
     *BLOCKING_STATE.lock().unwrap() = None;
@@ -354,3 +405,215 @@ pub fn notify_end_session(app_handle: AppHandle) -> Result<(), String> {
         .emit("end-session", ())
         .map_err(|e| e.to_string())
 }
+
+fn tide_manager() -> Result<&'static Arc<TideManager>, String> {
+    crate::TIDE_MANAGER
+        .get()
+        .ok_or_else(|| "TideManager not initialized".to_string())
+}
+
+#[tauri::command]
+pub async fn set_week_start_day(week_start_day: u8) -> Result<(), String> {
+    info!("command: set_week_start_day {}", week_start_day);
+    let tide_manager = tide_manager()?;
+    tide_manager
+        .set_week_start_day(week_start_day)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn create_tide_template(
+    metrics_type: MetricsType,
+    tag_ids: Option<Vec<String>>,
+    tide_frequency: TideFrequency,
+    goal_amount: f64,
+    goal_direction: Option<GoalDirection>,
+    day_of_week: Option<String>,
+) -> Result<TideTemplate, String> {
+    info!("command: create_tide_template {} {}", metrics_type, tide_frequency);
+    let tide_manager = tide_manager()?;
+    tide_manager
+        .create_template(
+            metrics_type,
+            tag_ids,
+            tide_frequency,
+            goal_amount,
+            goal_direction.unwrap_or(GoalDirection::Minimum),
+            day_of_week,
+        )
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn update_tide_template(
+    id: String,
+    goal_amount: f64,
+    day_of_week: Option<String>,
+) -> Result<(), String> {
+    info!("command: update_tide_template {}", id);
+    let tide_manager = tide_manager()?;
+    tide_manager
+        .update_template(&id, goal_amount, day_of_week)
+        .await
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn set_tide_template_milestones(
+    id: String,
+    progress_milestones: Vec<u8>,
+) -> Result<(), String> {
+    info!("command: set_tide_template_milestones {}", id);
+    let tide_manager = tide_manager()?;
+    tide_manager
+        .set_template_milestones(&id, progress_milestones)
+        .await
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
+/// `recurrence_rule` is an RRULE subset such as "RRULE:FREQ=WEEKLY;INTERVAL=2;BYDAY=MO", None clears it
+#[tauri::command]
+pub async fn set_tide_template_recurrence(
+    id: String,
+    recurrence_rule: Option<String>,
+) -> Result<(), String> {
+    info!("command: set_tide_template_recurrence {}", id);
+    let tide_manager = tide_manager()?;
+    let recurrence_rule = recurrence_rule
+        .map(|rule| rule.parse::<RecurrenceRule>())
+        .transpose()
+        .map_err(|e| e.to_string())?;
+    tide_manager
+        .set_template_recurrence_rule(&id, recurrence_rule)
+        .await
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn archive_tide_template(id: String) -> Result<(), String> {
+    info!("command: archive_tide_template {}", id);
+    let tide_manager = tide_manager()?;
+    tide_manager
+        .archive_template(&id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn suggest_tide_goal(id: String) -> Result<Option<GoalSuggestion>, String> {
+    info!("command: suggest_tide_goal {}", id);
+    let tide_manager = tide_manager()?;
+    tide_manager
+        .suggest_goal(&id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+fn invalid_date(date: &str) -> String {
+    format!("Invalid date '{}' (expected YYYY-MM-DD)", date)
+}
+
+#[tauri::command]
+pub async fn create_tide_pause(
+    start_date: String,
+    end_date: String,
+    reason: Option<String>,
+) -> Result<String, String> {
+    info!("command: create_tide_pause {} {}", start_date, end_date);
+    let tide_manager = tide_manager()?;
+    let start = parse_date(&start_date).ok_or_else(|| invalid_date(&start_date))?;
+    let end = parse_date(&end_date).ok_or_else(|| invalid_date(&end_date))?;
+    tide_manager
+        .create_pause(start, end, reason)
+        .await
+        .map(|pause| pause.id)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn delete_tide_pause(id: String) -> Result<(), String> {
+    info!("command: delete_tide_pause {}", id);
+    let tide_manager = tide_manager()?;
+    tide_manager
+        .delete_pause(&id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn set_tide_template_skip_date(
+    id: String,
+    date: String,
+    skipped: bool,
+) -> Result<(), String> {
+    info!("command: set_tide_template_skip_date {} {} {}", id, date, skipped);
+    let tide_manager = tide_manager()?;
+    let local_date = parse_date(&date).ok_or_else(|| invalid_date(&date))?;
+    tide_manager
+        .set_template_skip_date(&id, local_date, skipped)
+        .await
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_tide_template_history(
+    id: String,
+    start_date: String,
+    end_date: String,
+) -> Result<Vec<HistoryEntry>, String> {
+    info!("command: get_tide_template_history {} {} {}", id, start_date, end_date);
+    let tide_manager = tide_manager()?;
+    let start = parse_date(&start_date).ok_or_else(|| invalid_date(&start_date))?;
+    let end = parse_date(&end_date).ok_or_else(|| invalid_date(&end_date))?;
+    tide_manager
+        .get_template_history(&id, start, end)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_tide_template_rollups(
+    id: String,
+    start_date: String,
+    end_date: String,
+    period: RollupPeriod,
+) -> Result<Vec<HistoryRollup>, String> {
+    info!("command: get_tide_template_rollups {} {} {} {:?}", id, start_date, end_date, period);
+    let tide_manager = tide_manager()?;
+    let start = parse_date(&start_date).ok_or_else(|| invalid_date(&start_date))?;
+    let end = parse_date(&end_date).ok_or_else(|| invalid_date(&end_date))?;
+    tide_manager
+        .get_template_rollups(&id, start, end, period)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_tide_streak(id: String) -> Result<StreakSummary, String> {
+    info!("command: get_tide_streak {}", id);
+    let tide_manager = tide_manager()?;
+    tide_manager.get_streak(&id).await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_tide_streak_history(id: String) -> Result<Vec<StreakPeriod>, String> {
+    info!("command: get_tide_streak_history {}", id);
+    let tide_manager = tide_manager()?;
+    tide_manager.get_streak_history(&id).await.map_err(|e| e.to_string())
+}
+
+/// Next start of a focus schedule, used for reminders about schedules with custom rules
+#[tauri::command]
+pub async fn get_focus_schedule_next_start(id: String) -> Result<Option<ScheduledFlowStart>, String> {
+    info!("command: get_focus_schedule_next_start {}", id);
+    let focus_scheduler = crate::FOCUS_SCHEDULER
+        .get()
+        .ok_or_else(|| "FocusScheduler not initialized".to_string())?;
+    focus_scheduler.get_next_start(&id).await.map_err(|e| e.to_string())
+}
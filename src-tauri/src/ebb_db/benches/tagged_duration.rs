@@ -0,0 +1,160 @@
+//! Compares the SQL aggregation in `calculate_tagged_duration_in_range` with the
+//! application-side version on a generated dataset of 1M activity states.
+//!
+//! Run with `cargo bench -p ebb-db --bench tagged_duration --features test-utils`
+
+use std::time::{Duration, Instant};
+
+use ebb_db::db::activity_state_repo::{ActivityStateRepo, ACTIVITY_INDEX_RECOMMENDATIONS};
+use sqlx::sqlite::SqlitePoolOptions;
+use sqlx::{Pool, Sqlite};
+use time::macros::datetime;
+use time::OffsetDateTime;
+
+const ACTIVITY_STATE_COUNT: i64 = 1_000_000;
+const ITERATIONS: usize = 5;
+
+// One state every 30 seconds, ending at DATASET_END
+const DATASET_END: OffsetDateTime = datetime!(2025-06-01 00:00 UTC);
+
+async fn create_dataset() -> Pool<Sqlite> {
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+
+    for statement in [
+        "CREATE TABLE tag (
+            id TEXT PRIMARY KEY NOT NULL,
+            name TEXT NOT NULL,
+            tag_type TEXT NOT NULL
+        )",
+        "CREATE TABLE activity_state (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            state TEXT NOT NULL,
+            app_switches INTEGER NOT NULL DEFAULT 0,
+            start_time TIMESTAMP NOT NULL,
+            end_time TIMESTAMP NOT NULL,
+            created_at TIMESTAMP NOT NULL
+        )",
+        "CREATE TABLE activity_state_tag (
+            activity_state_id TEXT NOT NULL,
+            tag_id TEXT NOT NULL,
+            app_tag_id TEXT,
+            created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+            updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+            UNIQUE(activity_state_id, tag_id)
+        )",
+        "INSERT INTO tag (id, name, tag_type) VALUES
+            ('creating', 'creating', 'default'),
+            ('consuming', 'consuming', 'default'),
+            ('neutral', 'neutral', 'default'),
+            ('idle', 'idle', 'default'),
+            ('coding', 'coding', 'category'),
+            ('browsing', 'browsing', 'category')",
+    ] {
+        sqlx::query(statement).execute(&pool).await.unwrap();
+    }
+
+    // Timestamps use the same text format sqlx binds OffsetDateTime values with
+    let dataset_start = DATASET_END.unix_timestamp() - ACTIVITY_STATE_COUNT * 30;
+    sqlx::query(
+        "WITH RECURSIVE seq(n) AS (
+            SELECT 0 UNION ALL SELECT n + 1 FROM seq WHERE n + 1 < ?1
+        )
+        INSERT INTO activity_state (state, start_time, end_time, created_at)
+        SELECT
+            'ACTIVE',
+            strftime('%Y-%m-%d %H:%M:%f', ?2 + n * 30, 'unixepoch') || '+00:00',
+            strftime('%Y-%m-%d %H:%M:%f', ?2 + n * 30 + 30, 'unixepoch') || '+00:00',
+            strftime('%Y-%m-%d %H:%M:%f', ?2 + n * 30 + 30, 'unixepoch') || '+00:00'
+        FROM seq",
+    )
+    .bind(ACTIVITY_STATE_COUNT)
+    .bind(dataset_start)
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    // Every state gets a default tag, and about half also get a category tag
+    sqlx::query(
+        "INSERT INTO activity_state_tag (activity_state_id, tag_id)
+        SELECT id, CASE id % 4 WHEN 0 THEN 'creating' WHEN 1 THEN 'consuming' WHEN 2 THEN 'neutral' ELSE 'idle' END
+        FROM activity_state",
+    )
+    .execute(&pool)
+    .await
+    .unwrap();
+    sqlx::query(
+        "INSERT INTO activity_state_tag (activity_state_id, tag_id)
+        SELECT id, CASE id % 3 WHEN 0 THEN 'coding' ELSE 'browsing' END
+        FROM activity_state WHERE id % 2 = 0",
+    )
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    pool
+}
+
+async fn time_query<F, Fut>(mut query: F) -> (Duration, f64)
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = f64>,
+{
+    let mut timings = Vec::with_capacity(ITERATIONS);
+    let mut result = 0.0;
+    for _ in 0..ITERATIONS {
+        let started = Instant::now();
+        result = query().await;
+        timings.push(started.elapsed());
+    }
+    timings.sort();
+    (timings[ITERATIONS / 2], result)
+}
+
+async fn run_ranges(repo: &ActivityStateRepo, label: &str) {
+    for (range_label, days) in [("weekly", 7), ("monthly", 30)] {
+        let range_start = DATASET_END - time::Duration::days(days);
+
+        let (sql_time, sql_result) = time_query(|| async {
+            repo.calculate_tagged_duration_in_range("creating", range_start, DATASET_END)
+                .await
+                .unwrap()
+        })
+        .await;
+        let (app_time, app_result) = time_query(|| async {
+            repo.calculate_tagged_duration_in_range_app_side("creating", range_start, DATASET_END)
+                .await
+                .unwrap()
+        })
+        .await;
+
+        assert!((sql_result - app_result).abs() < 1e-6);
+        println!(
+            "{:<16} {:<8} sql {:>10.2?}  app side {:>10.2?}  ({:.1} minutes)",
+            label, range_label, sql_time, app_time, sql_result
+        );
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    let started = Instant::now();
+    let pool = create_dataset().await;
+    println!(
+        "Generated {} activity states in {:.2?}",
+        ACTIVITY_STATE_COUNT,
+        started.elapsed()
+    );
+
+    let repo = ActivityStateRepo::new(pool.clone());
+    run_ranges(&repo, "no indexes").await;
+
+    for statement in ACTIVITY_INDEX_RECOMMENDATIONS {
+        sqlx::query(statement).execute(&pool).await.unwrap();
+    }
+    sqlx::query("ANALYZE").execute(&pool).await.unwrap();
+    run_ranges(&repo, "with indexes").await;
+}
@@ -1,7 +1,18 @@
 pub mod activity_state_repo;
+pub mod app_repo;
+pub mod blocking_preference_repo;
 pub mod device_profile_repo;
 pub mod device_repo;
+pub mod flow_session_repo;
+pub mod focus_schedule_repo;
 pub mod models;
 pub mod tag_repo;
+pub mod tide_milestone_repo;
+pub mod tide_pause_repo;
+pub mod tide_progress_checkpoint_repo;
 pub mod tide_repo;
+pub mod tide_streak_snapshot_repo;
 pub mod tide_template_repo;
+pub mod tide_template_validation;
+pub mod tide_template_version_repo;
+pub mod workflow_repo;
@@ -9,6 +9,16 @@ pub struct ActivityStateRepo {
     pool: Pool<Sqlite>,
 }
 
+/// Indexes recommended on the CodeClimbers activity tables for tide progress queries
+/// `calculate_tagged_duration_in_range` filters on `end_time` and reads `start_time`, and joins tags
+/// by `activity_state_id`, so these let SQLite answer it from the indexes alone. The tables belong
+/// to the CodeClimbers database, so they are not part of the ebb migrations.
+#[cfg(any(test, feature = "test-utils"))]
+pub const ACTIVITY_INDEX_RECOMMENDATIONS: [&str; 2] = [
+    "CREATE INDEX IF NOT EXISTS idx_activity_state_end_start ON activity_state (end_time, start_time, id)",
+    "CREATE INDEX IF NOT EXISTS idx_activity_state_tag_state_tag ON activity_state_tag (activity_state_id, tag_id)",
+];
+
 impl ActivityStateRepo {
     pub fn new(pool: Pool<Sqlite>) -> Self {
         Self { pool }
@@ -63,12 +73,133 @@ impl ActivityStateRepo {
     }
 
     /// Calculate total duration in minutes for activity states with specific tag in date range
-    /// Uses application-side aggregation for better performance
+    /// Each activity state is credited in full to the range containing its end time, (start, end],
+    /// so adjacent ranges never count the same state twice and their sums match one calculation
+    /// over the combined range. States are written once they end, so attributing by end time also
+    /// credits a state at the first evaluation after it is recorded rather than dropping it.
+    ///
+    /// A state's duration is split evenly across its tag records of the requested tag's type, and
+    /// the requested tag gets one share per record. The aggregation runs in a single query; see
+    /// `ACTIVITY_INDEX_RECOMMENDATIONS` for the indexes that keep it off a full table scan.
     pub async fn calculate_tagged_duration_in_range(
         &self,
         tag_name: &str,
         start_time: OffsetDateTime,
         end_time: OffsetDateTime,
+    ) -> Result<f64> {
+        // Durations are rounded to whole milliseconds, the precision julianday() parses
+        let total_minutes: f64 = sqlx::query_scalar(
+            "WITH target_tag AS (
+                SELECT tag_type FROM tag WHERE name = ?1 LIMIT 1
+            ),
+            state_shares AS (
+                SELECT
+                    ROUND((julianday(activity_state.end_time) - julianday(activity_state.start_time)) * 86400000.0)
+                        / 60000.0 AS duration_minutes,
+                    SUM(tag.name = ?1) AS target_records,
+                    SUM(tag.tag_type = (SELECT tag_type FROM target_tag)) AS type_records
+                FROM activity_state
+                JOIN activity_state_tag ON activity_state.id = activity_state_tag.activity_state_id
+                JOIN tag ON activity_state_tag.tag_id = tag.id
+                WHERE activity_state.end_time > ?2
+                  AND activity_state.end_time <= ?3
+                GROUP BY activity_state.id
+            )
+            SELECT COALESCE(SUM(duration_minutes * target_records / type_records), 0.0)
+            FROM state_shares
+            WHERE target_records > 0 AND type_records > 0",
+        )
+        .bind(tag_name)
+        .bind(start_time)
+        .bind(end_time)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(total_minutes)
+    }
+
+    /// Calculate total duration in minutes for activity states tagged with any of a set of tags
+    /// Tags are matched by id, so renaming a tag doesn't change what is counted. Range attribution
+    /// and splitting match `calculate_tagged_duration_in_range`: within each tag type a state's
+    /// duration is split across its tag records, and the set gets one share per matching record.
+    /// When the set spans several tag types a state counts its largest share once, so a state
+    /// tagged both "creating" and "coding" is never counted twice toward the same goal.
+    pub async fn calculate_tag_set_duration_in_range(
+        &self,
+        tag_ids: &[String],
+        start_time: OffsetDateTime,
+        end_time: OffsetDateTime,
+    ) -> Result<f64> {
+        if tag_ids.is_empty() {
+            return Ok(0.0);
+        }
+
+        let tag_ids = serde_json::to_string(tag_ids)?;
+        let total_minutes: f64 = sqlx::query_scalar(
+            "WITH type_shares AS (
+                SELECT
+                    activity_state.id AS activity_state_id,
+                    ROUND((julianday(activity_state.end_time) - julianday(activity_state.start_time)) * 86400000.0)
+                        / 60000.0 AS duration_minutes,
+                    1.0 * SUM(tag.id IN (SELECT value FROM json_each(?1))) / COUNT(*) AS share
+                FROM activity_state
+                JOIN activity_state_tag ON activity_state.id = activity_state_tag.activity_state_id
+                JOIN tag ON activity_state_tag.tag_id = tag.id
+                WHERE activity_state.end_time > ?2
+                  AND activity_state.end_time <= ?3
+                GROUP BY activity_state.id, tag.tag_type
+            ),
+            state_shares AS (
+                SELECT MAX(duration_minutes) AS duration_minutes, MAX(share) AS share
+                FROM type_shares
+                GROUP BY activity_state_id
+            )
+            SELECT COALESCE(SUM(duration_minutes * share), 0.0)
+            FROM state_shares
+            WHERE share > 0",
+        )
+        .bind(tag_ids)
+        .bind(start_time)
+        .bind(end_time)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(total_minutes)
+    }
+
+    /// Sum app switches and active minutes for ACTIVE activity states in a date range
+    /// States are attributed to the range containing their end time, like tagged durations,
+    /// so totals over adjacent ranges add up. Returns (app switches, active minutes).
+    pub async fn get_app_switch_totals_in_range(
+        &self,
+        start_time: OffsetDateTime,
+        end_time: OffsetDateTime,
+    ) -> Result<(i64, f64)> {
+        let totals: (i64, f64) = sqlx::query_as(
+            "SELECT
+                COALESCE(SUM(app_switches), 0),
+                COALESCE(SUM(ROUND((julianday(end_time) - julianday(start_time)) * 86400000.0) / 60000.0), 0.0)
+            FROM activity_state
+            WHERE state = 'ACTIVE'
+              AND end_time > ?1
+              AND end_time <= ?2",
+        )
+        .bind(start_time)
+        .bind(end_time)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(totals)
+    }
+
+    /// Application-side version of `calculate_tagged_duration_in_range` that loads every tagged row
+    /// and groups them in Rust. Kept as the reference the SQL aggregation is tested and benchmarked against.
+    #[cfg(any(test, feature = "test-utils"))]
+    pub async fn calculate_tagged_duration_in_range_app_side(
+        &self,
+        tag_name: &str,
+        start_time: OffsetDateTime,
+        end_time: OffsetDateTime,
     ) -> Result<f64> {
         let function_start = std::time::Instant::now();
         log::debug!(
@@ -114,12 +245,12 @@ impl ActivityStateRepo {
             FROM activity_state
             JOIN activity_state_tag ON activity_state.id = activity_state_tag.activity_state_id
             JOIN tag ON activity_state_tag.tag_id = tag.id
-            WHERE activity_state.start_time < ?1
-              AND activity_state.end_time > ?2
+            WHERE activity_state.end_time > ?1
+              AND activity_state.end_time <= ?2
             ORDER BY activity_state.id",
         )
-        .bind(end_time)
         .bind(start_time)
+        .bind(end_time)
         .fetch_all(&self.pool)
         .await?;
         let main_query_duration = main_query_start.elapsed();
@@ -177,7 +308,7 @@ impl ActivityStateRepo {
                 activity_states.get(&activity_state_id)
             {
                 // Calculate the full duration of the activity state (not just overlap)
-                // This ensures activities discovered late due to recording timing still get full credit
+                // The state belongs to exactly one range, the one containing its end time
                 let duration_minutes = (*activity_end - *activity_start).as_seconds_f64() / 60.0;
 
                 // Get the count of tags for the target tag_type
@@ -262,6 +393,37 @@ impl ActivityStateRepo {
         Ok(())
     }
 
+    /// Check whether any activity state that ended within the range was inserted or re-tagged after `since`
+    /// Used to tell if a progress total computed up to `end_time` is stale
+    /// The CodeClimbers schema has no change marker for deletes or time edits, so deleted
+    /// `activity_state_tag` rows and edits to an existing state's `start_time`/`end_time` aren't detected
+    pub async fn has_activity_changed_since(
+        &self,
+        start_time: OffsetDateTime,
+        end_time: OffsetDateTime,
+        since: OffsetDateTime,
+    ) -> Result<bool> {
+        let changed: bool = sqlx::query_scalar(
+            "SELECT EXISTS (
+                SELECT 1
+                FROM activity_state
+                LEFT JOIN activity_state_tag ON activity_state.id = activity_state_tag.activity_state_id
+                WHERE activity_state.end_time > ?1
+                  AND activity_state.end_time <= ?2
+                  AND (julianday(activity_state.created_at) > julianday(?3)
+                    OR julianday(activity_state_tag.created_at) > julianday(?3)
+                    OR julianday(activity_state_tag.updated_at) > julianday(?3))
+            )",
+        )
+        .bind(start_time)
+        .bind(end_time)
+        .bind(since)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(changed)
+    }
+
     /// Get a single activity state by ID
     pub async fn get_activity_state(&self, id: i64) -> Result<Option<ActivityState>> {
         let state =
@@ -279,6 +441,7 @@ mod tests {
     use super::*;
     use crate::db_manager;
     use sqlx::{Pool, Sqlite};
+    use crate::test_utils::TestRng;
     use time::macros::datetime;
     /// Clean all activity state related data for testing
     pub async fn cleanup_activity_state_data(pool: &Pool<Sqlite>) -> Result<()> {
@@ -1234,8 +1397,344 @@ mod tests {
         Ok(())
     }
 
+    // ===== CALCULATE_TAG_SET_DURATION_IN_RANGE TESTS =====
+
+    #[tokio::test]
+    async fn test_calculate_tag_set_duration_by_category_ids() -> Result<()> {
+        let repo = setup_test_repo().await?;
+        let pool = &repo.pool;
+
+        // 10:00-11:00 creating + neutral + coding, 12:00-13:00 coding + meeting, 14:00-14:30 meeting
+        let states = [
+            (1, datetime!(2025-01-03 10:00:00 UTC), datetime!(2025-01-03 11:00:00 UTC), vec!["creating-tag-id", "neutral-tag-id", "coding-tag-id"]),
+            (2, datetime!(2025-01-03 12:00:00 UTC), datetime!(2025-01-03 13:00:00 UTC), vec!["coding-tag-id", "meeting-tag-id"]),
+            (3, datetime!(2025-01-03 14:00:00 UTC), datetime!(2025-01-03 14:30:00 UTC), vec!["meeting-tag-id"]),
+        ];
+        for (id, start, end, tag_ids) in states {
+            sqlx::query(
+                "INSERT INTO activity_state (id, state, activity_type, start_time, end_time, created_at)
+                 VALUES (?1, 'ACTIVE', 1, ?2, ?3, ?3)",
+            )
+            .bind(id)
+            .bind(start)
+            .bind(end)
+            .execute(pool)
+            .await?;
+            for tag_id in tag_ids {
+                sqlx::query(
+                    "INSERT INTO activity_state_tag (activity_state_id, tag_id, app_tag_id, created_at, updated_at)
+                     VALUES (?1, ?2, NULL, datetime('now'), datetime('now'))",
+                )
+                .bind(id)
+                .bind(tag_id)
+                .execute(pool)
+                .await?;
+            }
+        }
+
+        let query_start = datetime!(2025-01-03 00:00:00 UTC);
+        let query_end = datetime!(2025-01-04 00:00:00 UTC);
+        let duration = |tag_ids: &[&str]| {
+            let tag_ids: Vec<String> = tag_ids.iter().map(|id| id.to_string()).collect();
+            let repo = &repo;
+            async move {
+                repo.calculate_tag_set_duration_in_range(&tag_ids, query_start, query_end)
+                    .await
+                    .unwrap()
+            }
+        };
+
+        // Coding is the only category on the first state and shares the second with meeting
+        assert_eq!(duration(&["coding-tag-id"]).await, 90.0);
+        assert_eq!(duration(&["meeting-tag-id"]).await, 60.0);
+        // Both halves of the second state belong to the set
+        assert_eq!(duration(&["coding-tag-id", "meeting-tag-id"]).await, 150.0);
+        // Default and category tags on the same state count it once, at its largest share
+        assert_eq!(duration(&["creating-tag-id", "coding-tag-id"]).await, 90.0);
+        assert_eq!(duration(&["creating-tag-id"]).await, 30.0);
+        assert_eq!(duration(&["missing-tag-id"]).await, 0.0);
+        assert_eq!(duration(&[]).await, 0.0);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_single_tag_set_matches_tag_name() -> Result<()> {
+        let day_start = datetime!(2025-01-06 00:00:00 UTC);
+        let range_end = day_start + time::Duration::hours(27);
+
+        for seed in 0..24 {
+            let repo = setup_test_repo().await?;
+            let mut rng = TestRng::new(seed);
+            insert_random_activity(&repo.pool, &mut rng, day_start).await?;
+
+            for (tag_name, tag_id) in [("creating", "creating-tag-id"), ("coding", "coding-tag-id")] {
+                let by_name = repo
+                    .calculate_tagged_duration_in_range(tag_name, day_start, range_end)
+                    .await?;
+                let by_id = repo
+                    .calculate_tag_set_duration_in_range(&[tag_id.to_string()], day_start, range_end)
+                    .await?;
+
+                assert!(
+                    (by_name - by_id).abs() < 1e-6,
+                    "seed {}: '{}' by name {} != by id {}",
+                    seed,
+                    tag_name,
+                    by_name,
+                    by_id
+                );
+            }
+        }
+
+        Ok(())
+    }
+
     // ===== OTHER REPOSITORY TESTS =====
 
+    // ===== INCREMENTAL ACCOUNTING PROPERTIES =====
+    // Randomized over seeded cases: splitting a range anywhere must not change the total
+
+    /// Insert random activity states within a day, each with 1-3 random default/category tags
+    async fn insert_random_activity(pool: &Pool<Sqlite>, rng: &mut TestRng, day_start: OffsetDateTime) -> Result<()> {
+        let tag_ids = ["creating-tag-id", "consuming-tag-id", "coding-tag-id", "browsing-tag-id"];
+        let state_count = 1 + rng.next_below(25);
+
+        for id in 1..=state_count as i64 {
+            let start = day_start + time::Duration::seconds(rng.next_below(24 * 60 * 60) as i64);
+            let end = start + time::Duration::seconds(1 + rng.next_below(2 * 60 * 60) as i64);
+            sqlx::query(
+                "INSERT INTO activity_state (id, state, activity_type, start_time, end_time, created_at)
+                 VALUES (?1, 'ACTIVE', 1, ?2, ?3, ?3)",
+            )
+            .bind(id)
+            .bind(start)
+            .bind(end)
+            .execute(pool)
+            .await?;
+
+            let first_tag = rng.next_below(tag_ids.len() as u64) as usize;
+            let tag_count = 1 + rng.next_below(3) as usize;
+            for offset in 0..tag_count {
+                sqlx::query(
+                    "INSERT OR IGNORE INTO activity_state_tag (activity_state_id, tag_id, app_tag_id, created_at, updated_at)
+                     VALUES (?1, ?2, '', ?3, ?3)",
+                )
+                .bind(id)
+                .bind(tag_ids[(first_tag + offset) % tag_ids.len()])
+                .bind(end)
+                .execute(pool)
+                .await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_incremental_ranges_sum_to_full_range() -> Result<()> {
+        let day_start = datetime!(2025-01-06 00:00:00 UTC);
+        // Extends past the day so states ending after midnight are included in the full range
+        let range_end = day_start + time::Duration::hours(27);
+
+        for seed in 0..48 {
+            let repo = setup_test_repo().await?;
+            let mut rng = TestRng::new(seed);
+            insert_random_activity(&repo.pool, &mut rng, day_start).await?;
+
+            // Random split points, including ones that land inside activity states
+            let mut boundaries: Vec<OffsetDateTime> = (0..1 + rng.next_below(12))
+                .map(|_| day_start + time::Duration::seconds(rng.next_below(27 * 60 * 60) as i64))
+                .collect();
+            boundaries.push(day_start);
+            boundaries.push(range_end);
+            boundaries.sort();
+
+            for tag_name in ["creating", "consuming", "coding"] {
+                let full = repo
+                    .calculate_tagged_duration_in_range(tag_name, day_start, range_end)
+                    .await?;
+
+                let mut incremental = 0.0;
+                for window in boundaries.windows(2) {
+                    incremental += repo
+                        .calculate_tagged_duration_in_range(tag_name, window[0], window[1])
+                        .await?;
+                }
+
+                assert!(
+                    (incremental - full).abs() < 1e-6,
+                    "seed {}: '{}' incremental {} != full {} (boundaries {:?})",
+                    seed,
+                    tag_name,
+                    incremental,
+                    full,
+                    boundaries
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_sql_aggregation_matches_app_side() -> Result<()> {
+        let day_start = datetime!(2025-01-06 00:00:00 UTC);
+        let range_end = day_start + time::Duration::hours(27);
+
+        for seed in 0..48 {
+            let repo = setup_test_repo().await?;
+            let mut rng = TestRng::new(seed);
+            insert_random_activity(&repo.pool, &mut rng, day_start).await?;
+
+            let range_start = day_start + time::Duration::seconds(rng.next_below(12 * 60 * 60) as i64);
+            for tag_name in ["creating", "consuming", "coding", "browsing", "idle", "missing"] {
+                let sql = repo
+                    .calculate_tagged_duration_in_range(tag_name, range_start, range_end)
+                    .await?;
+                let app_side = repo
+                    .calculate_tagged_duration_in_range_app_side(tag_name, range_start, range_end)
+                    .await?;
+
+                assert!(
+                    (sql - app_side).abs() < 1e-6,
+                    "seed {}: '{}' sql {} != app side {}",
+                    seed,
+                    tag_name,
+                    sql,
+                    app_side
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_straddling_state_is_counted_once() -> Result<()> {
+        let repo = setup_test_repo().await?;
+        let pool = &repo.pool;
+
+        // 10:00-10:30, split by an evaluation at 10:10
+        sqlx::query(
+            "INSERT INTO activity_state (id, state, activity_type, start_time, end_time, created_at)
+             VALUES (1, 'ACTIVE', 1, ?1, ?2, ?2)",
+        )
+        .bind(datetime!(2025-01-06 10:00:00 UTC))
+        .bind(datetime!(2025-01-06 10:30:00 UTC))
+        .execute(pool)
+        .await?;
+        sqlx::query(
+            "INSERT INTO activity_state_tag (activity_state_id, tag_id, app_tag_id, created_at, updated_at)
+             VALUES (1, 'creating-tag-id', '', ?1, ?1)",
+        )
+        .bind(datetime!(2025-01-06 10:30:00 UTC))
+        .execute(pool)
+        .await?;
+
+        let before = repo
+            .calculate_tagged_duration_in_range("creating", datetime!(2025-01-06 09:00:00 UTC), datetime!(2025-01-06 10:10:00 UTC))
+            .await?;
+        let after = repo
+            .calculate_tagged_duration_in_range("creating", datetime!(2025-01-06 10:10:00 UTC), datetime!(2025-01-06 11:00:00 UTC))
+            .await?;
+
+        // Credited in full to the range containing its end
+        assert_eq!(before, 0.0);
+        assert!((after - 30.0).abs() < 0.01);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_has_activity_changed_since() -> Result<()> {
+        let repo = setup_test_repo().await?;
+        let pool = &repo.pool;
+        let checkpoint_saved_at = datetime!(2025-01-01 12:00:00 UTC);
+
+        // Recorded before the checkpoint was saved
+        sqlx::query(
+            "INSERT INTO activity_state (id, state, activity_type, start_time, end_time, created_at)
+             VALUES (1, 'ACTIVE', 1, ?1, ?2, ?3)",
+        )
+        .bind(datetime!(2025-01-01 10:00:00 UTC))
+        .bind(datetime!(2025-01-01 10:30:00 UTC))
+        .bind(datetime!(2025-01-01 10:30:00 UTC))
+        .execute(pool)
+        .await?;
+        sqlx::query(
+            "INSERT INTO activity_state_tag (activity_state_id, tag_id, created_at, updated_at)
+             VALUES (1, 'creating-tag-id', ?1, ?1)",
+        )
+        .bind(datetime!(2025-01-01 10:30:00 UTC))
+        .execute(pool)
+        .await?;
+
+        let range_start = datetime!(2025-01-01 09:00:00 UTC);
+        let range_end = datetime!(2025-01-01 11:00:00 UTC);
+        assert!(!repo.has_activity_changed_since(range_start, range_end, checkpoint_saved_at).await?);
+
+        // Rows ending after the range are picked up incrementally and don't count as changes
+        sqlx::query(
+            "INSERT INTO activity_state (id, state, activity_type, start_time, end_time, created_at)
+             VALUES (2, 'ACTIVE', 1, ?1, ?2, ?3)",
+        )
+        .bind(datetime!(2025-01-01 10:50:00 UTC))
+        .bind(datetime!(2025-01-01 11:10:00 UTC))
+        .bind(datetime!(2025-01-01 12:30:00 UTC))
+        .execute(pool)
+        .await?;
+        assert!(!repo.has_activity_changed_since(range_start, range_end, checkpoint_saved_at).await?);
+
+        // Re-tagging a row inside the range is a change
+        sqlx::query("UPDATE activity_state_tag SET tag_id = 'consuming-tag-id', updated_at = ?1 WHERE activity_state_id = 1")
+            .bind(datetime!(2025-01-01 12:15:00 UTC))
+            .execute(pool)
+            .await?;
+        assert!(repo.has_activity_changed_since(range_start, range_end, checkpoint_saved_at).await?);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_get_app_switch_totals_in_range() -> Result<()> {
+        let repo = setup_test_repo().await?;
+        let pool = &repo.pool;
+
+        for (id, state, app_switches, start, end) in [
+            (1, "ACTIVE", 12, datetime!(2025-01-01 09:00:00 UTC), datetime!(2025-01-01 09:30:00 UTC)),
+            (2, "INACTIVE", 0, datetime!(2025-01-01 09:30:00 UTC), datetime!(2025-01-01 10:00:00 UTC)),
+            (3, "ACTIVE", 8, datetime!(2025-01-01 10:00:00 UTC), datetime!(2025-01-01 10:30:00 UTC)),
+            (4, "ACTIVE", 50, datetime!(2025-01-01 11:30:00 UTC), datetime!(2025-01-01 12:00:00 UTC)),
+        ] {
+            sqlx::query(
+                "INSERT INTO activity_state (id, state, app_switches, activity_type, start_time, end_time, created_at)
+                 VALUES (?1, ?2, ?3, 1, ?4, ?5, ?5)",
+            )
+            .bind(id)
+            .bind(state)
+            .bind(app_switches)
+            .bind(start)
+            .bind(end)
+            .execute(pool)
+            .await?;
+        }
+
+        let (app_switches, active_minutes) = repo
+            .get_app_switch_totals_in_range(datetime!(2025-01-01 09:00:00 UTC), datetime!(2025-01-01 11:00:00 UTC))
+            .await?;
+        assert_eq!(app_switches, 20);
+        assert_eq!(active_minutes, 60.0);
+
+        let (app_switches, active_minutes) = repo
+            .get_app_switch_totals_in_range(datetime!(2025-01-02 00:00:00 UTC), datetime!(2025-01-03 00:00:00 UTC))
+            .await?;
+        assert_eq!(app_switches, 0);
+        assert_eq!(active_minutes, 0.0);
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_activity_state_repo_creation() -> Result<()> {
         let _repo = setup_test_repo().await?;
@@ -0,0 +1,165 @@
+use sqlx::{Pool, Sqlite};
+
+use crate::db::models::blocking_preference::BlockableApp;
+
+pub type Result<T> = std::result::Result<T, Box<dyn std::error::Error + Send + Sync>>;
+
+/// Reads apps from the CodeClimbers database
+pub struct AppRepo {
+    pool: Pool<Sqlite>,
+}
+
+impl AppRepo {
+    pub fn new(pool: Pool<Sqlite>) -> Self {
+        Self { pool }
+    }
+
+    /// Get the distinct apps that are either listed by id or tagged with any of the category tag ids
+    /// Only category tags expand to their apps, like the frontend's category selection
+    /// Ids are passed as JSON arrays so any number of them can be bound
+    pub async fn get_blockable_apps(
+        &self,
+        app_ids: &[String],
+        tag_ids: &[String],
+    ) -> Result<Vec<BlockableApp>> {
+        if app_ids.is_empty() && tag_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let apps = sqlx::query_as::<_, BlockableApp>(
+            "SELECT DISTINCT a.app_external_id, a.is_browser
+             FROM app a
+             WHERE a.app_external_id IS NOT NULL
+               AND (
+                 a.id IN (SELECT value FROM json_each(?1))
+                 OR EXISTS (
+                   SELECT 1 FROM app_tag at
+                   JOIN tag t ON t.id = at.tag_id
+                   WHERE at.app_id = a.id
+                     AND t.tag_type = 'category'
+                     AND at.tag_id IN (SELECT value FROM json_each(?2))
+                 )
+               )
+             ORDER BY a.app_external_id"
+        )
+        .bind(serde_json::to_string(app_ids)?)
+        .bind(serde_json::to_string(tag_ids)?)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(apps)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db_manager;
+
+    /// Create the CodeClimbers app tables with a few apps
+    /// YouTube and Reddit are in the "entertainment" category, Reddit also in "social", and Slack
+    /// has the default "creating" tag
+    async fn seed_apps(pool: &Pool<Sqlite>) -> Result<()> {
+        sqlx::query(
+            "CREATE TABLE IF NOT EXISTS app (
+                id TEXT PRIMARY KEY NOT NULL,
+                name TEXT,
+                app_external_id TEXT,
+                platform TEXT,
+                is_browser BOOLEAN NOT NULL DEFAULT FALSE
+            )"
+        )
+        .execute(pool)
+        .await?;
+        sqlx::query(
+            "CREATE TABLE IF NOT EXISTS app_tag (
+                id TEXT PRIMARY KEY NOT NULL,
+                app_id TEXT NOT NULL,
+                tag_id TEXT NOT NULL,
+                weight REAL NOT NULL DEFAULT 1
+            )"
+        )
+        .execute(pool)
+        .await?;
+        sqlx::query(
+            "CREATE TABLE IF NOT EXISTS tag (
+                id TEXT PRIMARY KEY NOT NULL,
+                name TEXT NOT NULL,
+                tag_type TEXT NOT NULL
+            )"
+        )
+        .execute(pool)
+        .await?;
+
+        for (id, tag_type) in [
+            ("entertainment", "category"),
+            ("social", "category"),
+            ("communication", "category"),
+            ("creating", "default"),
+        ] {
+            sqlx::query("INSERT INTO tag (id, name, tag_type) VALUES (?1, ?1, ?2)")
+                .bind(id)
+                .bind(tag_type)
+                .execute(pool)
+                .await?;
+        }
+        for (id, external_id, is_browser) in [
+            ("slack", "com.tinyspeck.slackmacgap", false),
+            ("youtube", "youtube.com", true),
+            ("reddit", "reddit.com", true),
+            ("chrome", "com.google.Chrome", false),
+        ] {
+            sqlx::query("INSERT INTO app (id, name, app_external_id, is_browser) VALUES (?1, ?1, ?2, ?3)")
+                .bind(id)
+                .bind(external_id)
+                .bind(is_browser)
+                .execute(pool)
+                .await?;
+        }
+        for (id, app_id, tag_id) in [
+            ("at-1", "youtube", "entertainment"),
+            ("at-2", "reddit", "entertainment"),
+            ("at-3", "reddit", "social"),
+            ("at-4", "slack", "communication"),
+            ("at-5", "slack", "creating"),
+        ] {
+            sqlx::query("INSERT INTO app_tag (id, app_id, tag_id) VALUES (?1, ?2, ?3)")
+                .bind(id)
+                .bind(app_id)
+                .bind(tag_id)
+                .execute(pool)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_get_blockable_apps_expands_tags_without_duplicates() -> Result<()> {
+        let pool = db_manager::create_test_db().await;
+        seed_apps(&pool).await?;
+        let repo = AppRepo::new(pool);
+
+        let apps = repo
+            .get_blockable_apps(
+                &["reddit".to_string(), "chrome".to_string()],
+                &["entertainment".to_string(), "social".to_string()],
+            )
+            .await?;
+        assert_eq!(
+            apps,
+            vec![
+                BlockableApp { app_external_id: "com.google.Chrome".to_string(), is_browser: false },
+                BlockableApp { app_external_id: "reddit.com".to_string(), is_browser: true },
+                BlockableApp { app_external_id: "youtube.com".to_string(), is_browser: true },
+            ]
+        );
+
+        assert!(repo.get_blockable_apps(&[], &[]).await?.is_empty());
+        assert!(repo.get_blockable_apps(&[], &["unknown".to_string()]).await?.is_empty());
+        // Default tags like "creating" aren't categories and don't block their apps
+        assert!(repo.get_blockable_apps(&[], &["creating".to_string()]).await?.is_empty());
+
+        Ok(())
+    }
+}
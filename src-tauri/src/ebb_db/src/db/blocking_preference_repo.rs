@@ -0,0 +1,93 @@
+use sqlx::{Pool, Sqlite};
+
+use crate::db::models::blocking_preference::BlockingPreference;
+
+pub type Result<T> = std::result::Result<T, Box<dyn std::error::Error + Send + Sync>>;
+
+pub struct BlockingPreferenceRepo {
+    pool: Pool<Sqlite>,
+}
+
+impl BlockingPreferenceRepo {
+    pub fn new(pool: Pool<Sqlite>) -> Self {
+        Self { pool }
+    }
+
+    pub async fn get_workflow_preferences(&self, workflow_id: &str) -> Result<Vec<BlockingPreference>> {
+        let preferences = sqlx::query_as::<_, BlockingPreference>(
+            "SELECT id, app_id, tag_id, workflow_id, created_at
+             FROM blocking_preference
+             WHERE workflow_id = ?1"
+        )
+        .bind(workflow_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(preferences)
+    }
+
+    /// Replace all of a workflow's preferences, like saving the workflow in the frontend does
+    pub async fn save_workflow_preferences(
+        &self,
+        workflow_id: &str,
+        preferences: &[BlockingPreference],
+    ) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query("DELETE FROM blocking_preference WHERE workflow_id = ?1")
+            .bind(workflow_id)
+            .execute(&mut *tx)
+            .await?;
+
+        for preference in preferences {
+            sqlx::query(
+                "INSERT INTO blocking_preference (id, app_id, tag_id, workflow_id, created_at)
+                 VALUES (?1, ?2, ?3, ?4, strftime('%Y-%m-%dT%H:%M:%fZ', ?5))"
+            )
+            .bind(&preference.id)
+            .bind(&preference.app_id)
+            .bind(&preference.tag_id)
+            .bind(workflow_id)
+            .bind(preference.created_at)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db_manager;
+
+    #[tokio::test]
+    async fn test_save_workflow_preferences_replaces_existing() -> Result<()> {
+        let pool = db_manager::create_test_db().await;
+        let repo = BlockingPreferenceRepo::new(pool);
+
+        repo.save_workflow_preferences(
+            "workflow-1",
+            &[
+                BlockingPreference::for_app("workflow-1", "app-1"),
+                BlockingPreference::for_tag("workflow-1", "tag-1"),
+            ],
+        )
+        .await?;
+        repo.save_workflow_preferences("workflow-2", &[BlockingPreference::for_app("workflow-2", "app-1")])
+            .await?;
+
+        repo.save_workflow_preferences("workflow-1", &[BlockingPreference::for_tag("workflow-1", "tag-2")])
+            .await?;
+
+        let preferences = repo.get_workflow_preferences("workflow-1").await?;
+        assert_eq!(preferences.len(), 1);
+        assert_eq!(preferences[0].tag_id.as_deref(), Some("tag-2"));
+        assert_eq!(preferences[0].app_id, None);
+        assert_eq!(repo.get_workflow_preferences("workflow-2").await?.len(), 1);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,293 @@
+use sqlx::{Pool, Sqlite};
+use time::OffsetDateTime;
+
+use crate::db::models::flow_session::FlowSession;
+
+pub type Result<T> = std::result::Result<T, Box<dyn std::error::Error + Send + Sync>>;
+
+pub struct FlowSessionRepo {
+    pool: Pool<Sqlite>,
+}
+
+impl FlowSessionRepo {
+    pub fn new(pool: Pool<Sqlite>) -> Self {
+        Self { pool }
+    }
+
+    /// Insert a session
+    /// Times are written as ISO strings like the frontend's `toISOString()`, so rows from either
+    /// side read the same. Fails if another session is in progress (`idx_single_active_session`).
+    pub async fn create_session(&self, session: &FlowSession) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO flow_session (
+                id, objective, self_score, start, end, stats, duration, workflow_id, type,
+                focus_schedule_id, created_at, updated_at
+            ) VALUES (
+                ?1, ?2, ?3, strftime('%Y-%m-%dT%H:%M:%fZ', ?4), strftime('%Y-%m-%dT%H:%M:%fZ', ?5),
+                ?6, ?7, ?8, ?9, ?10,
+                strftime('%Y-%m-%dT%H:%M:%fZ', ?11), strftime('%Y-%m-%dT%H:%M:%fZ', ?12)
+            )"
+        )
+        .bind(&session.id)
+        .bind(&session.objective)
+        .bind(session.self_score)
+        .bind(session.start)
+        .bind(session.end)
+        .bind(&session.stats)
+        .bind(session.duration)
+        .bind(&session.workflow_id)
+        .bind(session.session_type)
+        .bind(&session.focus_schedule_id)
+        .bind(session.created_at)
+        .bind(session.updated_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// End a session that is still in progress
+    /// Returns false if the session doesn't exist or has already ended
+    pub async fn end_session(&self, id: &str, end: OffsetDateTime) -> Result<bool> {
+        let result = sqlx::query(
+            "UPDATE flow_session
+             SET end = strftime('%Y-%m-%dT%H:%M:%fZ', ?2), updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', ?3)
+             WHERE id = ?1 AND end IS NULL"
+        )
+        .bind(id)
+        .bind(end)
+        .bind(OffsetDateTime::now_utc())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn get_session(&self, id: &str) -> Result<Option<FlowSession>> {
+        let session = sqlx::query_as::<_, FlowSession>("SELECT * FROM flow_session WHERE id = ?1")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(session)
+    }
+
+    /// Get the session in progress, if any
+    pub async fn get_active_session(&self) -> Result<Option<FlowSession>> {
+        let session = sqlx::query_as::<_, FlowSession>(
+            "SELECT * FROM flow_session WHERE end IS NULL LIMIT 1"
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(session)
+    }
+
+    /// Get the sessions overlapping a date range, including one still in progress, oldest first
+    pub async fn get_sessions_in_range(
+        &self,
+        start_time: OffsetDateTime,
+        end_time: OffsetDateTime,
+    ) -> Result<Vec<FlowSession>> {
+        let sessions = sqlx::query_as::<_, FlowSession>(
+            "SELECT * FROM flow_session
+             WHERE julianday(start) < julianday(?2)
+               AND (end IS NULL OR julianday(end) > julianday(?1))
+             ORDER BY julianday(start) ASC"
+        )
+        .bind(start_time)
+        .bind(end_time)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(sessions)
+    }
+
+    /// Count finished flow sessions and sum their minutes in a date range
+    /// Sessions are attributed to the range containing their end, so totals over adjacent
+    /// ranges add up and sessions still running aren't counted. The frontend writes ISO
+    /// timestamps, so times are compared with julianday(). Returns (sessions, minutes).
+    pub async fn get_session_totals_in_range(
+        &self,
+        start_time: OffsetDateTime,
+        end_time: OffsetDateTime,
+    ) -> Result<(i64, f64)> {
+        let totals: (i64, f64) = sqlx::query_as(
+            "SELECT
+                COUNT(*),
+                COALESCE(SUM(ROUND((julianday(end) - julianday(start)) * 86400000.0) / 60000.0), 0.0)
+            FROM flow_session
+            WHERE end IS NOT NULL
+              AND julianday(end) > julianday(?1)
+              AND julianday(end) <= julianday(?2)",
+        )
+        .bind(start_time)
+        .bind(end_time)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(totals)
+    }
+
+    /// Check whether any session that ended within the range was created or updated after `since`
+    /// Used to tell if a progress total computed up to `end_time` is stale. Deleted sessions, and
+    /// sessions whose end was moved out of the range, aren't detected.
+    pub async fn has_session_changed_since(
+        &self,
+        start_time: OffsetDateTime,
+        end_time: OffsetDateTime,
+        since: OffsetDateTime,
+    ) -> Result<bool> {
+        let changed: bool = sqlx::query_scalar(
+            "SELECT EXISTS (
+                SELECT 1
+                FROM flow_session
+                WHERE end IS NOT NULL
+                  AND julianday(end) > julianday(?1)
+                  AND julianday(end) <= julianday(?2)
+                  AND (julianday(created_at) > julianday(?3)
+                    OR julianday(updated_at) > julianday(?3))
+            )",
+        )
+        .bind(start_time)
+        .bind(end_time)
+        .bind(since)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(changed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::models::flow_session::FlowSessionType;
+    use crate::db_manager;
+    use time::macros::datetime;
+
+    #[tokio::test]
+    async fn test_get_session_totals_in_range() -> Result<()> {
+        let pool = db_manager::create_test_db().await;
+        let repo = FlowSessionRepo::new(pool.clone());
+
+        // Written by the frontend as ISO strings
+        for (id, start, end) in [
+            ("session-1", "2025-01-06T09:00:00.000Z", Some("2025-01-06T09:45:00.000Z")),
+            ("session-2", "2025-01-06T10:30:00.000Z", Some("2025-01-06T11:00:00.000Z")),
+            ("session-3", "2025-01-06T23:30:00.000Z", Some("2025-01-07T00:30:00.000Z")),
+            ("session-4", "2025-01-06T13:00:00.000Z", None),
+        ] {
+            sqlx::query("INSERT INTO flow_session (id, objective, start, end) VALUES (?1, 'focus', ?2, ?3)")
+                .bind(id)
+                .bind(start)
+                .bind(end)
+                .execute(&pool)
+                .await?;
+        }
+
+        let (sessions, minutes) = repo
+            .get_session_totals_in_range(datetime!(2025-01-06 00:00 UTC), datetime!(2025-01-07 00:00 UTC))
+            .await?;
+        assert_eq!(sessions, 2);
+        assert_eq!(minutes, 75.0);
+
+        // The session crossing midnight belongs to the day it ended
+        let (sessions, minutes) = repo
+            .get_session_totals_in_range(datetime!(2025-01-07 00:00 UTC), datetime!(2025-01-08 00:00 UTC))
+            .await?;
+        assert_eq!(sessions, 1);
+        assert_eq!(minutes, 60.0);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_has_session_changed_since() -> Result<()> {
+        let pool = db_manager::create_test_db().await;
+        let repo = FlowSessionRepo::new(pool.clone());
+
+        sqlx::query(
+            "INSERT INTO flow_session (id, objective, start, end, created_at, updated_at)
+             VALUES ('session-1', 'focus', '2025-01-06T09:00:00.000Z', '2025-01-06T09:45:00.000Z',
+                     '2025-01-06T09:00:00.000Z', '2025-01-06T09:45:00.000Z')"
+        )
+        .execute(&pool)
+        .await?;
+
+        let (start, end) = (datetime!(2025-01-06 00:00 UTC), datetime!(2025-01-07 00:00 UTC));
+        assert!(!repo.has_session_changed_since(start, end, datetime!(2025-01-06 10:00 UTC)).await?);
+        assert!(repo.has_session_changed_since(start, end, datetime!(2025-01-06 09:30 UTC)).await?);
+
+        // An edit from the frontend stamps updated_at
+        sqlx::query("UPDATE flow_session SET end = '2025-01-06T09:30:00.000Z', updated_at = '2025-01-06T12:00:00.000Z' WHERE id = 'session-1'")
+            .execute(&pool)
+            .await?;
+        assert!(repo.has_session_changed_since(start, end, datetime!(2025-01-06 10:00 UTC)).await?);
+
+        // Changes to sessions ending outside the range don't count
+        assert!(!repo
+            .has_session_changed_since(datetime!(2025-01-07 00:00 UTC), datetime!(2025-01-08 00:00 UTC), datetime!(2025-01-06 10:00 UTC))
+            .await?);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_session_lifecycle_round_trips_with_frontend_rows() -> Result<()> {
+        let pool = db_manager::create_test_db().await;
+        let repo = FlowSessionRepo::new(pool.clone());
+
+        // A finished session written by the frontend
+        sqlx::query(
+            "INSERT INTO flow_session (id, objective, self_score, start, end, duration, type)
+             VALUES ('frontend', 'Plan', 0, '2025-01-06T08:00:00.000Z', '2025-01-06T08:30:00.000Z', 1800, 'manual')"
+        )
+        .execute(&pool)
+        .await?;
+
+        let session = FlowSession::new(
+            "Write".to_string(),
+            FlowSessionType::Smart,
+            Some("workflow-1".to_string()),
+            Some(1500),
+            datetime!(2025-01-06 09:00 UTC),
+        );
+        repo.create_session(&session).await?;
+
+        let start: String = sqlx::query_scalar("SELECT start FROM flow_session WHERE id = ?1")
+            .bind(&session.id)
+            .fetch_one(&pool)
+            .await?;
+        assert_eq!(start, "2025-01-06T09:00:00.000Z");
+
+        let active = repo.get_active_session().await?.expect("session should be active");
+        assert_eq!(active.id, session.id);
+        assert_eq!(active.session_type, Some(FlowSessionType::Smart));
+        assert_eq!(active.stats, "{}");
+
+        // Only one session can be in progress
+        let second = FlowSession::new("Read".to_string(), FlowSessionType::Manual, None, None, datetime!(2025-01-06 09:10 UTC));
+        assert!(repo.create_session(&second).await.is_err());
+
+        let in_range = repo
+            .get_sessions_in_range(datetime!(2025-01-06 08:15 UTC), datetime!(2025-01-06 12:00 UTC))
+            .await?;
+        assert_eq!(
+            in_range.iter().map(|s| s.id.as_str()).collect::<Vec<_>>(),
+            vec!["frontend", session.id.as_str()]
+        );
+        assert_eq!(in_range[0].end, Some(datetime!(2025-01-06 08:30 UTC)));
+        assert_eq!(in_range[0].duration, Some(1800));
+
+        assert!(repo.end_session(&session.id, datetime!(2025-01-06 09:25 UTC)).await?);
+        assert!(!repo.end_session(&session.id, datetime!(2025-01-06 09:30 UTC)).await?);
+        assert!(repo.get_active_session().await?.is_none());
+        assert_eq!(
+            repo.get_session(&session.id).await?.unwrap().end,
+            Some(datetime!(2025-01-06 09:25 UTC))
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,176 @@
+use sqlx::{FromRow, Pool, Sqlite};
+use time::OffsetDateTime;
+
+use crate::db::models::focus_schedule::FocusSchedule;
+
+pub type Result<T> = std::result::Result<T, Box<dyn std::error::Error + Send + Sync>>;
+
+/// Focus schedules are also written by the frontend, so times are stored as ISO strings like its
+/// `toISOString()`
+pub struct FocusScheduleRepo {
+    pool: Pool<Sqlite>,
+}
+
+impl FocusScheduleRepo {
+    pub fn new(pool: Pool<Sqlite>) -> Self {
+        Self { pool }
+    }
+
+    pub async fn create_schedule(&self, schedule: &FocusSchedule) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO focus_schedule (
+                id, label, scheduled_time, workflow_id, recurrence_settings, is_active, created_at, updated_at
+             )
+             VALUES (
+                ?1, ?2, strftime('%Y-%m-%dT%H:%M:%fZ', ?3), ?4, ?5, ?6,
+                strftime('%Y-%m-%dT%H:%M:%fZ', ?7),
+                strftime('%Y-%m-%dT%H:%M:%fZ', ?8)
+             )"
+        )
+        .bind(&schedule.id)
+        .bind(&schedule.label)
+        .bind(schedule.scheduled_time)
+        .bind(&schedule.workflow_id)
+        .bind(&schedule.recurrence_settings)
+        .bind(schedule.is_active)
+        .bind(schedule.created_at)
+        .bind(schedule.updated_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn get_schedule(&self, id: &str) -> Result<Option<FocusSchedule>> {
+        let schedule = sqlx::query_as::<_, FocusSchedule>("SELECT * FROM focus_schedule WHERE id = ?1")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(schedule)
+    }
+
+    /// Get the active schedules, oldest first
+    /// Rows that can't be decoded, like ones with malformed recurrence settings from the frontend,
+    /// are logged and skipped so they don't stop the other schedules from running
+    pub async fn get_active_schedules(&self) -> Result<Vec<FocusSchedule>> {
+        let rows = sqlx::query(
+            "SELECT * FROM focus_schedule WHERE is_active = 1 ORDER BY scheduled_time ASC"
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let schedules = rows
+            .iter()
+            .filter_map(|row| match FocusSchedule::from_row(row) {
+                Ok(schedule) => Some(schedule),
+                Err(e) => {
+                    let id: Option<String> = sqlx::Row::try_get(row, "id").ok();
+                    log::warn!("Skipping focus schedule {}: {}", id.as_deref().unwrap_or("<unknown>"), e);
+                    None
+                }
+            })
+            .collect();
+
+        Ok(schedules)
+    }
+
+    /// Deactivate a schedule, which is how the frontend deletes them
+    pub async fn deactivate_schedule(&self, id: &str) -> Result<()> {
+        sqlx::query(
+            "UPDATE focus_schedule
+             SET is_active = 0, updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', ?2)
+             WHERE id = ?1"
+        )
+        .bind(id)
+        .bind(OffsetDateTime::now_utc())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::models::focus_schedule::{RecurrenceSettings, RecurrenceType};
+    use crate::db::models::workflow::{Workflow, WorkflowSettings};
+    use crate::db::workflow_repo::WorkflowRepo;
+    use crate::db_manager;
+    use time::macros::datetime;
+
+    #[tokio::test]
+    async fn test_get_active_schedules_with_frontend_rows() -> Result<()> {
+        let pool = db_manager::create_test_db().await;
+        let repo = FocusScheduleRepo::new(pool.clone());
+        let workflow = Workflow::new("Deep Work".to_string(), WorkflowSettings::default());
+        WorkflowRepo::new(pool.clone()).create_workflow(&workflow).await?;
+
+        // Saved by the frontend, which leaves created_at and updated_at to their defaults
+        sqlx::query(
+            "INSERT INTO focus_schedule (id, label, scheduled_time, workflow_id, recurrence_settings, is_active)
+             VALUES ('frontend', 'Morning', '2025-01-06T14:00:00.000Z', ?1, '{\"type\":\"weekly\",\"daysOfWeek\":[1,3]}', 1)"
+        )
+        .bind(&workflow.id)
+        .execute(&pool)
+        .await?;
+
+        let schedule = FocusSchedule::new(
+            workflow.id.clone(),
+            datetime!(2025-01-07 09:00 UTC),
+            Some(RecurrenceSettings::daily()),
+            None,
+        );
+        repo.create_schedule(&schedule).await?;
+        let one_off = FocusSchedule::new(workflow.id.clone(), datetime!(2025-01-08 09:00 UTC), None, None);
+        repo.create_schedule(&one_off).await?;
+        repo.deactivate_schedule(&one_off.id).await?;
+
+        let schedules = repo.get_active_schedules().await?;
+        assert_eq!(
+            schedules.iter().map(|s| s.id.as_str()).collect::<Vec<_>>(),
+            vec!["frontend", schedule.id.as_str()]
+        );
+        assert_eq!(schedules[0].scheduled_time, datetime!(2025-01-06 14:00 UTC));
+        assert_eq!(schedules[0].recurrence_settings, Some(RecurrenceSettings::weekly(vec![1, 3])));
+        assert!(schedules[0].created_at.is_some());
+        assert_eq!(schedules[1].recurrence_type(), RecurrenceType::Daily);
+
+        let one_off = repo.get_schedule(&one_off.id).await?.unwrap();
+        assert!(!one_off.is_active);
+        assert_eq!(one_off.recurrence_type(), RecurrenceType::None);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_get_active_schedules_skips_malformed_rows() -> Result<()> {
+        let pool = db_manager::create_test_db().await;
+        let repo = FocusScheduleRepo::new(pool.clone());
+        let workflow = Workflow::new("Deep Work".to_string(), WorkflowSettings::default());
+        WorkflowRepo::new(pool.clone()).create_workflow(&workflow).await?;
+
+        for (id, scheduled_time, recurrence_settings) in [
+            ("bad-settings", "2025-01-06T08:00:00.000Z", "{\"type\":\"fortnightly\"}"),
+            ("bad-time", "not a time", "{\"type\":\"daily\"}"),
+            ("good", "2025-01-06T09:00:00.000Z", "{\"type\":\"daily\"}"),
+        ] {
+            sqlx::query(
+                "INSERT INTO focus_schedule (id, scheduled_time, workflow_id, recurrence_settings, is_active)
+                 VALUES (?1, ?2, ?3, ?4, 1)"
+            )
+            .bind(id)
+            .bind(scheduled_time)
+            .bind(&workflow.id)
+            .bind(recurrence_settings)
+            .execute(&pool)
+            .await?;
+        }
+
+        let schedules = repo.get_active_schedules().await?;
+        assert_eq!(schedules.iter().map(|s| s.id.as_str()).collect::<Vec<_>>(), vec!["good"]);
+
+        Ok(())
+    }
+}
@@ -1,7 +1,18 @@
 pub mod activity_state;
 pub mod activity_state_tag;
+pub mod blocking_preference;
 pub mod device;
 pub mod device_profile;
+pub mod flow_session;
+pub mod focus_schedule;
+pub mod recurrence_rule;
 pub mod tag;
 pub mod tide;
+pub mod tide_milestone;
+pub mod tide_pause;
+pub mod tide_progress_checkpoint;
+pub mod tide_streak_snapshot;
 pub mod tide_template;
+pub mod tide_template_version;
+pub mod tide_types;
+pub mod workflow;
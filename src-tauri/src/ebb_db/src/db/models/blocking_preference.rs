@@ -0,0 +1,42 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+/// An app or category tag a workflow blocks (or allows, for allow list workflows)
+/// Exactly one of `app_id` and `tag_id` is set; both refer to rows in the CodeClimbers database
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct BlockingPreference {
+    pub id: String,
+    pub app_id: Option<String>,
+    pub tag_id: Option<String>,
+    pub workflow_id: Option<String>,
+    pub created_at: OffsetDateTime,
+}
+
+impl BlockingPreference {
+    pub fn for_app(workflow_id: &str, app_id: &str) -> Self {
+        Self::new(workflow_id, Some(app_id.to_string()), None)
+    }
+
+    pub fn for_tag(workflow_id: &str, tag_id: &str) -> Self {
+        Self::new(workflow_id, None, Some(tag_id.to_string()))
+    }
+
+    fn new(workflow_id: &str, app_id: Option<String>, tag_id: Option<String>) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            app_id,
+            tag_id,
+            workflow_id: Some(workflow_id.to_string()),
+            created_at: OffsetDateTime::now_utc(),
+        }
+    }
+}
+
+/// An app resolved from blocking preferences, in the shape the OS blocker takes (`BlockableItem`)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, FromRow)]
+pub struct BlockableApp {
+    pub app_external_id: String, // Bundle id, or the url for browser sites
+    pub is_browser: bool,
+}
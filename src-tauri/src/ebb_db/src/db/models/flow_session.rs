@@ -0,0 +1,135 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use thiserror::Error;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use super::tide_types::impl_sqlite_text_enum;
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("Invalid flow session type: '{0}'")]
+pub struct InvalidFlowSessionType(pub String);
+
+/// How a flow session was started - stored as lowercase text ("smart", "manual")
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FlowSessionType {
+    /// Started automatically by smart focus
+    Smart,
+    /// Started by the user
+    Manual,
+}
+
+impl FlowSessionType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FlowSessionType::Smart => "smart",
+            FlowSessionType::Manual => "manual",
+        }
+    }
+}
+
+impl FromStr for FlowSessionType {
+    type Err = InvalidFlowSessionType;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "smart" => Ok(FlowSessionType::Smart),
+            "manual" => Ok(FlowSessionType::Manual),
+            _ => Err(InvalidFlowSessionType(s.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for FlowSessionType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl_sqlite_text_enum!(FlowSessionType);
+
+/// A focus session, started and ended by the frontend or from Rust through `FlowSessionService`
+/// Only one session can be in progress (no `end`) at a time, enforced by `idx_single_active_session`
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct FlowSession {
+    pub id: String,
+    pub objective: String,
+    pub self_score: Option<f64>,
+    pub start: OffsetDateTime,
+    pub end: Option<OffsetDateTime>, // None while the session is in progress
+    pub stats: String,               // JSON object, "{}" until stats are recorded
+    pub duration: Option<i64>,       // Planned length in seconds, None for open-ended sessions
+    pub workflow_id: Option<String>,
+    #[sqlx(rename = "type")]
+    #[serde(rename = "type")]
+    pub session_type: Option<FlowSessionType>, // Null for sessions from before types were recorded
+    pub focus_schedule_id: Option<String>, // Set when the session was started by a focus schedule
+    pub created_at: Option<OffsetDateTime>,
+    pub updated_at: Option<OffsetDateTime>,
+}
+
+impl FlowSession {
+    pub fn new(
+        objective: String,
+        session_type: FlowSessionType,
+        workflow_id: Option<String>,
+        duration: Option<i64>,
+        start: OffsetDateTime,
+    ) -> Self {
+        let now = OffsetDateTime::now_utc();
+        Self {
+            id: Uuid::new_v4().to_string(),
+            objective,
+            self_score: None,
+            start,
+            end: None,
+            stats: "{}".to_string(),
+            duration,
+            workflow_id,
+            session_type: Some(session_type),
+            focus_schedule_id: None,
+            created_at: Some(now),
+            updated_at: Some(now),
+        }
+    }
+
+    /// Check if the session is still in progress
+    pub fn is_active(&self) -> bool {
+        self.end.is_none()
+    }
+
+    /// Planned end of the session, None for open-ended sessions
+    pub fn planned_end(&self) -> Option<OffsetDateTime> {
+        self.duration
+            .map(|seconds| self.start + time::Duration::seconds(seconds))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::datetime;
+
+    #[test]
+    fn test_flow_session_type_round_trip() {
+        for session_type in [FlowSessionType::Smart, FlowSessionType::Manual] {
+            assert_eq!(session_type.as_str().parse::<FlowSessionType>(), Ok(session_type));
+        }
+        assert!("scheduled".parse::<FlowSessionType>().is_err());
+    }
+
+    #[test]
+    fn test_planned_end_uses_duration_in_seconds() {
+        let start = datetime!(2025-01-06 09:00 UTC);
+        let mut session = FlowSession::new("Write".to_string(), FlowSessionType::Manual, None, Some(1500), start);
+        assert!(session.is_active());
+        assert_eq!(session.planned_end(), Some(datetime!(2025-01-06 09:25 UTC)));
+
+        session.duration = None;
+        assert_eq!(session.planned_end(), None);
+    }
+}
@@ -0,0 +1,178 @@
+use serde::{Deserialize, Serialize};
+use sqlx::{Decode, Encode, FromRow, Type};
+use time::{OffsetDateTime, Weekday};
+use uuid::Uuid;
+
+use super::recurrence_rule::RecurrenceRule;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RecurrenceType {
+    #[default]
+    None,
+    Daily,
+    Weekly,
+    /// Repeats by `RecurrenceSettings::rrule`
+    Custom,
+}
+
+/// How a focus schedule repeats, stored as JSON in `focus_schedule.recurrence_settings`
+/// with the frontend's camelCase keys
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecurrenceSettings {
+    #[serde(rename = "type")]
+    pub recurrence_type: RecurrenceType,
+    /// Days weekly schedules run on (0=Sunday, 6=Saturday)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub days_of_week: Option<Vec<u8>>,
+    /// Rule for custom schedules, e.g. "RRULE:FREQ=MONTHLY;BYDAY=1MO" for the first Monday of the month
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rrule: Option<RecurrenceRule>,
+}
+
+impl RecurrenceSettings {
+    pub fn daily() -> Self {
+        Self {
+            recurrence_type: RecurrenceType::Daily,
+            ..Self::default()
+        }
+    }
+
+    pub fn weekly(days_of_week: Vec<u8>) -> Self {
+        Self {
+            recurrence_type: RecurrenceType::Weekly,
+            days_of_week: Some(days_of_week),
+            ..Self::default()
+        }
+    }
+
+    pub fn custom(rrule: RecurrenceRule) -> Self {
+        Self {
+            recurrence_type: RecurrenceType::Custom,
+            rrule: Some(rrule),
+            ..Self::default()
+        }
+    }
+
+    /// Get the rule the schedule repeats by, None for one-off schedules
+    /// Weekly schedules without any days, like custom ones without a rule, never repeat
+    pub fn to_rule(&self) -> Option<RecurrenceRule> {
+        match self.recurrence_type {
+            RecurrenceType::None => None,
+            RecurrenceType::Daily => Some(RecurrenceRule::daily()),
+            RecurrenceType::Weekly => {
+                let weekdays: Vec<Weekday> = self
+                    .days_of_week
+                    .iter()
+                    .flatten()
+                    .filter(|day| **day <= 6)
+                    .map(|day| Weekday::Sunday.nth_next(*day))
+                    .collect();
+                (!weekdays.is_empty()).then(|| RecurrenceRule::weekly_on(&weekdays))
+            }
+            RecurrenceType::Custom => self.rrule.clone(),
+        }
+    }
+}
+
+// Custom implementation for SQLx to handle JSON serialization
+impl Type<sqlx::Sqlite> for RecurrenceSettings {
+    fn type_info() -> sqlx::sqlite::SqliteTypeInfo {
+        <String as Type<sqlx::Sqlite>>::type_info()
+    }
+}
+
+impl<'r> Decode<'r, sqlx::Sqlite> for RecurrenceSettings {
+    fn decode(value: sqlx::sqlite::SqliteValueRef<'r>) -> Result<Self, sqlx::error::BoxDynError> {
+        let json_str = <String as Decode<sqlx::Sqlite>>::decode(value)?;
+        let settings: RecurrenceSettings = serde_json::from_str(&json_str)?;
+        Ok(settings)
+    }
+}
+
+impl<'q> Encode<'q, sqlx::Sqlite> for RecurrenceSettings {
+    fn encode_by_ref(
+        &self,
+        args: &mut Vec<sqlx::sqlite::SqliteArgumentValue<'q>>,
+    ) -> Result<sqlx::encode::IsNull, Box<dyn std::error::Error + Send + Sync>> {
+        let json_str = serde_json::to_string(self)?;
+        args.push(sqlx::sqlite::SqliteArgumentValue::Text(json_str.into()));
+        Ok(sqlx::encode::IsNull::No)
+    }
+}
+
+/// A focus session scheduled to start with a workflow, once or on repeat
+/// Recurring schedules run at the local time of day of `scheduled_time`, starting from it
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct FocusSchedule {
+    pub id: String,
+    pub label: Option<String>,
+    pub scheduled_time: OffsetDateTime,
+    pub workflow_id: String,
+    pub recurrence_settings: Option<RecurrenceSettings>,
+    pub is_active: bool, // Deleting a schedule only deactivates it
+    pub created_at: Option<OffsetDateTime>,
+    pub updated_at: Option<OffsetDateTime>,
+}
+
+impl FocusSchedule {
+    pub fn new(
+        workflow_id: String,
+        scheduled_time: OffsetDateTime,
+        recurrence_settings: Option<RecurrenceSettings>,
+        label: Option<String>,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            label,
+            scheduled_time,
+            workflow_id,
+            recurrence_settings,
+            is_active: true,
+            created_at: Some(OffsetDateTime::now_utc()),
+            updated_at: Some(OffsetDateTime::now_utc()),
+        }
+    }
+
+    pub fn recurrence_type(&self) -> RecurrenceType {
+        self.recurrence_settings
+            .as_ref()
+            .map(|settings| settings.recurrence_type)
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recurrence_settings_match_frontend_json() {
+        let weekly: RecurrenceSettings =
+            serde_json::from_str(r#"{"type":"weekly","daysOfWeek":[1,3,5]}"#).unwrap();
+        assert_eq!(weekly, RecurrenceSettings::weekly(vec![1, 3, 5]));
+
+        let daily = serde_json::to_string(&RecurrenceSettings::daily()).unwrap();
+        assert_eq!(daily, r#"{"type":"daily"}"#);
+
+        let none: RecurrenceSettings = serde_json::from_str(r#"{"type":"none"}"#).unwrap();
+        assert_eq!(none.recurrence_type, RecurrenceType::None);
+
+        let custom: RecurrenceSettings =
+            serde_json::from_str(r#"{"type":"custom","rrule":"RRULE:FREQ=WEEKLY;INTERVAL=2;BYDAY=TU"}"#).unwrap();
+        assert_eq!(custom.to_rule().unwrap().to_string(), "RRULE:FREQ=WEEKLY;INTERVAL=2;BYDAY=TU");
+        assert!(serde_json::from_str::<RecurrenceSettings>(r#"{"type":"custom","rrule":"FREQ=SECONDLY"}"#).is_err());
+    }
+
+    #[test]
+    fn test_to_rule() {
+        assert_eq!(RecurrenceSettings::default().to_rule(), None);
+        assert_eq!(RecurrenceSettings::daily().to_rule(), Some(RecurrenceRule::daily()));
+        assert_eq!(
+            RecurrenceSettings::weekly(vec![0, 3]).to_rule(),
+            Some(RecurrenceRule::weekly_on(&[Weekday::Sunday, Weekday::Wednesday]))
+        );
+        assert_eq!(RecurrenceSettings::weekly(vec![]).to_rule(), None);
+    }
+}
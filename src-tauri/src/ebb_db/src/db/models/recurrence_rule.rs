@@ -0,0 +1,643 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use thiserror::Error;
+use time::{Date, Duration, Month, Weekday};
+
+/// A rule that repeats with no occurrences for this long has run out, e.g. "every February 30th"
+/// The calendar's weekday and date pattern repeats every 28 years
+const MAX_YEARS_WITHOUT_OCCURRENCE: i64 = 28;
+
+/// Largest INTERVAL accepted, so finding an occurrence never has to look centuries ahead
+const MAX_INTERVAL: u32 = 100;
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("Invalid recurrence rule '{rule}': {reason}")]
+pub struct InvalidRecurrenceRule {
+    pub rule: String,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+}
+
+impl Frequency {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Frequency::Daily => "DAILY",
+            Frequency::Weekly => "WEEKLY",
+            Frequency::Monthly => "MONTHLY",
+        }
+    }
+
+    /// Most periods of this frequency that start in one year
+    fn max_periods_per_year(&self) -> i64 {
+        match self {
+            Frequency::Daily => 366,
+            Frequency::Weekly => 53,
+            Frequency::Monthly => 12,
+        }
+    }
+}
+
+/// A BYDAY entry: a weekday, optionally the nth of it in the month ("1MO" first Monday, "-1FR" last Friday)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ByDay {
+    pub ordinal: Option<i8>,
+    pub weekday: Weekday,
+}
+
+impl ByDay {
+    pub fn every(weekday: Weekday) -> Self {
+        Self { ordinal: None, weekday }
+    }
+
+    pub fn nth(ordinal: i8, weekday: Weekday) -> Self {
+        Self {
+            ordinal: Some(ordinal),
+            weekday,
+        }
+    }
+
+    fn matches(&self, date: Date) -> bool {
+        if date.weekday() != self.weekday {
+            return false;
+        }
+        match self.ordinal {
+            None => true,
+            Some(ordinal) if ordinal > 0 => (date.day() as i8 - 1) / 7 + 1 == ordinal,
+            Some(ordinal) => (days_in_month(date) as i8 - date.day() as i8) / 7 + 1 == -ordinal,
+        }
+    }
+}
+
+impl fmt::Display for ByDay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(ordinal) = self.ordinal {
+            write!(f, "{}", ordinal)?;
+        }
+        f.write_str(weekday_code(self.weekday))
+    }
+}
+
+/// A subset of RFC 5545 recurrence rules over local calendar dates: FREQ (DAILY, WEEKLY, MONTHLY),
+/// INTERVAL, BYDAY, BYMONTHDAY, UNTIL and COUNT, plus EXDATE
+///
+/// Rules are stored as text like "RRULE:FREQ=WEEKLY;INTERVAL=2;BYDAY=TU", with excluded dates on a
+/// second "EXDATE:20250107,20250121" line. The first occurrence date (DTSTART) is not part of the
+/// rule; callers pass it in, e.g. a schedule's first date, and it anchors INTERVAL and COUNT.
+/// Weeks start on Monday, RFC 5545's default WKST.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecurrenceRule {
+    pub frequency: Frequency,
+    pub interval: u32,
+    pub by_day: Vec<ByDay>,
+    pub by_month_day: Vec<i8>, // 1 to 31, or -1 (last day) to -31
+    pub until: Option<Date>, // Last possible occurrence, inclusive
+    pub count: Option<u32>, // Number of occurrences, counting excluded dates
+    pub exdates: Vec<Date>,
+}
+
+impl RecurrenceRule {
+    pub fn new(frequency: Frequency) -> Self {
+        Self {
+            frequency,
+            interval: 1,
+            by_day: Vec::new(),
+            by_month_day: Vec::new(),
+            until: None,
+            count: None,
+            exdates: Vec::new(),
+        }
+    }
+
+    pub fn daily() -> Self {
+        Self::new(Frequency::Daily)
+    }
+
+    pub fn weekly_on(weekdays: &[Weekday]) -> Self {
+        Self {
+            by_day: weekdays.iter().copied().map(ByDay::every).collect(),
+            ..Self::new(Frequency::Weekly)
+        }
+    }
+
+    /// Get the occurrences on or after `from`, in order, for a rule first occurring from `dtstart`
+    /// Ends at UNTIL, after COUNT occurrences, or once the rule can't occur again
+    pub fn occurrences_from(&self, dtstart: Date, from: Date) -> Occurrences<'_> {
+        // COUNT is counted from the first occurrence, so only rules without it can skip ahead to
+        // the period containing `from`
+        let (period, first) = if self.count.is_some() || from <= dtstart {
+            (0, dtstart)
+        } else {
+            let period = self.period_index(dtstart, from);
+            (period - period % self.interval as i64, from)
+        };
+        Occurrences {
+            rule: self,
+            dtstart,
+            from,
+            period,
+            next: self.period_start(dtstart, period).map(|start| start.max(first)),
+            empty_periods: 0,
+            matched_in_period: false,
+            generated: 0,
+        }
+    }
+
+    /// Get the first occurrence on or after `date`
+    pub fn next_occurrence(&self, dtstart: Date, date: Date) -> Option<Date> {
+        self.occurrences_from(dtstart, date).next()
+    }
+
+    pub fn occurs_on(&self, dtstart: Date, date: Date) -> bool {
+        self.next_occurrence(dtstart, date) == Some(date)
+    }
+
+    /// Check whether the rule occurs on any date from `start` up to, but not including, `end`
+    pub fn occurs_between(&self, dtstart: Date, start: Date, end: Date) -> bool {
+        self.next_occurrence(dtstart, start).is_some_and(|date| date < end)
+    }
+
+    /// Get the index of the day, week or month containing `date`, counting DTSTART's as 0
+    fn period_index(&self, dtstart: Date, date: Date) -> i64 {
+        match self.frequency {
+            Frequency::Daily => (date - dtstart).whole_days(),
+            Frequency::Weekly => (week_start(date) - week_start(dtstart)).whole_weeks(),
+            Frequency::Monthly => months_between(dtstart, date),
+        }
+    }
+
+    /// Get the first date of a period, None past the end of the calendar
+    fn period_start(&self, dtstart: Date, period: i64) -> Option<Date> {
+        match self.frequency {
+            Frequency::Daily => dtstart.checked_add(Duration::days(period)),
+            Frequency::Weekly => week_start(dtstart).checked_add(Duration::weeks(period)),
+            Frequency::Monthly => {
+                let months = dtstart.year() as i64 * 12 + dtstart.month() as i64 - 1 + period;
+                let month = Month::try_from((months.rem_euclid(12) + 1) as u8).ok()?;
+                Date::from_calendar_date(i32::try_from(months.div_euclid(12)).ok()?, month, 1).ok()
+            }
+        }
+    }
+
+    /// Check whether a date in one of the periods the rule repeats in fits its pattern, ignoring
+    /// UNTIL, COUNT and EXDATE
+    fn matches(&self, dtstart: Date, date: Date) -> bool {
+        if date < dtstart {
+            return false;
+        }
+
+        if !self.by_month_day.is_empty() && !self.by_month_day.iter().any(|day| month_day_matches(*day, date)) {
+            return false;
+        }
+        if !self.by_day.is_empty() {
+            return self.by_day.iter().any(|by_day| by_day.matches(date));
+        }
+
+        // Without BYDAY or BYMONTHDAY, weekly and monthly rules repeat on DTSTART's day
+        match self.frequency {
+            Frequency::Weekly => date.weekday() == dtstart.weekday(),
+            Frequency::Monthly if self.by_month_day.is_empty() => date.day() == dtstart.day(),
+            _ => true,
+        }
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.interval == 0 || self.interval > MAX_INTERVAL {
+            return Err(format!("INTERVAL must be between 1 and {}", MAX_INTERVAL));
+        }
+        if self.count == Some(0) {
+            return Err("COUNT must be at least 1".to_string());
+        }
+        if self.count.is_some() && self.until.is_some() {
+            return Err("UNTIL and COUNT can't both be set".to_string());
+        }
+        if self.frequency != Frequency::Monthly && self.by_day.iter().any(|by_day| by_day.ordinal.is_some()) {
+            return Err("BYDAY ordinals like 1MO are only supported with FREQ=MONTHLY".to_string());
+        }
+        if self.by_day.iter().any(|by_day| by_day.ordinal.is_some_and(|n| n == 0 || !(-5..=5).contains(&n))) {
+            return Err("BYDAY ordinals must be between -5 and 5, excluding 0".to_string());
+        }
+        if self.frequency == Frequency::Weekly && !self.by_month_day.is_empty() {
+            return Err("BYMONTHDAY can't be used with FREQ=WEEKLY".to_string());
+        }
+        if self.by_month_day.iter().any(|day| *day == 0 || !(-31..=31).contains(day)) {
+            return Err("BYMONTHDAY must be between -31 and 31, excluding 0".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Iterator over a rule's occurrences, see `RecurrenceRule::occurrences_from`
+/// Steps through the periods the rule repeats in (every INTERVAL days, weeks or months) and checks
+/// the dates within each, so the work doesn't grow with INTERVAL
+pub struct Occurrences<'a> {
+    rule: &'a RecurrenceRule,
+    dtstart: Date,
+    from: Date,
+    period: i64, // Index of the period being checked, see `RecurrenceRule::period_index`
+    next: Option<Date>, // Next date to check, None once the rule has ended
+    empty_periods: i64, // Periods checked in a row without an occurrence
+    matched_in_period: bool,
+    generated: u32,
+}
+
+impl Iterator for Occurrences<'_> {
+    type Item = Date;
+
+    fn next(&mut self) -> Option<Date> {
+        let max_empty_periods = MAX_YEARS_WITHOUT_OCCURRENCE * self.rule.frequency.max_periods_per_year();
+
+        while let Some(date) = self.next {
+            if self.rule.until.is_some_and(|until| date > until) {
+                break;
+            }
+
+            if self.rule.period_index(self.dtstart, date) != self.period {
+                // Past the end of the period, move on to the next one the rule repeats in
+                self.empty_periods = if self.matched_in_period { 0 } else { self.empty_periods + 1 };
+                if self.empty_periods >= max_empty_periods {
+                    break;
+                }
+                self.matched_in_period = false;
+                self.period += self.rule.interval as i64;
+                self.next = self.rule.period_start(self.dtstart, self.period);
+                continue;
+            }
+            self.next = date.next_day();
+
+            if !self.rule.matches(self.dtstart, date) {
+                continue;
+            }
+            self.matched_in_period = true;
+            self.generated += 1;
+            if self.rule.count.is_some_and(|count| self.generated > count) {
+                break;
+            }
+            if date >= self.from && !self.rule.exdates.contains(&date) {
+                return Some(date);
+            }
+        }
+
+        self.next = None;
+        None
+    }
+}
+
+impl fmt::Display for RecurrenceRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "RRULE:FREQ={}", self.frequency.as_str())?;
+        if self.interval != 1 {
+            write!(f, ";INTERVAL={}", self.interval)?;
+        }
+        if !self.by_day.is_empty() {
+            let by_day: Vec<String> = self.by_day.iter().map(|by_day| by_day.to_string()).collect();
+            write!(f, ";BYDAY={}", by_day.join(","))?;
+        }
+        if !self.by_month_day.is_empty() {
+            let by_month_day: Vec<String> = self.by_month_day.iter().map(|day| day.to_string()).collect();
+            write!(f, ";BYMONTHDAY={}", by_month_day.join(","))?;
+        }
+        if let Some(until) = self.until {
+            write!(f, ";UNTIL={}", format_date(until))?;
+        }
+        if let Some(count) = self.count {
+            write!(f, ";COUNT={}", count)?;
+        }
+        if !self.exdates.is_empty() {
+            let exdates: Vec<String> = self.exdates.iter().map(|date| format_date(*date)).collect();
+            write!(f, "\nEXDATE:{}", exdates.join(","))?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for RecurrenceRule {
+    type Err = InvalidRecurrenceRule;
+
+    /// Parse a rule as written by Display; the "RRULE:" prefix is optional
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = |reason: String| InvalidRecurrenceRule {
+            rule: s.to_string(),
+            reason,
+        };
+
+        let mut rule_parts = None;
+        let mut exdates = Vec::new();
+        for line in s.lines().map(str::trim).filter(|line| !line.is_empty()) {
+            if let Some(dates) = line.strip_prefix("EXDATE:") {
+                for date in dates.split(',') {
+                    exdates.push(parse_date(date.trim()).ok_or_else(|| invalid(format!("invalid EXDATE '{}'", date)))?);
+                }
+            } else if rule_parts.is_none() {
+                rule_parts = Some(line.strip_prefix("RRULE:").unwrap_or(line));
+            } else {
+                return Err(invalid("more than one RRULE".to_string()));
+            }
+        }
+        let rule_parts = rule_parts.ok_or_else(|| invalid("missing RRULE".to_string()))?;
+
+        let mut frequency = None;
+        let mut rule = RecurrenceRule::daily();
+        let mut seen = Vec::new();
+        for part in rule_parts.split(';') {
+            let (key, value) = part
+                .split_once('=')
+                .ok_or_else(|| invalid(format!("expected KEY=VALUE, got '{}'", part)))?;
+            if seen.contains(&key) {
+                return Err(invalid(format!("{} is set more than once", key)));
+            }
+            seen.push(key);
+
+            let bad_value = || invalid(format!("invalid {} '{}'", key, value));
+            match key {
+                "FREQ" => {
+                    frequency = Some(match value {
+                        "DAILY" => Frequency::Daily,
+                        "WEEKLY" => Frequency::Weekly,
+                        "MONTHLY" => Frequency::Monthly,
+                        _ => return Err(invalid(format!("FREQ={} is not supported", value))),
+                    })
+                }
+                "INTERVAL" => rule.interval = value.parse().map_err(|_| bad_value())?,
+                "BYDAY" => {
+                    rule.by_day = value
+                        .split(',')
+                        .map(|by_day| parse_by_day(by_day).ok_or_else(bad_value))
+                        .collect::<Result<_, _>>()?
+                }
+                "BYMONTHDAY" => {
+                    rule.by_month_day = value
+                        .split(',')
+                        .map(|day| day.parse().map_err(|_| bad_value()))
+                        .collect::<Result<_, _>>()?
+                }
+                "UNTIL" => rule.until = Some(parse_date(value).ok_or_else(bad_value)?),
+                "COUNT" => rule.count = Some(value.parse().map_err(|_| bad_value())?),
+                _ => return Err(invalid(format!("{} is not supported", key))),
+            }
+        }
+
+        rule.frequency = frequency.ok_or_else(|| invalid("FREQ is required".to_string()))?;
+        rule.exdates = exdates;
+        rule.validate().map_err(invalid)?;
+        Ok(rule)
+    }
+}
+
+impl Serialize for RecurrenceRule {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for RecurrenceRule {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        text.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+// Stored as its text form
+impl sqlx::Type<sqlx::Sqlite> for RecurrenceRule {
+    fn type_info() -> sqlx::sqlite::SqliteTypeInfo {
+        <String as sqlx::Type<sqlx::Sqlite>>::type_info()
+    }
+
+    fn compatible(ty: &sqlx::sqlite::SqliteTypeInfo) -> bool {
+        <String as sqlx::Type<sqlx::Sqlite>>::compatible(ty)
+    }
+}
+
+impl<'r> sqlx::Decode<'r, sqlx::Sqlite> for RecurrenceRule {
+    fn decode(value: sqlx::sqlite::SqliteValueRef<'r>) -> Result<Self, sqlx::error::BoxDynError> {
+        let text = <String as sqlx::Decode<sqlx::Sqlite>>::decode(value)?;
+        Ok(text.parse::<RecurrenceRule>()?)
+    }
+}
+
+impl<'q> sqlx::Encode<'q, sqlx::Sqlite> for RecurrenceRule {
+    fn encode_by_ref(
+        &self,
+        args: &mut Vec<sqlx::sqlite::SqliteArgumentValue<'q>>,
+    ) -> Result<sqlx::encode::IsNull, Box<dyn std::error::Error + Send + Sync>> {
+        args.push(sqlx::sqlite::SqliteArgumentValue::Text(self.to_string().into()));
+        Ok(sqlx::encode::IsNull::No)
+    }
+}
+
+fn weekday_code(weekday: Weekday) -> &'static str {
+    match weekday {
+        Weekday::Monday => "MO",
+        Weekday::Tuesday => "TU",
+        Weekday::Wednesday => "WE",
+        Weekday::Thursday => "TH",
+        Weekday::Friday => "FR",
+        Weekday::Saturday => "SA",
+        Weekday::Sunday => "SU",
+    }
+}
+
+fn parse_by_day(value: &str) -> Option<ByDay> {
+    let split = value.len().checked_sub(2)?;
+    let (ordinal, code) = (value.get(..split)?, value.get(split..)?);
+    let weekday = [
+        Weekday::Monday,
+        Weekday::Tuesday,
+        Weekday::Wednesday,
+        Weekday::Thursday,
+        Weekday::Friday,
+        Weekday::Saturday,
+        Weekday::Sunday,
+    ]
+    .into_iter()
+    .find(|weekday| weekday_code(*weekday) == code)?;
+
+    let ordinal = match ordinal {
+        "" => None,
+        ordinal => Some(ordinal.strip_prefix('+').unwrap_or(ordinal).parse().ok()?),
+    };
+    Some(ByDay { ordinal, weekday })
+}
+
+/// Format a date in the iCalendar DATE form, "20250107"
+fn format_date(date: Date) -> String {
+    format!("{:04}{:02}{:02}", date.year(), date.month() as u8, date.day())
+}
+
+/// Parse an iCalendar DATE ("20250107"), or the date of a DATE-TIME ("20250107T090000Z")
+fn parse_date(value: &str) -> Option<Date> {
+    let date = value.split_once('T').map_or(value, |(date, _)| date);
+    if date.len() != 8 || !date.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let year = date[..4].parse().ok()?;
+    let month = Month::try_from(date[4..6].parse::<u8>().ok()?).ok()?;
+    let day = date[6..].parse().ok()?;
+    Date::from_calendar_date(year, month, day).ok()
+}
+
+fn days_in_month(date: Date) -> u8 {
+    date.month().length(date.year())
+}
+
+fn month_day_matches(day: i8, date: Date) -> bool {
+    if day > 0 {
+        date.day() as i8 == day
+    } else {
+        days_in_month(date) as i8 - date.day() as i8 + 1 == -day
+    }
+}
+
+/// Get the Monday on or before the given date
+fn week_start(date: Date) -> Date {
+    date - Duration::days(date.weekday().number_days_from_monday() as i64)
+}
+
+fn months_between(start: Date, end: Date) -> i64 {
+    (end.year() as i64 - start.year() as i64) * 12 + end.month() as i64 - start.month() as i64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::date;
+
+    fn parse(rule: &str) -> RecurrenceRule {
+        rule.parse().unwrap()
+    }
+
+    fn first_occurrences(rule: &RecurrenceRule, dtstart: Date, n: usize) -> Vec<Date> {
+        rule.occurrences_from(dtstart, dtstart).take(n).collect()
+    }
+
+    #[test]
+    fn test_round_trip_to_text() {
+        for text in [
+            "RRULE:FREQ=DAILY",
+            "RRULE:FREQ=WEEKLY;INTERVAL=2;BYDAY=TU",
+            "RRULE:FREQ=MONTHLY;BYDAY=1MO,-1FR;UNTIL=20251231",
+            "RRULE:FREQ=MONTHLY;BYMONTHDAY=1,-1;COUNT=6\nEXDATE:20250131,20250301",
+        ] {
+            assert_eq!(parse(text).to_string(), text);
+        }
+
+        // The prefix is optional and DATE-TIME values are read as their date
+        let rule = parse("FREQ=WEEKLY;BYDAY=MO,WE;UNTIL=20250301T120000Z");
+        assert_eq!(rule, RecurrenceRule { until: Some(date!(2025-03-01)), ..RecurrenceRule::weekly_on(&[Weekday::Monday, Weekday::Wednesday]) });
+        assert_eq!(serde_json::to_string(&rule).unwrap(), r#""RRULE:FREQ=WEEKLY;BYDAY=MO,WE;UNTIL=20250301""#);
+    }
+
+    #[test]
+    fn test_rejects_unsupported_rules() {
+        for text in [
+            "",
+            "RRULE:INTERVAL=2",
+            "RRULE:FREQ=YEARLY",
+            "RRULE:FREQ=HOURLY",
+            "RRULE:FREQ=DAILY;INTERVAL=0",
+            "RRULE:FREQ=DAILY;INTERVAL=101",
+            "RRULE:FREQ=DAILY;COUNT=3;UNTIL=20250101",
+            "RRULE:FREQ=DAILY;FREQ=WEEKLY",
+            "RRULE:FREQ=WEEKLY;BYDAY=1MO",
+            "RRULE:FREQ=WEEKLY;BYMONTHDAY=1",
+            "RRULE:FREQ=MONTHLY;BYDAY=6MO",
+            "RRULE:FREQ=MONTHLY;BYMONTHDAY=32",
+            "RRULE:FREQ=DAILY;BYDAY=XX",
+            "RRULE:FREQ=DAILY;WKST=SU",
+            "RRULE:FREQ=DAILY\nEXDATE:20250230",
+        ] {
+            assert!(text.parse::<RecurrenceRule>().is_err(), "{:?} should be rejected", text);
+        }
+    }
+
+    #[test]
+    fn test_every_other_tuesday() {
+        let rule = parse("RRULE:FREQ=WEEKLY;INTERVAL=2;BYDAY=TU");
+        // Starting on Monday 2025-01-06, the first Tuesday is in the same week
+        assert_eq!(
+            first_occurrences(&rule, date!(2025-01-06), 3),
+            vec![date!(2025-01-07), date!(2025-01-21), date!(2025-02-04)]
+        );
+        assert!(!rule.occurs_on(date!(2025-01-06), date!(2025-01-14)));
+        assert_eq!(rule.next_occurrence(date!(2025-01-06), date!(2025-01-08)), Some(date!(2025-01-21)));
+    }
+
+    #[test]
+    fn test_first_monday_and_last_day_of_month() {
+        let first_monday = parse("RRULE:FREQ=MONTHLY;BYDAY=1MO");
+        assert_eq!(
+            first_occurrences(&first_monday, date!(2025-01-01), 3),
+            vec![date!(2025-01-06), date!(2025-02-03), date!(2025-03-03)]
+        );
+
+        let last_day = parse("RRULE:FREQ=MONTHLY;BYMONTHDAY=-1");
+        assert_eq!(
+            first_occurrences(&last_day, date!(2024-01-15), 3),
+            vec![date!(2024-01-31), date!(2024-02-29), date!(2024-03-31)]
+        );
+
+        // Without BYDAY or BYMONTHDAY, months without DTSTART's day are skipped
+        let monthly = parse("RRULE:FREQ=MONTHLY");
+        assert_eq!(
+            first_occurrences(&monthly, date!(2025-01-31), 3),
+            vec![date!(2025-01-31), date!(2025-03-31), date!(2025-05-31)]
+        );
+    }
+
+    #[test]
+    fn test_until_count_and_exdates() {
+        let until = parse("RRULE:FREQ=DAILY;INTERVAL=3;UNTIL=20250110");
+        assert_eq!(
+            first_occurrences(&until, date!(2025-01-01), 10),
+            vec![date!(2025-01-01), date!(2025-01-04), date!(2025-01-07), date!(2025-01-10)]
+        );
+
+        // Excluded dates still count toward COUNT
+        let count = parse("RRULE:FREQ=WEEKLY;BYDAY=MO,FR;COUNT=4\nEXDATE:20250110");
+        assert_eq!(
+            first_occurrences(&count, date!(2025-01-06), 10),
+            vec![date!(2025-01-06), date!(2025-01-13), date!(2025-01-17)]
+        );
+        assert_eq!(count.next_occurrence(date!(2025-01-06), date!(2025-01-14)), Some(date!(2025-01-17)));
+        assert_eq!(count.next_occurrence(date!(2025-01-06), date!(2025-01-18)), None);
+
+        assert!(until.occurs_between(date!(2025-01-01), date!(2025-01-05), date!(2025-01-08)));
+        assert!(!until.occurs_between(date!(2025-01-01), date!(2025-01-05), date!(2025-01-07)));
+    }
+
+    #[test]
+    fn test_rule_that_never_occurs_again_ends() {
+        let rule = parse("RRULE:FREQ=MONTHLY;BYMONTHDAY=30;BYDAY=MO;INTERVAL=12");
+        // Only Februaries, which never have a 30th
+        assert_eq!(rule.next_occurrence(date!(2025-02-01), date!(2025-02-01)), None);
+    }
+
+    #[test]
+    fn test_steps_by_interval_periods() {
+        let rule = parse("RRULE:FREQ=MONTHLY;INTERVAL=100;BYDAY=-1FR");
+        assert_eq!(
+            first_occurrences(&rule, date!(2025-01-01), 3),
+            vec![date!(2025-01-31), date!(2033-05-27), date!(2041-09-27)]
+        );
+
+        // Skipping ahead lands on a period the rule repeats in
+        let every_third_week = parse("RRULE:FREQ=WEEKLY;INTERVAL=3;BYDAY=WE");
+        assert_eq!(
+            every_third_week.occurrences_from(date!(2025-01-06), date!(2030-06-01)).take(2).collect::<Vec<_>>(),
+            vec![date!(2030-06-05), date!(2030-06-26)]
+        );
+
+        // COUNT is counted from DTSTART even when asking about dates long after it
+        let count = parse("RRULE:FREQ=DAILY;COUNT=100000");
+        assert_eq!(count.next_occurrence(date!(2000-01-01), date!(2025-01-06)), Some(date!(2025-01-06)));
+        let count = parse("RRULE:FREQ=DAILY;COUNT=9000");
+        assert_eq!(count.next_occurrence(date!(2000-01-01), date!(2025-01-06)), None);
+    }
+}
@@ -1,19 +1,95 @@
+use std::fmt;
+use std::str::FromStr;
+
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use time::OffsetDateTime;
 use uuid::Uuid;
 
+use super::tide_types::{impl_sqlite_text_enum, GoalDirection, MetricsType, TideFrequency, TideValueError};
+
+/// Terminal status recorded when a tide's period ends - stored as lowercase text ("completed", ...)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TideStatus {
+    /// The goal was reached
+    Completed,
+    /// Some progress was made but the goal was not reached
+    Partial,
+    /// No progress was made
+    Missed,
+    /// The period fell in a pause, so it counts as neither hit nor missed
+    Excused,
+}
+
+impl TideStatus {
+    /// Determine the terminal status from the final amount and goal
+    pub fn from_amounts(actual_amount: f64, goal_amount: f64) -> Self {
+        if actual_amount >= goal_amount {
+            TideStatus::Completed
+        } else if actual_amount > 0.0 {
+            TideStatus::Partial
+        } else {
+            TideStatus::Missed
+        }
+    }
+
+    /// Determine the terminal status for a goal direction
+    /// Limit tides succeed when they end at or under the limit and are missed once they go over it
+    pub fn for_direction(goal_direction: GoalDirection, actual_amount: f64, goal_amount: f64) -> Self {
+        match goal_direction {
+            GoalDirection::Minimum => Self::from_amounts(actual_amount, goal_amount),
+            GoalDirection::Limit if actual_amount <= goal_amount => TideStatus::Completed,
+            GoalDirection::Limit => TideStatus::Missed,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TideStatus::Completed => "completed",
+            TideStatus::Partial => "partial",
+            TideStatus::Missed => "missed",
+            TideStatus::Excused => "excused",
+        }
+    }
+}
+
+impl FromStr for TideStatus {
+    type Err = TideValueError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "completed" => Ok(TideStatus::Completed),
+            "partial" => Ok(TideStatus::Partial),
+            "missed" => Ok(TideStatus::Missed),
+            "excused" => Ok(TideStatus::Excused),
+            _ => Err(TideValueError::InvalidTideStatus(s.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for TideStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl_sqlite_text_enum!(TideStatus);
+
 #[derive(Debug, Serialize, Deserialize, FromRow)]
 pub struct Tide {
     pub id: String,
     pub start: OffsetDateTime,
     pub end: Option<OffsetDateTime>, // System-generated end time based on frequency/interval (nullable for indefinite tides)
     pub completed_at: Option<OffsetDateTime>, // When the tide was actually completed by the user
-    pub metrics_type: String, // "creating", etc.
-    pub tide_frequency: String, // "daily", "weekly", "monthly", "indefinite"
+    pub status: Option<TideStatus>, // Terminal status once the tide has expired, or excused when created during a pause
+    pub metrics_type: MetricsType,
+    pub tide_frequency: TideFrequency,
     pub goal_amount: f64,
+    pub goal_direction: GoalDirection, // Copied from the template when the tide was created
     pub actual_amount: f64,
     pub tide_template_id: String,
+    pub tag_ids: Option<String>, // For MetricsType::Tags: the template's tag ids when the tide was created
     pub created_at: OffsetDateTime,
     pub updated_at: OffsetDateTime,
 }
@@ -22,8 +98,8 @@ impl Tide {
     pub fn new(
         start: OffsetDateTime,
         end: Option<OffsetDateTime>,
-        metrics_type: String,
-        tide_frequency: String,
+        metrics_type: MetricsType,
+        tide_frequency: TideFrequency,
         goal_amount: f64,
         tide_template_id: String,
     ) -> Self {
@@ -32,36 +108,43 @@ impl Tide {
             start,
             end,
             completed_at: None,
+            status: None,
             metrics_type,
             tide_frequency,
             goal_amount,
+            goal_direction: GoalDirection::Minimum,
             actual_amount: 0.0,
             tide_template_id,
+            tag_ids: None,
             created_at: OffsetDateTime::now_utc(),
             updated_at: OffsetDateTime::now_utc(),
         }
     }
 
-    pub fn from_template(template: &super::tide_template::TideTemplate, start: OffsetDateTime) -> Self {
-        use time::Duration;
-        
-        // Calculate end time based on tide frequency
-        let end = match template.tide_frequency.as_str() {
-            "daily" => Some(start + Duration::days(1)),
-            "weekly" => Some(start + Duration::days(7)),
-            "monthly" => Some(start + Duration::days(30)), // Approximate
-            "indefinite" => None, // No end time for indefinite tides
-            _ => Some(start + Duration::days(1)), // Default to daily
-        };
-
-        Self::new(
-            start,
-            end,
-            template.metrics_type.clone(),
-            template.tide_frequency.clone(),
-            template.goal_amount,
-            template.id.clone(),
-        )
+    /// Create a tide from a template with an explicit end time
+    /// Used when the period boundaries have been resolved in the user's local timezone
+    pub fn from_template_with_end(
+        template: &super::tide_template::TideTemplate,
+        start: OffsetDateTime,
+        end: Option<OffsetDateTime>,
+    ) -> Self {
+        Self {
+            tag_ids: template.tag_ids.clone(),
+            goal_direction: template.goal_direction,
+            ..Self::new(
+                start,
+                end,
+                template.metrics_type,
+                template.tide_frequency,
+                template.goal_amount,
+                template.id.clone(),
+            )
+        }
+    }
+
+    /// Ids of the tags whose time counts toward a `MetricsType::Tags` tide
+    pub fn get_tag_ids(&self) -> Vec<String> {
+        super::tide_template::parse_tag_ids(self.tag_ids.as_deref())
     }
 
     /// Check if the tide is completed (has completed_at set)
@@ -82,6 +165,16 @@ impl Tide {
         }
     }
 
+    /// Check if the tide has been closed out with a terminal status
+    pub fn is_finalized(&self) -> bool {
+        self.status.is_some()
+    }
+
+    /// Check if the tide was excused because its period started during a pause
+    pub fn is_excused(&self) -> bool {
+        self.status == Some(TideStatus::Excused)
+    }
+
     /// Mark the tide as completed
     pub fn mark_completed(&mut self) {
         self.completed_at = Some(OffsetDateTime::now_utc());
@@ -99,8 +192,8 @@ mod tests {
     fn create_test_template() -> TideTemplateModel {
         let first_tide = datetime!(2025-01-01 0:00 UTC);
         TideTemplateModel::new(
-            "creating".to_string(),
-            "daily".to_string(),
+            MetricsType::Creating,
+            TideFrequency::Daily,
             100.0,
             first_tide,
             None,
@@ -114,8 +207,8 @@ mod tests {
         let tide = Tide::new(
             start,
             end,
-            "creating".to_string(),
-            "daily".to_string(),
+            MetricsType::Creating,
+            TideFrequency::Daily,
             100.0,
             "template-id".to_string(),
         );
@@ -123,8 +216,8 @@ mod tests {
         assert_eq!(tide.start, start);
         assert_eq!(tide.end, end);
         assert_eq!(tide.completed_at, None);
-        assert_eq!(tide.metrics_type, "creating");
-        assert_eq!(tide.tide_frequency, "daily");
+        assert_eq!(tide.metrics_type, MetricsType::Creating);
+        assert_eq!(tide.tide_frequency, TideFrequency::Daily);
         assert_eq!(tide.goal_amount, 100.0);
         assert_eq!(tide.actual_amount, 0.0);
         assert_eq!(tide.tide_template_id, "template-id");
@@ -132,59 +225,25 @@ mod tests {
     }
 
     #[test]
-    fn test_from_template_daily() {
-        let template = create_test_template();
-        let start = datetime!(2025-01-01 12:00 UTC);
-        let tide = Tide::from_template(&template, start);
-
-        assert_eq!(tide.start, start);
-        assert_eq!(tide.end, Some(start + Duration::days(1)));
-        assert_eq!(tide.completed_at, None);
-        assert_eq!(tide.metrics_type, "creating");
-        assert_eq!(tide.tide_frequency, "daily");
-        assert_eq!(tide.goal_amount, 100.0);
-        assert_eq!(tide.tide_template_id, template.id);
-    }
-
-    #[test]
-    fn test_from_template_weekly() {
-        let first_tide = datetime!(2025-01-01 0:00 UTC);
-        let template = TideTemplateModel::new(
-            "learning".to_string(),
-            "weekly".to_string(),
-            500.0,
-            first_tide,
+    fn test_from_template_copies_tag_ids() {
+        let template = TideTemplateModel::new_for_tags(
+            vec!["coding-tag-id".to_string()],
+            TideFrequency::Weekly,
+            600.0,
+            datetime!(2025-01-01 0:00 UTC),
             None,
         );
-        let start = datetime!(2025-01-01 12:00 UTC);
-        let tide = Tide::from_template(&template, start);
+        let tide = Tide::from_template_with_end(&template, datetime!(2025-01-06 0:00 UTC), None);
 
-        assert_eq!(tide.end, Some(start + Duration::days(7)));
-        assert_eq!(tide.tide_frequency, "weekly");
-    }
-
-    #[test]
-    fn test_from_template_indefinite() {
-        let first_tide = datetime!(2025-01-01 0:00 UTC);
-        let template = TideTemplateModel::new(
-            "project".to_string(),
-            "indefinite".to_string(),
-            1000.0,
-            first_tide,
-            None,
-        );
-        let start = datetime!(2025-01-01 12:00 UTC);
-        let tide = Tide::from_template(&template, start);
-
-        assert_eq!(tide.end, None);
-        assert_eq!(tide.tide_frequency, "indefinite");
+        assert_eq!(tide.metrics_type, MetricsType::Tags);
+        assert_eq!(tide.get_tag_ids(), vec!["coding-tag-id"]);
     }
 
     #[test]
     fn test_is_completed_false() {
         let template = create_test_template();
         let start = datetime!(2025-01-01 12:00 UTC);
-        let tide = Tide::from_template(&template, start);
+        let tide = Tide::from_template_with_end(&template, start, Some(start + Duration::days(1)));
 
         assert!(!tide.is_completed());
     }
@@ -193,7 +252,7 @@ mod tests {
     fn test_is_completed_true() {
         let template = create_test_template();
         let start = datetime!(2025-01-01 12:00 UTC);
-        let mut tide = Tide::from_template(&template, start);
+        let mut tide = Tide::from_template_with_end(&template, start, Some(start + Duration::days(1)));
         tide.mark_completed();
 
         assert!(tide.is_completed());
@@ -203,7 +262,7 @@ mod tests {
     fn test_is_active_not_completed_within_window() {
         let template = create_test_template();
         let start = OffsetDateTime::now_utc() - Duration::hours(12); // Started 12 hours ago
-        let tide = Tide::from_template(&template, start);
+        let tide = Tide::from_template_with_end(&template, start, Some(start + Duration::days(1)));
 
         // Should be active since it's not completed and within 24 hour window
         assert!(tide.is_active());
@@ -213,7 +272,7 @@ mod tests {
     fn test_is_active_completed() {
         let template = create_test_template();
         let start = OffsetDateTime::now_utc() - Duration::hours(12);
-        let mut tide = Tide::from_template(&template, start);
+        let mut tide = Tide::from_template_with_end(&template, start, Some(start + Duration::days(1)));
         tide.mark_completed();
 
         // Should not be active since it's completed
@@ -224,7 +283,7 @@ mod tests {
     fn test_is_active_past_end_time() {
         let template = create_test_template();
         let start = OffsetDateTime::now_utc() - Duration::days(2); // Started 2 days ago
-        let tide = Tide::from_template(&template, start);
+        let tide = Tide::from_template_with_end(&template, start, Some(start + Duration::days(1)));
 
         // Should not be active since it's past the end time (daily = 1 day)
         assert!(!tide.is_active());
@@ -234,14 +293,14 @@ mod tests {
     fn test_is_active_indefinite() {
         let first_tide = datetime!(2025-01-01 0:00 UTC);
         let template = TideTemplateModel::new(
-            "project".to_string(),
-            "indefinite".to_string(),
+            MetricsType::Neutral,
+            TideFrequency::Indefinite,
             1000.0,
             first_tide,
             None,
         );
         let start = OffsetDateTime::now_utc() - Duration::days(30); // Started 30 days ago
-        let tide = Tide::from_template(&template, start);
+        let tide = Tide::from_template_with_end(&template, start, None);
 
         // Should be active since indefinite tides have no end time
         assert!(tide.is_active());
@@ -251,7 +310,7 @@ mod tests {
     fn test_mark_completed() {
         let template = create_test_template();
         let start = datetime!(2025-01-01 12:00 UTC);
-        let mut tide = Tide::from_template(&template, start);
+        let mut tide = Tide::from_template_with_end(&template, start, Some(start + Duration::days(1)));
         let original_updated_at = tide.updated_at;
 
         assert_eq!(tide.completed_at, None);
@@ -265,4 +324,36 @@ mod tests {
         assert!(tide.updated_at >= original_updated_at);
         assert!(tide.is_completed());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_from_template_with_end() {
+        let template = create_test_template();
+        let start = datetime!(2025-03-09 06:00 UTC);
+        let end = Some(datetime!(2025-03-10 05:00 UTC)); // 23 hour DST day
+        let tide = Tide::from_template_with_end(&template, start, end);
+
+        assert_eq!(tide.start, start);
+        assert_eq!(tide.end, end);
+        assert_eq!(tide.tide_template_id, template.id);
+        assert_eq!(tide.goal_amount, 100.0);
+    }
+
+    #[test]
+    fn test_tide_status_from_amounts() {
+        assert_eq!(TideStatus::from_amounts(120.0, 100.0), TideStatus::Completed);
+        assert_eq!(TideStatus::from_amounts(100.0, 100.0), TideStatus::Completed);
+        assert_eq!(TideStatus::from_amounts(40.0, 100.0), TideStatus::Partial);
+        assert_eq!(TideStatus::from_amounts(0.0, 100.0), TideStatus::Missed);
+        assert_eq!(TideStatus::Partial.as_str(), "partial");
+        assert_eq!("partial".parse::<TideStatus>(), Ok(TideStatus::Partial));
+        assert!("done".parse::<TideStatus>().is_err());
+    }
+
+    #[test]
+    fn test_tide_status_for_direction() {
+        assert_eq!(TideStatus::for_direction(GoalDirection::Minimum, 40.0, 100.0), TideStatus::Partial);
+        assert_eq!(TideStatus::for_direction(GoalDirection::Limit, 40.0, 100.0), TideStatus::Completed);
+        assert_eq!(TideStatus::for_direction(GoalDirection::Limit, 100.0, 100.0), TideStatus::Completed);
+        assert_eq!(TideStatus::for_direction(GoalDirection::Limit, 100.5, 100.0), TideStatus::Missed);
+    }
+}
@@ -0,0 +1,11 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use time::OffsetDateTime;
+
+/// A progress milestone a tide has already reached, kept so notifications fire once per tide
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct TideMilestone {
+    pub tide_id: String,
+    pub threshold: i64, // Percentage of the goal, 1-100
+    pub reached_at: OffsetDateTime,
+}
@@ -0,0 +1,50 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use time::{Date, OffsetDateTime};
+use uuid::Uuid;
+
+/// A range of local dates (e.g. a vacation) during which tides are created as excused
+/// Pauses apply to every template so streaks aren't broken while they are in effect
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct TidePause {
+    pub id: String,
+    pub start_date: Date, // First paused local date
+    pub end_date: Date,   // Last paused local date, inclusive
+    pub reason: Option<String>,
+    pub created_at: OffsetDateTime,
+    pub updated_at: OffsetDateTime,
+}
+
+impl TidePause {
+    pub fn new(start_date: Date, end_date: Date, reason: Option<String>) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            start_date,
+            end_date,
+            reason,
+            created_at: OffsetDateTime::now_utc(),
+            updated_at: OffsetDateTime::now_utc(),
+        }
+    }
+
+    /// Check if a local date falls within the pause
+    pub fn contains(&self, date: Date) -> bool {
+        self.start_date <= date && date <= self.end_date
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::date;
+
+    #[test]
+    fn test_pause_contains_both_ends() {
+        let pause = TidePause::new(date!(2025-01-06), date!(2025-01-10), Some("Vacation".to_string()));
+
+        assert!(!pause.contains(date!(2025-01-05)));
+        assert!(pause.contains(date!(2025-01-06)));
+        assert!(pause.contains(date!(2025-01-10)));
+        assert!(!pause.contains(date!(2025-01-11)));
+    }
+}
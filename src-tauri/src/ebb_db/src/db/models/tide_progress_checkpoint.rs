@@ -0,0 +1,23 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use time::OffsetDateTime;
+
+/// Persisted incremental progress for a tide so a restart doesn't rescan from the tide's start
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct TideProgressCheckpoint {
+    pub tide_id: String,
+    pub amount: f64, // Progress in minutes from tide start up to last_evaluation_time
+    pub last_evaluation_time: OffsetDateTime,
+    pub updated_at: OffsetDateTime, // When the checkpoint was written, used to detect later activity edits
+}
+
+impl TideProgressCheckpoint {
+    pub fn new(tide_id: String, amount: f64, last_evaluation_time: OffsetDateTime) -> Self {
+        Self {
+            tide_id,
+            amount,
+            last_evaluation_time,
+            updated_at: OffsetDateTime::now_utc(),
+        }
+    }
+}
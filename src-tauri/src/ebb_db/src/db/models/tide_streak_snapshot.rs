@@ -0,0 +1,49 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use time::OffsetDateTime;
+
+use super::recurrence_rule::RecurrenceRule;
+
+/// Persisted streak state for a tide template so streaks don't need to replay every tide
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct TideStreakSnapshot {
+    pub tide_template_id: String,
+    pub current_streak: i64,
+    pub longest_streak: i64,
+    pub last_tide_start: Option<OffsetDateTime>, // Start of the last finalized tide folded into the streak
+    pub day_of_week: Option<String>, // Template day_of_week the snapshot was computed with
+    pub recurrence_rule: Option<RecurrenceRule>, // Template recurrence_rule the snapshot was computed with
+    pub created_at: OffsetDateTime,
+    pub updated_at: OffsetDateTime,
+}
+
+impl TideStreakSnapshot {
+    pub fn new(tide_template_id: String, day_of_week: Option<String>) -> Self {
+        Self {
+            tide_template_id,
+            current_streak: 0,
+            longest_streak: 0,
+            last_tide_start: None,
+            day_of_week,
+            recurrence_rule: None,
+            created_at: OffsetDateTime::now_utc(),
+            updated_at: OffsetDateTime::now_utc(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_tide_streak_snapshot() {
+        let snapshot = TideStreakSnapshot::new("template-1".to_string(), Some("1,2,3,4,5".to_string()));
+
+        assert_eq!(snapshot.tide_template_id, "template-1");
+        assert_eq!(snapshot.current_streak, 0);
+        assert_eq!(snapshot.longest_streak, 0);
+        assert!(snapshot.last_tide_start.is_none());
+        assert_eq!(snapshot.day_of_week, Some("1,2,3,4,5".to_string()));
+    }
+}
@@ -1,24 +1,37 @@
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
-use time::OffsetDateTime;
+use time::{Date, Month, OffsetDateTime};
 use uuid::Uuid;
 
-#[derive(Debug, Serialize, Deserialize, FromRow)]
+use super::recurrence_rule::RecurrenceRule;
+use super::tide_types::{GoalDirection, MetricsType, TideFrequency};
+
+/// Percentages of the goal that trigger a milestone notification when a template doesn't set its own
+pub const DEFAULT_PROGRESS_MILESTONES: [u8; 3] = [50, 90, 100];
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct TideTemplate {
     pub id: String,
-    pub metrics_type: String, // "creating", etc.
-    pub tide_frequency: String, // "daily", "weekly"
+    pub metrics_type: MetricsType,
+    pub tide_frequency: TideFrequency,
     pub first_tide: OffsetDateTime, // How far back to create tides when generating
     pub day_of_week: Option<String>, // For daily tides: comma-separated days "0,1,2,3,4,5,6" (0=Sunday, 6=Saturday)
+    pub recurrence_rule: Option<RecurrenceRule>, // Periods that get a tide, anchored at first_tide; replaces day_of_week when set
     pub goal_amount: f64,
+    pub goal_direction: GoalDirection, // Whether goal_amount is a minimum to reach or a limit to stay under
+    pub progress_milestones: Option<String>, // Comma-separated percentages of the goal to notify at, e.g. "50,90,100" (None = defaults)
+    pub tag_ids: Option<String>, // For MetricsType::Tags: comma-separated ids of the tags whose time counts toward the goal
+    pub skip_dates: Option<String>, // Comma-separated local dates "2025-01-06,2025-01-07" whose periods get no tide
+    pub version: i64, // Bumped each time the goal changes, see tide_template_version
+    pub archived_at: Option<OffsetDateTime>, // Archived templates create no tides but keep their history
     pub created_at: OffsetDateTime,
     pub updated_at: OffsetDateTime,
 }
 
 impl TideTemplate {
     pub fn new(
-        metrics_type: String, 
-        tide_frequency: String, 
+        metrics_type: MetricsType,
+        tide_frequency: TideFrequency,
         goal_amount: f64,
         first_tide: OffsetDateTime,
         day_of_week: Option<String>,
@@ -29,12 +42,48 @@ impl TideTemplate {
             tide_frequency,
             first_tide,
             day_of_week,
+            recurrence_rule: None,
             goal_amount,
+            goal_direction: GoalDirection::Minimum,
+            progress_milestones: None,
+            tag_ids: None,
+            skip_dates: None,
+            version: 1,
+            archived_at: None,
             created_at: OffsetDateTime::now_utc(),
             updated_at: OffsetDateTime::now_utc(),
         }
     }
 
+    /// Create a template measuring time tagged with any of the given tags, e.g. category tags
+    pub fn new_for_tags(
+        tag_ids: Vec<String>,
+        tide_frequency: TideFrequency,
+        goal_amount: f64,
+        first_tide: OffsetDateTime,
+        day_of_week: Option<String>,
+    ) -> Self {
+        Self {
+            tag_ids: Self::set_tag_ids(tag_ids),
+            ..Self::new(MetricsType::Tags, tide_frequency, goal_amount, first_tide, day_of_week)
+        }
+    }
+
+    /// Check if the template has been archived
+    pub fn is_archived(&self) -> bool {
+        self.archived_at.is_some()
+    }
+
+    /// Check if another copy of this template sets a different goal, which makes it a new version
+    /// Skip dates and milestones only affect scheduling and notifications, so they don't count
+    pub fn goal_differs_from(&self, other: &TideTemplate) -> bool {
+        self.goal_amount != other.goal_amount
+            || self.goal_direction != other.goal_direction
+            || self.day_of_week != other.day_of_week
+            || self.recurrence_rule != other.recurrence_rule
+            || self.tag_ids != other.tag_ids
+    }
+
     /// Helper method to parse day_of_week string into a Vec<u8>
     pub fn get_days_of_week(&self) -> Vec<u8> {
         match &self.day_of_week {
@@ -57,26 +106,108 @@ impl TideTemplate {
             Some(days.iter().map(|d| d.to_string()).collect::<Vec<_>>().join(","))
         }
     }
+
+    /// Helper method to parse progress_milestones into percentages of the goal
+    pub fn get_progress_milestones(&self) -> Vec<u8> {
+        match &self.progress_milestones {
+            Some(milestones_str) => milestones_str
+                .split(',')
+                .filter_map(|s| s.trim().parse::<u8>().ok())
+                .filter(|&milestone| (1..=100).contains(&milestone))
+                .collect(),
+            None => DEFAULT_PROGRESS_MILESTONES.to_vec(),
+        }
+    }
+
+    /// Helper method to create a progress_milestones string from percentages
+    pub fn set_progress_milestones(milestones: Vec<u8>) -> Option<String> {
+        if milestones == DEFAULT_PROGRESS_MILESTONES {
+            None // Defaults = None so they can change with the app
+        } else {
+            Some(milestones.iter().map(|m| m.to_string()).collect::<Vec<_>>().join(","))
+        }
+    }
+
+    /// Helper method to parse tag_ids into the ids of the measured tags
+    pub fn get_tag_ids(&self) -> Vec<String> {
+        parse_tag_ids(self.tag_ids.as_deref())
+    }
+
+    /// Helper method to create a tag_ids string from tag ids
+    pub fn set_tag_ids(tag_ids: Vec<String>) -> Option<String> {
+        if tag_ids.is_empty() {
+            None
+        } else {
+            Some(tag_ids.join(","))
+        }
+    }
+
+    /// Helper method to parse skip_dates into local dates, ignoring malformed entries
+    pub fn get_skip_dates(&self) -> Vec<Date> {
+        match &self.skip_dates {
+            Some(dates_str) => dates_str.split(',').filter_map(|s| parse_date(s.trim())).collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Helper method to create a skip_dates string from local dates, sorted and deduplicated
+    pub fn set_skip_dates(mut dates: Vec<Date>) -> Option<String> {
+        dates.sort();
+        dates.dedup();
+        if dates.is_empty() {
+            None
+        } else {
+            Some(dates.iter().map(|d| d.to_string()).collect::<Vec<_>>().join(","))
+        }
+    }
+
+    /// Check if the period starting on a local date is skipped
+    pub fn is_skipped_on(&self, date: Date) -> bool {
+        self.get_skip_dates().contains(&date)
+    }
+}
+
+/// Parse a "YYYY-MM-DD" date as written by `Date`'s Display, as used for skip dates and date ranges
+pub fn parse_date(value: &str) -> Option<Date> {
+    let mut parts = value.splitn(3, '-');
+    let year = parts.next()?.parse().ok()?;
+    let month = Month::try_from(parts.next()?.parse::<u8>().ok()?).ok()?;
+    let day = parts.next()?.parse().ok()?;
+    Date::from_calendar_date(year, month, day).ok()
+}
+
+/// Split a stored comma-separated tag_ids string, shared by templates and the tides created from them
+pub fn parse_tag_ids(tag_ids: Option<&str>) -> Vec<String> {
+    tag_ids
+        .map(|tag_ids| {
+            tag_ids
+                .split(',')
+                .map(str::trim)
+                .filter(|tag_id| !tag_id.is_empty())
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use time::macros::datetime;
+    use time::macros::{date, datetime};
 
     #[test]
     fn test_new_tide_template() {
         let first_tide = datetime!(2025-01-01 0:00 UTC);
         let template = TideTemplate::new(
-            "creating".to_string(),
-            "daily".to_string(),
+            MetricsType::Creating,
+            TideFrequency::Daily,
             100.0,
             first_tide,
             Some("1,2,3,4,5".to_string()), // Weekdays only
         );
 
-        assert_eq!(template.metrics_type, "creating");
-        assert_eq!(template.tide_frequency, "daily");
+        assert_eq!(template.metrics_type, MetricsType::Creating);
+        assert_eq!(template.tide_frequency, TideFrequency::Daily);
         assert_eq!(template.goal_amount, 100.0);
         assert_eq!(template.first_tide, first_tide);
         assert_eq!(template.day_of_week, Some("1,2,3,4,5".to_string()));
@@ -87,8 +218,8 @@ mod tests {
     fn test_get_days_of_week_with_specific_days() {
         let first_tide = datetime!(2025-01-01 0:00 UTC);
         let template = TideTemplate::new(
-            "creating".to_string(),
-            "daily".to_string(),
+            MetricsType::Creating,
+            TideFrequency::Daily,
             100.0,
             first_tide,
             Some("1,3,5".to_string()), // Monday, Wednesday, Friday
@@ -102,8 +233,8 @@ mod tests {
     fn test_get_days_of_week_all_days() {
         let first_tide = datetime!(2025-01-01 0:00 UTC);
         let template = TideTemplate::new(
-            "creating".to_string(),
-            "daily".to_string(),
+            MetricsType::Creating,
+            TideFrequency::Daily,
             100.0,
             first_tide,
             None, // All days
@@ -117,8 +248,8 @@ mod tests {
     fn test_get_days_of_week_filters_invalid() {
         let first_tide = datetime!(2025-01-01 0:00 UTC);
         let template = TideTemplate::new(
-            "creating".to_string(),
-            "daily".to_string(),
+            MetricsType::Creating,
+            TideFrequency::Daily,
             100.0,
             first_tide,
             Some("1,3,8,invalid,5".to_string()), // 8 and "invalid" should be filtered out
@@ -132,8 +263,8 @@ mod tests {
     fn test_get_days_of_week_empty_string() {
         let first_tide = datetime!(2025-01-01 0:00 UTC);
         let template = TideTemplate::new(
-            "creating".to_string(),
-            "daily".to_string(),
+            MetricsType::Creating,
+            TideFrequency::Daily,
             100.0,
             first_tide,
             Some("".to_string()), // Empty string
@@ -179,8 +310,8 @@ mod tests {
         
         let first_tide = datetime!(2025-01-01 0:00 UTC);
         let template = TideTemplate::new(
-            "creating".to_string(),
-            "daily".to_string(),
+            MetricsType::Creating,
+            TideFrequency::Daily,
             100.0,
             first_tide,
             days_string,
@@ -189,4 +320,67 @@ mod tests {
         let parsed_days = template.get_days_of_week();
         assert_eq!(parsed_days, original_days);
     }
-} 
\ No newline at end of file
+
+    #[test]
+    fn test_progress_milestones() {
+        let mut template = TideTemplate::new(
+            MetricsType::Creating,
+            TideFrequency::Daily,
+            100.0,
+            datetime!(2025-01-01 0:00 UTC),
+            None,
+        );
+        assert_eq!(template.get_progress_milestones(), vec![50, 90, 100]);
+
+        template.progress_milestones = TideTemplate::set_progress_milestones(vec![25, 75]);
+        assert_eq!(template.progress_milestones, Some("25,75".to_string()));
+        assert_eq!(template.get_progress_milestones(), vec![25, 75]);
+
+        template.progress_milestones = Some("0,50,101,x".to_string());
+        assert_eq!(template.get_progress_milestones(), vec![50]);
+
+        assert_eq!(TideTemplate::set_progress_milestones(vec![50, 90, 100]), None);
+    }
+
+    #[test]
+    fn test_tag_ids() {
+        let template = TideTemplate::new_for_tags(
+            vec!["coding-tag-id".to_string(), "writing-tag-id".to_string()],
+            TideFrequency::Weekly,
+            600.0,
+            datetime!(2025-01-01 0:00 UTC),
+            None,
+        );
+        assert_eq!(template.metrics_type, MetricsType::Tags);
+        assert_eq!(template.tag_ids, Some("coding-tag-id,writing-tag-id".to_string()));
+        assert_eq!(template.get_tag_ids(), vec!["coding-tag-id", "writing-tag-id"]);
+
+        assert_eq!(TideTemplate::set_tag_ids(vec![]), None);
+        assert_eq!(parse_tag_ids(Some(" a, ,b ")), vec!["a", "b"]);
+        assert!(parse_tag_ids(None).is_empty());
+    }
+
+    #[test]
+    fn test_skip_dates_round_trip() {
+        let mut template = TideTemplate::new(
+            MetricsType::Creating,
+            TideFrequency::Daily,
+            100.0,
+            datetime!(2025-01-01 0:00 UTC),
+            None,
+        );
+        assert!(template.get_skip_dates().is_empty());
+
+        template.skip_dates = TideTemplate::set_skip_dates(vec![
+            date!(2025-01-07),
+            date!(2025-01-06),
+            date!(2025-01-07),
+        ]);
+        assert_eq!(template.skip_dates, Some("2025-01-06,2025-01-07".to_string()));
+        assert!(template.is_skipped_on(date!(2025-01-06)));
+        assert!(!template.is_skipped_on(date!(2025-01-08)));
+
+        template.skip_dates = Some("2025-01-06,2025-02-30,invalid, 2025-03-01".to_string());
+        assert_eq!(template.get_skip_dates(), vec![date!(2025-01-06), date!(2025-03-01)]);
+    }
+}
@@ -0,0 +1,35 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use time::OffsetDateTime;
+
+use super::{recurrence_rule::RecurrenceRule, tide_template::TideTemplate, tide_types::GoalDirection};
+
+/// A recorded revision of a template's goal, kept so past tides can be read against the goal
+/// that was in effect when they ran
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct TideTemplateVersion {
+    pub tide_template_id: String,
+    pub version: i64,
+    pub goal_amount: f64,
+    pub goal_direction: GoalDirection,
+    pub day_of_week: Option<String>,
+    pub recurrence_rule: Option<RecurrenceRule>,
+    pub tag_ids: Option<String>,
+    pub effective_from: OffsetDateTime, // When this version replaced the previous one
+}
+
+impl TideTemplateVersion {
+    /// Snapshot the template's current version
+    pub fn from_template(template: &TideTemplate, effective_from: OffsetDateTime) -> Self {
+        Self {
+            tide_template_id: template.id.clone(),
+            version: template.version,
+            goal_amount: template.goal_amount,
+            goal_direction: template.goal_direction,
+            day_of_week: template.day_of_week.clone(),
+            recurrence_rule: template.recurrence_rule.clone(),
+            tag_ids: template.tag_ids.clone(),
+            effective_from,
+        }
+    }
+}
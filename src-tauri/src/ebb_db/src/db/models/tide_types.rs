@@ -0,0 +1,258 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum TideValueError {
+    #[error("Invalid tide frequency: '{0}'")]
+    InvalidFrequency(String),
+    #[error("Invalid metrics type: '{0}'")]
+    InvalidMetricsType(String),
+    #[error("Invalid goal direction: '{0}'")]
+    InvalidGoalDirection(String),
+    #[error("Invalid tide status: '{0}'")]
+    InvalidTideStatus(String),
+}
+
+/// How often a tide repeats - stored as lowercase text ("daily", "weekly", ...)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TideFrequency {
+    Daily,
+    Weekly,
+    Monthly,
+    Indefinite,
+}
+
+impl TideFrequency {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TideFrequency::Daily => "daily",
+            TideFrequency::Weekly => "weekly",
+            TideFrequency::Monthly => "monthly",
+            TideFrequency::Indefinite => "indefinite",
+        }
+    }
+}
+
+impl FromStr for TideFrequency {
+    type Err = TideValueError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "daily" => Ok(TideFrequency::Daily),
+            "weekly" => Ok(TideFrequency::Weekly),
+            "monthly" => Ok(TideFrequency::Monthly),
+            "indefinite" => Ok(TideFrequency::Indefinite),
+            _ => Err(TideValueError::InvalidFrequency(s.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for TideFrequency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// What a tide measures - the name of the default tag whose time counts toward the goal,
+/// `Tags` for time tagged with any of the tide's `tag_ids` (e.g. category tags like "coding"),
+/// or one of the non-tag metrics measured by their own provider
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MetricsType {
+    Creating,
+    Consuming,
+    Neutral,
+    Idle,
+    Tags,
+    /// App switches per hour of active time
+    AppSwitchRate,
+    /// Minutes spent in finished flow sessions
+    FlowSessionMinutes,
+    /// Number of finished flow sessions
+    FlowSessionCount,
+}
+
+impl MetricsType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MetricsType::Creating => "creating",
+            MetricsType::Consuming => "consuming",
+            MetricsType::Neutral => "neutral",
+            MetricsType::Idle => "idle",
+            MetricsType::Tags => "tags",
+            MetricsType::AppSwitchRate => "app_switch_rate",
+            MetricsType::FlowSessionMinutes => "flow_session_minutes",
+            MetricsType::FlowSessionCount => "flow_session_count",
+        }
+    }
+}
+
+impl FromStr for MetricsType {
+    type Err = TideValueError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "creating" => Ok(MetricsType::Creating),
+            "consuming" => Ok(MetricsType::Consuming),
+            "neutral" => Ok(MetricsType::Neutral),
+            "idle" => Ok(MetricsType::Idle),
+            "tags" => Ok(MetricsType::Tags),
+            "app_switch_rate" => Ok(MetricsType::AppSwitchRate),
+            "flow_session_minutes" => Ok(MetricsType::FlowSessionMinutes),
+            "flow_session_count" => Ok(MetricsType::FlowSessionCount),
+            _ => Err(TideValueError::InvalidMetricsType(s.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for MetricsType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Whether a tide's goal is a minimum to reach or a limit to stay under
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GoalDirection {
+    /// Succeeds once the amount reaches the goal ("at least 2 hours of creating")
+    Minimum,
+    /// Succeeds if the period ends with the amount at or under the goal, and fails
+    /// as soon as it goes over ("no more than 60 minutes of consuming")
+    Limit,
+}
+
+impl GoalDirection {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            GoalDirection::Minimum => "minimum",
+            GoalDirection::Limit => "limit",
+        }
+    }
+}
+
+impl FromStr for GoalDirection {
+    type Err = TideValueError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "minimum" => Ok(GoalDirection::Minimum),
+            "limit" => Ok(GoalDirection::Limit),
+            _ => Err(TideValueError::InvalidGoalDirection(s.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for GoalDirection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+// These enums are stored as TEXT so existing rows stay compatible
+// Shared with other models whose enums are stored the same way
+macro_rules! impl_sqlite_text_enum {
+    ($ty:ty) => {
+        impl sqlx::Type<sqlx::Sqlite> for $ty {
+            fn type_info() -> sqlx::sqlite::SqliteTypeInfo {
+                <String as sqlx::Type<sqlx::Sqlite>>::type_info()
+            }
+
+            fn compatible(ty: &sqlx::sqlite::SqliteTypeInfo) -> bool {
+                <String as sqlx::Type<sqlx::Sqlite>>::compatible(ty)
+            }
+        }
+
+        impl<'r> sqlx::Decode<'r, sqlx::Sqlite> for $ty {
+            fn decode(
+                value: sqlx::sqlite::SqliteValueRef<'r>,
+            ) -> Result<Self, sqlx::error::BoxDynError> {
+                let text = <String as sqlx::Decode<sqlx::Sqlite>>::decode(value)?;
+                Ok(text.parse::<$ty>()?)
+            }
+        }
+
+        impl<'q> sqlx::Encode<'q, sqlx::Sqlite> for $ty {
+            fn encode_by_ref(
+                &self,
+                args: &mut Vec<sqlx::sqlite::SqliteArgumentValue<'q>>,
+            ) -> Result<sqlx::encode::IsNull, Box<dyn std::error::Error + Send + Sync>> {
+                args.push(sqlx::sqlite::SqliteArgumentValue::Text(self.as_str().into()));
+                Ok(sqlx::encode::IsNull::No)
+            }
+        }
+    };
+}
+
+pub(crate) use impl_sqlite_text_enum;
+
+impl_sqlite_text_enum!(TideFrequency);
+impl_sqlite_text_enum!(MetricsType);
+impl_sqlite_text_enum!(GoalDirection);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tide_frequency_round_trip() {
+        for frequency in [
+            TideFrequency::Daily,
+            TideFrequency::Weekly,
+            TideFrequency::Monthly,
+            TideFrequency::Indefinite,
+        ] {
+            assert_eq!(frequency.as_str().parse::<TideFrequency>(), Ok(frequency));
+        }
+        assert_eq!(
+            "fortnightly".parse::<TideFrequency>(),
+            Err(TideValueError::InvalidFrequency("fortnightly".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_metrics_type_round_trip() {
+        for metrics_type in [
+            MetricsType::Creating,
+            MetricsType::Consuming,
+            MetricsType::Neutral,
+            MetricsType::Idle,
+            MetricsType::Tags,
+            MetricsType::AppSwitchRate,
+            MetricsType::FlowSessionMinutes,
+            MetricsType::FlowSessionCount,
+        ] {
+            assert_eq!(metrics_type.as_str().parse::<MetricsType>(), Ok(metrics_type));
+        }
+        assert!("Creating".parse::<MetricsType>().is_err());
+    }
+
+    #[test]
+    fn test_goal_direction_round_trip() {
+        for goal_direction in [GoalDirection::Minimum, GoalDirection::Limit] {
+            assert_eq!(goal_direction.as_str().parse::<GoalDirection>(), Ok(goal_direction));
+        }
+        assert_eq!(
+            "maximum".parse::<GoalDirection>(),
+            Err(TideValueError::InvalidGoalDirection("maximum".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_serde_uses_stored_text() {
+        assert_eq!(serde_json::to_string(&TideFrequency::Weekly).unwrap(), "\"weekly\"");
+        assert_eq!(
+            serde_json::from_str::<MetricsType>("\"creating\"").unwrap(),
+            MetricsType::Creating
+        );
+        assert_eq!(
+            serde_json::to_string(&MetricsType::FlowSessionCount).unwrap(),
+            "\"flow_session_count\""
+        );
+        assert!(serde_json::from_str::<TideFrequency>("\"hourly\"").is_err());
+    }
+}
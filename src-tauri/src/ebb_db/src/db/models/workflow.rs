@@ -0,0 +1,149 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use sqlx::{Decode, Encode, FromRow, Type};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WorkflowDifficulty {
+    Easy,
+    Medium,
+    Hard,
+}
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SlackSettings {
+    #[serde(default)]
+    pub dnd_enabled: bool,
+    // Catch-all for fields this version doesn't know about, kept when the settings are saved
+    #[serde(flatten)]
+    pub additional: HashMap<String, serde_json::Value>,
+}
+
+/// Settings of a workflow, stored as JSON in `workflow.settings` with the frontend's camelCase keys
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkflowSettings {
+    #[serde(default)]
+    pub typewriter_mode: bool,
+    #[serde(default)]
+    pub has_breathing: bool,
+    #[serde(default)]
+    pub has_music: bool,
+    /// Whether the workflow's apps are the only ones allowed instead of the ones blocked
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub is_allow_list: Option<bool>,
+    /// Session length in minutes, None for open-ended sessions
+    #[serde(default)]
+    pub default_duration: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub selected_playlist: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub selected_playlist_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub difficulty: Option<WorkflowDifficulty>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub slack: Option<SlackSettings>,
+    // Catch-all for fields this version doesn't know about, kept when the settings are saved
+    #[serde(flatten)]
+    pub additional: HashMap<String, serde_json::Value>,
+}
+
+impl WorkflowSettings {
+    /// Whether Slack do-not-disturb is turned on for sessions with this workflow
+    pub fn slack_dnd_enabled(&self) -> bool {
+        self.slack.as_ref().is_some_and(|slack| slack.dnd_enabled)
+    }
+}
+
+// Custom implementation for SQLx to handle JSON serialization
+impl Type<sqlx::Sqlite> for WorkflowSettings {
+    fn type_info() -> sqlx::sqlite::SqliteTypeInfo {
+        <String as Type<sqlx::Sqlite>>::type_info()
+    }
+}
+
+impl<'r> Decode<'r, sqlx::Sqlite> for WorkflowSettings {
+    fn decode(value: sqlx::sqlite::SqliteValueRef<'r>) -> Result<Self, sqlx::error::BoxDynError> {
+        let json_str = <String as Decode<sqlx::Sqlite>>::decode(value)?;
+        let settings: WorkflowSettings = serde_json::from_str(&json_str)?;
+        Ok(settings)
+    }
+}
+
+impl<'q> Encode<'q, sqlx::Sqlite> for WorkflowSettings {
+    fn encode_by_ref(
+        &self,
+        args: &mut Vec<sqlx::sqlite::SqliteArgumentValue<'q>>,
+    ) -> Result<sqlx::encode::IsNull, Box<dyn std::error::Error + Send + Sync>> {
+        let json_str = serde_json::to_string(self)?;
+        args.push(sqlx::sqlite::SqliteArgumentValue::Text(json_str.into()));
+        Ok(sqlx::encode::IsNull::No)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Workflow {
+    pub id: String,
+    pub name: String,
+    pub settings: WorkflowSettings,
+    pub last_selected: Option<OffsetDateTime>,
+    pub created_at: OffsetDateTime,
+    pub updated_at: OffsetDateTime,
+}
+
+impl Workflow {
+    pub fn new(name: String, settings: WorkflowSettings) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            name,
+            settings,
+            last_selected: None,
+            created_at: OffsetDateTime::now_utc(),
+            updated_at: OffsetDateTime::now_utc(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_settings_keep_unknown_fields() {
+        let json = r#"{
+            "typewriterMode": false,
+            "hasBreathing": true,
+            "hasMusic": true,
+            "isAllowList": false,
+            "defaultDuration": 25,
+            "selectedPlaylist": "playlist-1",
+            "difficulty": "hard",
+            "slack": { "dndEnabled": true, "statusText": "Focusing" },
+            "spotifyShuffle": true
+        }"#;
+
+        let settings: WorkflowSettings = serde_json::from_str(json).unwrap();
+        assert!(settings.has_breathing);
+        assert_eq!(settings.default_duration, Some(25));
+        assert_eq!(settings.difficulty, Some(WorkflowDifficulty::Hard));
+        assert!(settings.slack_dnd_enabled());
+        assert_eq!(settings.additional.get("spotifyShuffle"), Some(&serde_json::json!(true)));
+
+        let round_trip: serde_json::Value = serde_json::to_value(&settings).unwrap();
+        assert_eq!(round_trip, serde_json::from_str::<serde_json::Value>(json).unwrap());
+    }
+
+    #[test]
+    fn test_settings_default_missing_fields() {
+        let settings: WorkflowSettings =
+            serde_json::from_str(r#"{ "hasMusic": true, "defaultDuration": null }"#).unwrap();
+        assert!(settings.has_music);
+        assert!(!settings.typewriter_mode);
+        assert_eq!(settings.default_duration, None);
+        assert!(!settings.slack_dnd_enabled());
+    }
+}
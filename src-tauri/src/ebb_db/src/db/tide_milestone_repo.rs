@@ -0,0 +1,89 @@
+use sqlx::{Pool, Sqlite};
+use time::OffsetDateTime;
+
+use crate::db::models::tide_milestone::TideMilestone;
+
+pub type Result<T> = std::result::Result<T, Box<dyn std::error::Error + Send + Sync>>;
+
+pub struct TideMilestoneRepo {
+    pool: Pool<Sqlite>,
+}
+
+impl TideMilestoneRepo {
+    pub fn new(pool: Pool<Sqlite>) -> Self {
+        Self { pool }
+    }
+
+    /// Record that a tide reached a milestone
+    /// Returns false if it was already recorded, so callers only notify once
+    pub async fn record_milestone(
+        &self,
+        tide_id: &str,
+        threshold: u8,
+        reached_at: OffsetDateTime,
+    ) -> Result<bool> {
+        let result = sqlx::query(
+            "INSERT OR IGNORE INTO tide_milestone (tide_id, threshold, reached_at) VALUES (?1, ?2, ?3)"
+        )
+        .bind(tide_id)
+        .bind(threshold)
+        .bind(reached_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn get_milestones_for_tide(&self, tide_id: &str) -> Result<Vec<TideMilestone>> {
+        let milestones = sqlx::query_as::<_, TideMilestone>(
+            "SELECT * FROM tide_milestone WHERE tide_id = ?1 ORDER BY threshold ASC"
+        )
+        .bind(tide_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(milestones)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::models::tide::Tide;
+    use crate::db::models::tide_types::{MetricsType, TideFrequency};
+    use crate::db::tide_repo::TideRepo;
+    use crate::db_manager;
+    use time::macros::datetime;
+
+    async fn create_tide(pool: &Pool<Sqlite>) -> Tide {
+        let tide = Tide::new(
+            datetime!(2025-01-06 0:00 UTC),
+            Some(datetime!(2025-01-07 0:00 UTC)),
+            MetricsType::Creating,
+            TideFrequency::Daily,
+            100.0,
+            "default-daily-template".to_string(),
+        );
+        TideRepo::new(pool.clone()).create_tide(&tide).await.unwrap();
+        tide
+    }
+
+    #[tokio::test]
+    async fn test_record_milestone_only_once() -> Result<()> {
+        let pool = db_manager::create_test_db().await;
+        let repo = TideMilestoneRepo::new(pool.clone());
+        let tide = create_tide(&pool).await;
+
+        assert!(repo.record_milestone(&tide.id, 90, datetime!(2025-01-06 12:00 UTC)).await?);
+        assert!(repo.record_milestone(&tide.id, 50, datetime!(2025-01-06 10:00 UTC)).await?);
+        assert!(!repo.record_milestone(&tide.id, 50, datetime!(2025-01-06 11:00 UTC)).await?);
+
+        let milestones = repo.get_milestones_for_tide(&tide.id).await?;
+        assert_eq!(milestones.len(), 2);
+        assert_eq!(milestones[0].threshold, 50);
+        assert_eq!(milestones[0].reached_at, datetime!(2025-01-06 10:00 UTC));
+        assert_eq!(milestones[1].threshold, 90);
+
+        Ok(())
+    }
+}
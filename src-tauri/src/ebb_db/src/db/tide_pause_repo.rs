@@ -0,0 +1,118 @@
+use sqlx::{Pool, Sqlite};
+use time::Date;
+
+use crate::db::models::tide_pause::TidePause;
+
+pub type Result<T> = std::result::Result<T, Box<dyn std::error::Error + Send + Sync>>;
+
+pub struct TidePauseRepo {
+    pool: Pool<Sqlite>,
+}
+
+impl TidePauseRepo {
+    pub fn new(pool: Pool<Sqlite>) -> Self {
+        Self { pool }
+    }
+
+    pub async fn create_pause(&self, pause: &TidePause) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO tide_pause (id, start_date, end_date, reason, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)"
+        )
+        .bind(&pause.id)
+        .bind(pause.start_date)
+        .bind(pause.end_date)
+        .bind(&pause.reason)
+        .bind(pause.created_at)
+        .bind(pause.updated_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn get_pause(&self, id: &str) -> Result<Option<TidePause>> {
+        let pause = sqlx::query_as::<_, TidePause>("SELECT * FROM tide_pause WHERE id = ?1")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(pause)
+    }
+
+    pub async fn get_all_pauses(&self) -> Result<Vec<TidePause>> {
+        let pauses = sqlx::query_as::<_, TidePause>(
+            "SELECT * FROM tide_pause ORDER BY start_date ASC"
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(pauses)
+    }
+
+    /// Get the pauses that cover a local date
+    pub async fn get_pauses_on(&self, date: Date) -> Result<Vec<TidePause>> {
+        let pauses = sqlx::query_as::<_, TidePause>(
+            "SELECT * FROM tide_pause WHERE start_date <= ?1 AND end_date >= ?1 ORDER BY start_date ASC"
+        )
+        .bind(date)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(pauses)
+    }
+
+    pub async fn delete_pause(&self, id: &str) -> Result<()> {
+        sqlx::query("DELETE FROM tide_pause WHERE id = ?1")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db_manager;
+    use time::macros::date;
+
+    #[tokio::test]
+    async fn test_create_get_and_delete_pause() -> Result<()> {
+        let pool = db_manager::create_test_db().await;
+        let repo = TidePauseRepo::new(pool);
+
+        let pause = TidePause::new(date!(2025-01-06), date!(2025-01-10), Some("Vacation".to_string()));
+        repo.create_pause(&pause).await?;
+
+        let retrieved = repo.get_pause(&pause.id).await?.unwrap();
+        assert_eq!(retrieved.start_date, date!(2025-01-06));
+        assert_eq!(retrieved.end_date, date!(2025-01-10));
+        assert_eq!(retrieved.reason, Some("Vacation".to_string()));
+        assert_eq!(repo.get_all_pauses().await?.len(), 1);
+
+        repo.delete_pause(&pause.id).await?;
+        assert!(repo.get_pause(&pause.id).await?.is_none());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_get_pauses_on() -> Result<()> {
+        let pool = db_manager::create_test_db().await;
+        let repo = TidePauseRepo::new(pool);
+
+        repo.create_pause(&TidePause::new(date!(2025-01-06), date!(2025-01-10), None)).await?;
+        repo.create_pause(&TidePause::new(date!(2025-12-24), date!(2026-01-02), None)).await?;
+
+        assert!(repo.get_pauses_on(date!(2025-01-05)).await?.is_empty());
+        assert_eq!(repo.get_pauses_on(date!(2025-01-06)).await?.len(), 1);
+        assert_eq!(repo.get_pauses_on(date!(2025-01-10)).await?.len(), 1);
+        assert!(repo.get_pauses_on(date!(2025-01-11)).await?.is_empty());
+        // Date text sorts chronologically across years
+        assert_eq!(repo.get_pauses_on(date!(2026-01-01)).await?.len(), 1);
+
+        Ok(())
+    }
+}
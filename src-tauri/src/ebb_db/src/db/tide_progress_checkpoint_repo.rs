@@ -0,0 +1,111 @@
+use sqlx::{Pool, Sqlite};
+
+use crate::db::models::tide_progress_checkpoint::TideProgressCheckpoint;
+
+pub type Result<T> = std::result::Result<T, Box<dyn std::error::Error + Send + Sync>>;
+
+pub struct TideProgressCheckpointRepo {
+    pool: Pool<Sqlite>,
+}
+
+impl TideProgressCheckpointRepo {
+    pub fn new(pool: Pool<Sqlite>) -> Self {
+        Self { pool }
+    }
+
+    pub async fn get_checkpoint(&self, tide_id: &str) -> Result<Option<TideProgressCheckpoint>> {
+        let checkpoint = sqlx::query_as::<_, TideProgressCheckpoint>(
+            "SELECT * FROM tide_progress_checkpoint WHERE tide_id = ?1"
+        )
+        .bind(tide_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(checkpoint)
+    }
+
+    pub async fn upsert_checkpoint(&self, checkpoint: &TideProgressCheckpoint) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO tide_progress_checkpoint (tide_id, amount, last_evaluation_time, updated_at)
+             VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT(tide_id) DO UPDATE SET
+                amount = excluded.amount,
+                last_evaluation_time = excluded.last_evaluation_time,
+                updated_at = excluded.updated_at"
+        )
+        .bind(&checkpoint.tide_id)
+        .bind(checkpoint.amount)
+        .bind(checkpoint.last_evaluation_time)
+        .bind(checkpoint.updated_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn delete_checkpoint(&self, tide_id: &str) -> Result<()> {
+        sqlx::query("DELETE FROM tide_progress_checkpoint WHERE tide_id = ?1")
+            .bind(tide_id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn delete_all_checkpoints(&self) -> Result<()> {
+        sqlx::query("DELETE FROM tide_progress_checkpoint")
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::models::tide::Tide;
+    use crate::db::models::tide_types::{MetricsType, TideFrequency};
+    use crate::db::tide_repo::TideRepo;
+    use crate::db_manager;
+    use time::macros::datetime;
+
+    async fn create_tide(pool: &Pool<Sqlite>) -> Tide {
+        let tide = Tide::new(
+            datetime!(2025-01-06 0:00 UTC),
+            None,
+            MetricsType::Creating,
+            TideFrequency::Indefinite,
+            100.0,
+            "default-daily-template".to_string(),
+        );
+        TideRepo::new(pool.clone()).create_tide(&tide).await.unwrap();
+        tide
+    }
+
+    #[tokio::test]
+    async fn test_upsert_get_and_delete_checkpoint() -> Result<()> {
+        let pool = db_manager::create_test_db().await;
+        let repo = TideProgressCheckpointRepo::new(pool.clone());
+        let tide = create_tide(&pool).await;
+
+        assert!(repo.get_checkpoint(&tide.id).await?.is_none());
+
+        let mut checkpoint =
+            TideProgressCheckpoint::new(tide.id.clone(), 42.5, datetime!(2025-01-06 10:00 UTC));
+        repo.upsert_checkpoint(&checkpoint).await?;
+
+        checkpoint.amount = 60.0;
+        checkpoint.last_evaluation_time = datetime!(2025-01-06 11:00 UTC);
+        repo.upsert_checkpoint(&checkpoint).await?;
+
+        let retrieved = repo.get_checkpoint(&tide.id).await?.unwrap();
+        assert_eq!(retrieved.amount, 60.0);
+        assert_eq!(retrieved.last_evaluation_time, datetime!(2025-01-06 11:00 UTC));
+
+        repo.delete_checkpoint(&tide.id).await?;
+        assert!(repo.get_checkpoint(&tide.id).await?.is_none());
+
+        Ok(())
+    }
+}
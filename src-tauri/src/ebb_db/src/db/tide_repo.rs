@@ -1,7 +1,10 @@
 use sqlx::{Pool, Sqlite};
 use time::OffsetDateTime;
 
-use crate::db::models::tide::Tide;
+use crate::db::models::{
+    tide::{Tide, TideStatus},
+    tide_types::GoalDirection,
+};
 
 pub type Result<T> = std::result::Result<T, Box<dyn std::error::Error + Send + Sync>>;
 
@@ -16,20 +19,23 @@ impl TideRepo {
 
     pub async fn create_tide(&self, tide: &Tide) -> Result<()> {
         sqlx::query(
-            "INSERT INTO tide (id, start, end, completed_at, metrics_type, tide_frequency, goal_amount, actual_amount, tide_template_id, created_at, updated_at) 
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)"
+            "INSERT INTO tide (id, start, end, completed_at, metrics_type, tide_frequency, goal_amount, actual_amount, tide_template_id, created_at, updated_at, status, tag_ids, goal_direction) 
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)"
         )
         .bind(&tide.id)
         .bind(&tide.start)
         .bind(&tide.end)
         .bind(&tide.completed_at)
-        .bind(&tide.metrics_type)
-        .bind(&tide.tide_frequency)
+        .bind(tide.metrics_type)
+        .bind(tide.tide_frequency)
         .bind(tide.goal_amount)
         .bind(tide.actual_amount)
         .bind(&tide.tide_template_id)
         .bind(&tide.created_at)
         .bind(&tide.updated_at)
+        .bind(tide.status)
+        .bind(&tide.tag_ids)
+        .bind(tide.goal_direction)
         .execute(&self.pool)
         .await?;
 
@@ -69,6 +75,18 @@ impl TideRepo {
         Ok(tides)
     }
 
+    /// Get tides whose period ended before `evaluation_time` and haven't been given a terminal status yet
+    pub async fn get_expired_unfinalized_tides(&self, evaluation_time: OffsetDateTime) -> Result<Vec<Tide>> {
+        let tides = sqlx::query_as::<_, Tide>(
+            "SELECT * FROM tide WHERE end IS NOT NULL AND end < ?1 AND status IS NULL ORDER BY start ASC"
+        )
+        .bind(evaluation_time)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(tides)
+    }
+
     pub async fn get_tides_by_template(&self, template_id: &str) -> Result<Vec<Tide>> {
         let tides = sqlx::query_as::<_, Tide>(
             "SELECT * FROM tide WHERE tide_template_id = ?1 ORDER BY start DESC"
@@ -100,19 +118,22 @@ impl TideRepo {
         sqlx::query(
             "UPDATE tide 
              SET start = ?2, end = ?3, completed_at = ?4, metrics_type = ?5, tide_frequency = ?6, 
-                 goal_amount = ?7, actual_amount = ?8, tide_template_id = ?9, updated_at = ?10
+                 goal_amount = ?7, actual_amount = ?8, tide_template_id = ?9, updated_at = ?10, status = ?11, tag_ids = ?12, goal_direction = ?13
              WHERE id = ?1"
         )
         .bind(&tide.id)
         .bind(&tide.start)
         .bind(&tide.end)
         .bind(&tide.completed_at)
-        .bind(&tide.metrics_type)
-        .bind(&tide.tide_frequency)
+        .bind(tide.metrics_type)
+        .bind(tide.tide_frequency)
         .bind(tide.goal_amount)
         .bind(tide.actual_amount)
         .bind(&tide.tide_template_id)
         .bind(&tide.updated_at)
+        .bind(tide.status)
+        .bind(&tide.tag_ids)
+        .bind(tide.goal_direction)
         .execute(&self.pool)
         .await?;
 
@@ -145,6 +166,26 @@ impl TideRepo {
         Ok(())
     }
 
+    /// Set the goal a tide is measured against, used when a template edit applies to its running tide
+    pub async fn update_tide_goal(
+        &self,
+        id: &str,
+        goal_amount: f64,
+        goal_direction: GoalDirection,
+    ) -> Result<()> {
+        sqlx::query(
+            "UPDATE tide SET goal_amount = ?2, goal_direction = ?3, updated_at = ?4 WHERE id = ?1"
+        )
+        .bind(id)
+        .bind(goal_amount)
+        .bind(goal_direction)
+        .bind(OffsetDateTime::now_utc())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
     pub async fn complete_tide(&self, id: &str) -> Result<()> {
         let now = OffsetDateTime::now_utc();
         sqlx::query(
@@ -159,6 +200,28 @@ impl TideRepo {
         Ok(())
     }
 
+    /// Record a tide's final amount and terminal status
+    pub async fn finalize_tide(
+        &self,
+        id: &str,
+        actual_amount: f64,
+        status: TideStatus,
+        completed_at: Option<OffsetDateTime>,
+    ) -> Result<()> {
+        sqlx::query(
+            "UPDATE tide SET actual_amount = ?2, status = ?3, completed_at = ?4, updated_at = ?5 WHERE id = ?1"
+        )
+        .bind(id)
+        .bind(actual_amount)
+        .bind(status)
+        .bind(completed_at)
+        .bind(OffsetDateTime::now_utc())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
     pub async fn delete_tide(&self, id: &str) -> Result<()> {
         sqlx::query("DELETE FROM tide WHERE id = ?1")
             .bind(id)
@@ -206,6 +269,7 @@ impl TideRepo {
 mod tests {
     use crate::db_manager;
     use crate::db::models::tide_template::TideTemplate;
+    use crate::db::models::tide_types::{MetricsType, TideFrequency};
     use crate::db::tide_template_repo::TideTemplateRepo;
     use time::macros::datetime;
 
@@ -214,14 +278,19 @@ mod tests {
     fn create_test_template() -> TideTemplate {
         let first_tide = datetime!(2025-01-01 0:00 UTC);
         TideTemplate::new(
-            "creating".to_string(),
-            "daily".to_string(),
+            MetricsType::Creating,
+            TideFrequency::Daily,
             100.0,
             first_tide,
             None,
         )
     }
 
+    /// Create a tide for a daily template's period starting at `start`
+    fn create_test_tide(template: &TideTemplate, start: OffsetDateTime) -> Tide {
+        Tide::from_template_with_end(template, start, Some(start + time::Duration::days(1)))
+    }
+
     #[tokio::test]
     async fn test_create_and_get_tide() -> Result<()> {
         let pool = db_manager::create_test_db().await;
@@ -231,15 +300,15 @@ mod tests {
         let template = create_test_template();
         template_repo.create_tide_template(&template).await?;
         
-        let tide = Tide::from_template(&template, OffsetDateTime::now_utc());
+        let tide = create_test_tide(&template, OffsetDateTime::now_utc());
         tide_repo.create_tide(&tide).await?;
         
         let retrieved = tide_repo.get_tide(&tide.id).await?;
         assert!(retrieved.is_some());
         let retrieved = retrieved.unwrap();
         assert_eq!(retrieved.id, tide.id);
-        assert_eq!(retrieved.metrics_type, "creating");
-        assert_eq!(retrieved.tide_frequency, "daily");
+        assert_eq!(retrieved.metrics_type, MetricsType::Creating);
+        assert_eq!(retrieved.tide_frequency, TideFrequency::Daily);
         assert_eq!(retrieved.goal_amount, 100.0);
         assert_eq!(retrieved.actual_amount, 0.0);
         
@@ -255,8 +324,8 @@ mod tests {
         let template = create_test_template();
         template_repo.create_tide_template(&template).await?;
         
-        let mut tide1 = Tide::from_template(&template, OffsetDateTime::now_utc());
-        let tide2 = Tide::from_template(&template, OffsetDateTime::now_utc());
+        let mut tide1 = create_test_tide(&template, OffsetDateTime::now_utc());
+        let tide2 = create_test_tide(&template, OffsetDateTime::now_utc());
         
         // End one tide
         tide1.end = Some(OffsetDateTime::now_utc());
@@ -280,7 +349,7 @@ mod tests {
         let template = create_test_template();
         template_repo.create_tide_template(&template).await?;
         
-        let tide = Tide::from_template(&template, OffsetDateTime::now_utc());
+        let tide = create_test_tide(&template, OffsetDateTime::now_utc());
         tide_repo.create_tide(&tide).await?;
         
         tide_repo.update_actual_amount(&tide.id, 50.0).await?;
@@ -301,7 +370,7 @@ mod tests {
         let template = create_test_template();
         template_repo.create_tide_template(&template).await?;
         
-        let tide = Tide::from_template(&template, OffsetDateTime::now_utc());
+        let tide = create_test_tide(&template, OffsetDateTime::now_utc());
         tide_repo.create_tide(&tide).await?;
         
         let end_time = OffsetDateTime::now_utc();
@@ -321,15 +390,15 @@ mod tests {
         let template_repo = TideTemplateRepo::new(pool);
         
         let first_tide = datetime!(2025-01-01 0:00 UTC);
-        let template1 = TideTemplate::new("creating".to_string(), "daily".to_string(), 100.0, first_tide, None);
-        let template2 = TideTemplate::new("learning".to_string(), "weekly".to_string(), 500.0, first_tide, None);
+        let template1 = TideTemplate::new(MetricsType::Creating, TideFrequency::Daily, 100.0, first_tide, None);
+        let template2 = TideTemplate::new(MetricsType::Consuming, TideFrequency::Weekly, 500.0, first_tide, None);
         
         template_repo.create_tide_template(&template1).await?;
         template_repo.create_tide_template(&template2).await?;
         
-        let tide1 = Tide::from_template(&template1, OffsetDateTime::now_utc());
-        let tide2 = Tide::from_template(&template1, OffsetDateTime::now_utc());
-        let tide3 = Tide::from_template(&template2, OffsetDateTime::now_utc());
+        let tide1 = create_test_tide(&template1, OffsetDateTime::now_utc());
+        let tide2 = create_test_tide(&template1, OffsetDateTime::now_utc());
+        let tide3 = Tide::from_template_with_end(&template2, OffsetDateTime::now_utc(), None);
         
         tide_repo.create_tide(&tide1).await?;
         tide_repo.create_tide(&tide2).await?;
@@ -369,7 +438,7 @@ mod tests {
         let template = create_test_template();
         template_repo.create_tide_template(&template).await?;
         
-        let tide = Tide::from_template(&template, OffsetDateTime::now_utc());
+        let tide = create_test_tide(&template, OffsetDateTime::now_utc());
         tide_repo.create_tide(&tide).await?;
         
         let latest_end = tide_repo.get_latest_tide_end_for_template(&template.id).await?;
@@ -391,9 +460,9 @@ mod tests {
         let now = OffsetDateTime::now_utc();
         
         // Create multiple tides with different end times
-        let tide1 = Tide::from_template(&template, now - time::Duration::days(3));
-        let tide2 = Tide::from_template(&template, now - time::Duration::days(2)); 
-        let tide3 = Tide::from_template(&template, now - time::Duration::days(1));
+        let tide1 = create_test_tide(&template, now - time::Duration::days(3));
+        let tide2 = create_test_tide(&template, now - time::Duration::days(2)); 
+        let tide3 = create_test_tide(&template, now - time::Duration::days(1));
         
         tide_repo.create_tide(&tide1).await?;
         tide_repo.create_tide(&tide2).await?;
@@ -419,8 +488,8 @@ mod tests {
         let now = OffsetDateTime::now_utc();
         
         // Create one tide with end time and one indefinite tide (end = None)
-        let tide_with_end = Tide::from_template(&template, now - time::Duration::days(2));
-        let mut indefinite_tide = Tide::from_template(&template, now - time::Duration::days(1));
+        let tide_with_end = create_test_tide(&template, now - time::Duration::days(2));
+        let mut indefinite_tide = create_test_tide(&template, now - time::Duration::days(1));
         indefinite_tide.end = None; // Indefinite tide
         
         tide_repo.create_tide(&tide_with_end).await?;
@@ -444,8 +513,8 @@ mod tests {
         template_repo.create_tide_template(&template).await?;
         
         // Create multiple indefinite tides (all end = None)
-        let mut tide1 = Tide::from_template(&template, OffsetDateTime::now_utc() - time::Duration::days(3));
-        let mut tide2 = Tide::from_template(&template, OffsetDateTime::now_utc() - time::Duration::days(2));
+        let mut tide1 = create_test_tide(&template, OffsetDateTime::now_utc() - time::Duration::days(3));
+        let mut tide2 = create_test_tide(&template, OffsetDateTime::now_utc() - time::Duration::days(2));
         tide1.end = None;
         tide2.end = None;
         
@@ -466,8 +535,8 @@ mod tests {
         
         // Create two different templates
         let first_tide = datetime!(2025-01-01 0:00 UTC);
-        let template1 = TideTemplate::new("creating".to_string(), "daily".to_string(), 100.0, first_tide, None);
-        let template2 = TideTemplate::new("learning".to_string(), "weekly".to_string(), 500.0, first_tide, None);
+        let template1 = TideTemplate::new(MetricsType::Creating, TideFrequency::Daily, 100.0, first_tide, None);
+        let template2 = TideTemplate::new(MetricsType::Consuming, TideFrequency::Weekly, 500.0, first_tide, None);
         
         template_repo.create_tide_template(&template1).await?;
         template_repo.create_tide_template(&template2).await?;
@@ -475,8 +544,8 @@ mod tests {
         let now = OffsetDateTime::now_utc();
         
         // Create tides for template1 only
-        let tide1 = Tide::from_template(&template1, now - time::Duration::days(2));
-        let tide2 = Tide::from_template(&template1, now - time::Duration::days(1));
+        let tide1 = create_test_tide(&template1, now - time::Duration::days(2));
+        let tide2 = create_test_tide(&template1, now - time::Duration::days(1));
         
         tide_repo.create_tide(&tide1).await?;
         tide_repo.create_tide(&tide2).await?;
@@ -523,7 +592,7 @@ mod tests {
         
         // Create a tide that starts within the range
         let tide_start = datetime!(2025-01-01 12:00:00 UTC);
-        let tide = Tide::from_template(&template, tide_start);
+        let tide = create_test_tide(&template, tide_start);
         tide_repo.create_tide(&tide).await?;
         
         let range_start = datetime!(2025-01-01 00:00:00 UTC);
@@ -546,7 +615,7 @@ mod tests {
         
         // Create a tide that starts before the range
         let tide_start = datetime!(2024-12-31 12:00:00 UTC);
-        let tide = Tide::from_template(&template, tide_start);
+        let tide = create_test_tide(&template, tide_start);
         tide_repo.create_tide(&tide).await?;
         
         let range_start = datetime!(2025-01-01 00:00:00 UTC);
@@ -569,7 +638,7 @@ mod tests {
         
         // Create a tide that starts after the range
         let tide_start = datetime!(2025-01-02 12:00:00 UTC);
-        let tide = Tide::from_template(&template, tide_start);
+        let tide = create_test_tide(&template, tide_start);
         tide_repo.create_tide(&tide).await?;
         
         let range_start = datetime!(2025-01-01 00:00:00 UTC);
@@ -592,7 +661,7 @@ mod tests {
         
         // Create a tide that starts exactly at range_start
         let tide_start = datetime!(2025-01-01 00:00:00 UTC);
-        let tide = Tide::from_template(&template, tide_start);
+        let tide = create_test_tide(&template, tide_start);
         tide_repo.create_tide(&tide).await?;
         
         let range_start = datetime!(2025-01-01 00:00:00 UTC);
@@ -614,10 +683,10 @@ mod tests {
         template_repo.create_tide_template(&template).await?;
         
         // Create multiple tides - some in range, some outside
-        let tide1 = Tide::from_template(&template, datetime!(2024-12-31 12:00:00 UTC)); // Before range
-        let tide2 = Tide::from_template(&template, datetime!(2025-01-01 08:00:00 UTC)); // In range
-        let tide3 = Tide::from_template(&template, datetime!(2025-01-01 16:00:00 UTC)); // In range
-        let tide4 = Tide::from_template(&template, datetime!(2025-01-03 12:00:00 UTC)); // After range
+        let tide1 = create_test_tide(&template, datetime!(2024-12-31 12:00:00 UTC)); // Before range
+        let tide2 = create_test_tide(&template, datetime!(2025-01-01 08:00:00 UTC)); // In range
+        let tide3 = create_test_tide(&template, datetime!(2025-01-01 16:00:00 UTC)); // In range
+        let tide4 = create_test_tide(&template, datetime!(2025-01-03 12:00:00 UTC)); // After range
         
         tide_repo.create_tide(&tide1).await?;
         tide_repo.create_tide(&tide2).await?;
@@ -641,14 +710,14 @@ mod tests {
         
         // Create two different templates
         let first_tide = datetime!(2025-01-01 0:00 UTC);
-        let template1 = TideTemplate::new("creating".to_string(), "daily".to_string(), 100.0, first_tide, None);
-        let template2 = TideTemplate::new("learning".to_string(), "weekly".to_string(), 500.0, first_tide, None);
+        let template1 = TideTemplate::new(MetricsType::Creating, TideFrequency::Daily, 100.0, first_tide, None);
+        let template2 = TideTemplate::new(MetricsType::Consuming, TideFrequency::Weekly, 500.0, first_tide, None);
         
         template_repo.create_tide_template(&template1).await?;
         template_repo.create_tide_template(&template2).await?;
         
         // Create a tide for template1 within the range
-        let tide = Tide::from_template(&template1, datetime!(2025-01-01 12:00:00 UTC));
+        let tide = create_test_tide(&template1, datetime!(2025-01-01 12:00:00 UTC));
         tide_repo.create_tide(&tide).await?;
         
         let range_start = datetime!(2025-01-01 00:00:00 UTC);
@@ -679,7 +748,7 @@ mod tests {
         let range_end = datetime!(2025-01-01 20:00:00 UTC);
         
         // Tide that starts exactly at range_end (should NOT be included)
-        let tide_at_end = Tide::from_template(&template, range_end);
+        let tide_at_end = create_test_tide(&template, range_end);
         tide_repo.create_tide(&tide_at_end).await?;
         
         let has_tide = tide_repo.has_tide_for_date_range(&template.id, range_start, range_end).await?;
@@ -687,4 +756,55 @@ mod tests {
         
         Ok(())
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_get_expired_unfinalized_tides() -> Result<()> {
+        let pool = db_manager::create_test_db().await;
+        let tide_repo = TideRepo::new(pool.clone());
+        let template_repo = TideTemplateRepo::new(pool);
+
+        let template = create_test_template();
+        template_repo.create_tide_template(&template).await?;
+
+        let expired = create_test_tide(&template, datetime!(2025-01-06 0:00 UTC));
+        let active = create_test_tide(&template, datetime!(2025-01-07 0:00 UTC));
+        let mut finalized = create_test_tide(&template, datetime!(2025-01-05 0:00 UTC));
+        finalized.status = Some(TideStatus::Missed);
+        tide_repo.create_tide(&expired).await?;
+        tide_repo.create_tide(&active).await?;
+        tide_repo.create_tide(&finalized).await?;
+
+        let tides = tide_repo
+            .get_expired_unfinalized_tides(datetime!(2025-01-07 12:00 UTC))
+            .await?;
+        assert_eq!(tides.len(), 1);
+        assert_eq!(tides[0].id, expired.id);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_finalize_tide() -> Result<()> {
+        let pool = db_manager::create_test_db().await;
+        let tide_repo = TideRepo::new(pool.clone());
+        let template_repo = TideTemplateRepo::new(pool);
+
+        let template = create_test_template();
+        template_repo.create_tide_template(&template).await?;
+
+        let tide = create_test_tide(&template, datetime!(2025-01-06 0:00 UTC));
+        tide_repo.create_tide(&tide).await?;
+
+        tide_repo
+            .finalize_tide(&tide.id, 40.0, TideStatus::Partial, None)
+            .await?;
+
+        let retrieved = tide_repo.get_tide(&tide.id).await?.unwrap();
+        assert_eq!(retrieved.actual_amount, 40.0);
+        assert_eq!(retrieved.status, Some(TideStatus::Partial));
+        assert!(retrieved.is_finalized());
+        assert!(retrieved.completed_at.is_none());
+
+        Ok(())
+    }
+}
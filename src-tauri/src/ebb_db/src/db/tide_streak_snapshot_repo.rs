@@ -0,0 +1,131 @@
+use sqlx::{Pool, Sqlite};
+use time::OffsetDateTime;
+
+use crate::db::models::tide_streak_snapshot::TideStreakSnapshot;
+
+pub type Result<T> = std::result::Result<T, Box<dyn std::error::Error + Send + Sync>>;
+
+pub struct TideStreakSnapshotRepo {
+    pool: Pool<Sqlite>,
+}
+
+impl TideStreakSnapshotRepo {
+    pub fn new(pool: Pool<Sqlite>) -> Self {
+        Self { pool }
+    }
+
+    pub async fn get_snapshot(&self, tide_template_id: &str) -> Result<Option<TideStreakSnapshot>> {
+        let snapshot = sqlx::query_as::<_, TideStreakSnapshot>(
+            "SELECT * FROM tide_streak_snapshot WHERE tide_template_id = ?1"
+        )
+        .bind(tide_template_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(snapshot)
+    }
+
+    pub async fn upsert_snapshot(&self, snapshot: &TideStreakSnapshot) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO tide_streak_snapshot (tide_template_id, current_streak, longest_streak, last_tide_start, day_of_week, created_at, updated_at, recurrence_rule)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+             ON CONFLICT(tide_template_id) DO UPDATE SET
+                current_streak = excluded.current_streak,
+                longest_streak = excluded.longest_streak,
+                last_tide_start = excluded.last_tide_start,
+                day_of_week = excluded.day_of_week,
+                recurrence_rule = excluded.recurrence_rule,
+                updated_at = excluded.updated_at"
+        )
+        .bind(&snapshot.tide_template_id)
+        .bind(snapshot.current_streak)
+        .bind(snapshot.longest_streak)
+        .bind(snapshot.last_tide_start)
+        .bind(&snapshot.day_of_week)
+        .bind(snapshot.created_at)
+        .bind(OffsetDateTime::now_utc())
+        .bind(&snapshot.recurrence_rule)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn delete_snapshot(&self, tide_template_id: &str) -> Result<()> {
+        sqlx::query("DELETE FROM tide_streak_snapshot WHERE tide_template_id = ?1")
+            .bind(tide_template_id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::models::tide_template::TideTemplate;
+    use crate::db::models::tide_types::{MetricsType, TideFrequency};
+    use crate::db::tide_template_repo::TideTemplateRepo;
+    use crate::db_manager;
+    use time::macros::datetime;
+
+    async fn create_template(pool: &Pool<Sqlite>) -> TideTemplate {
+        let template = TideTemplate::new(
+            MetricsType::Creating,
+            TideFrequency::Daily,
+            100.0,
+            datetime!(2025-01-01 0:00 UTC),
+            None,
+        );
+        TideTemplateRepo::new(pool.clone())
+            .create_tide_template(&template)
+            .await
+            .unwrap();
+        template
+    }
+
+    #[tokio::test]
+    async fn test_upsert_and_get_snapshot() -> Result<()> {
+        let pool = db_manager::create_test_db().await;
+        let repo = TideStreakSnapshotRepo::new(pool.clone());
+        let template = create_template(&pool).await;
+
+        assert!(repo.get_snapshot(&template.id).await?.is_none());
+
+        let mut snapshot = TideStreakSnapshot::new(template.id.clone(), None);
+        snapshot.current_streak = 3;
+        snapshot.longest_streak = 5;
+        snapshot.last_tide_start = Some(datetime!(2025-01-06 0:00 UTC));
+        repo.upsert_snapshot(&snapshot).await?;
+
+        let retrieved = repo.get_snapshot(&template.id).await?.unwrap();
+        assert_eq!(retrieved.current_streak, 3);
+        assert_eq!(retrieved.longest_streak, 5);
+        assert_eq!(retrieved.last_tide_start, Some(datetime!(2025-01-06 0:00 UTC)));
+
+        // Upserting again updates the existing row
+        snapshot.current_streak = 0;
+        repo.upsert_snapshot(&snapshot).await?;
+        let retrieved = repo.get_snapshot(&template.id).await?.unwrap();
+        assert_eq!(retrieved.current_streak, 0);
+        assert_eq!(retrieved.longest_streak, 5);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_delete_snapshot() -> Result<()> {
+        let pool = db_manager::create_test_db().await;
+        let repo = TideStreakSnapshotRepo::new(pool.clone());
+        let template = create_template(&pool).await;
+
+        repo.upsert_snapshot(&TideStreakSnapshot::new(template.id.clone(), None))
+            .await?;
+        repo.delete_snapshot(&template.id).await?;
+
+        assert!(repo.get_snapshot(&template.id).await?.is_none());
+
+        Ok(())
+    }
+}
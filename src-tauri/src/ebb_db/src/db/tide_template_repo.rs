@@ -1,9 +1,22 @@
 use sqlx::{Pool, Sqlite};
+use time::OffsetDateTime;
 
-use crate::db::models::tide_template::TideTemplate;
+use crate::db::models::{tide_template::TideTemplate, tide_types::TideValueError};
+use crate::db::tide_template_validation::validate_template;
 
 pub type Result<T> = std::result::Result<T, Box<dyn std::error::Error + Send + Sync>>;
 
+/// Surface rows with unknown metrics_type/tide_frequency/goal_direction text as a `TideValueError`
+/// instead of a generic column decode error
+fn map_validation_error(error: sqlx::Error) -> Box<dyn std::error::Error + Send + Sync> {
+    if let sqlx::Error::ColumnDecode { source, .. } = &error
+        && let Some(value_error) = source.downcast_ref::<TideValueError>()
+    {
+        return Box::new(value_error.clone());
+    }
+    Box::new(error)
+}
+
 pub struct TideTemplateRepo {
     pool: Pool<Sqlite>,
 }
@@ -13,19 +26,28 @@ impl TideTemplateRepo {
         Self { pool }
     }
 
+    /// Validate and insert a template, storing its normalized copy
     pub async fn create_tide_template(&self, template: &TideTemplate) -> Result<()> {
+        let template = validate_template(template, OffsetDateTime::now_utc())?;
         sqlx::query(
-            "INSERT INTO tide_template (id, metrics_type, tide_frequency, first_tide, day_of_week, goal_amount, created_at, updated_at) 
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)"
+            "INSERT INTO tide_template (id, metrics_type, tide_frequency, first_tide, day_of_week, goal_amount, progress_milestones, tag_ids, goal_direction, skip_dates, version, archived_at, created_at, updated_at, recurrence_rule) 
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)"
         )
         .bind(&template.id)
-        .bind(&template.metrics_type)
-        .bind(&template.tide_frequency)
+        .bind(template.metrics_type)
+        .bind(template.tide_frequency)
         .bind(&template.first_tide)
         .bind(&template.day_of_week)
         .bind(template.goal_amount)
+        .bind(&template.progress_milestones)
+        .bind(&template.tag_ids)
+        .bind(template.goal_direction)
+        .bind(&template.skip_dates)
+        .bind(template.version)
+        .bind(template.archived_at)
         .bind(&template.created_at)
         .bind(&template.updated_at)
+        .bind(&template.recurrence_rule)
         .execute(&self.pool)
         .await?;
 
@@ -36,40 +58,76 @@ impl TideTemplateRepo {
         let template = sqlx::query_as::<_, TideTemplate>("SELECT * FROM tide_template WHERE id = ?1")
             .bind(id)
             .fetch_optional(&self.pool)
-            .await?;
+            .await
+            .map_err(map_validation_error)?;
 
         Ok(template)
     }
 
+    /// Get every template that hasn't been archived
     pub async fn get_all_tide_templates(&self) -> Result<Vec<TideTemplate>> {
         let templates = sqlx::query_as::<_, TideTemplate>(
-            "SELECT * FROM tide_template ORDER BY created_at DESC"
+            "SELECT * FROM tide_template WHERE archived_at IS NULL ORDER BY created_at DESC"
         )
         .fetch_all(&self.pool)
-        .await?;
+        .await
+        .map_err(map_validation_error)?;
 
         Ok(templates)
     }
 
+    /// Validate and update a template, storing its normalized copy
     pub async fn update_tide_template(&self, template: &TideTemplate) -> Result<()> {
+        let template = validate_template(template, OffsetDateTime::now_utc())?;
         sqlx::query(
             "UPDATE tide_template 
-             SET metrics_type = ?2, tide_frequency = ?3, first_tide = ?4, day_of_week = ?5, goal_amount = ?6, updated_at = ?7
+             SET metrics_type = ?2, tide_frequency = ?3, first_tide = ?4, day_of_week = ?5, goal_amount = ?6, progress_milestones = ?7, tag_ids = ?8, goal_direction = ?9, skip_dates = ?10, version = ?11, archived_at = ?12, updated_at = ?13, recurrence_rule = ?14
              WHERE id = ?1"
         )
         .bind(&template.id)
-        .bind(&template.metrics_type)
-        .bind(&template.tide_frequency)
+        .bind(template.metrics_type)
+        .bind(template.tide_frequency)
         .bind(&template.first_tide)
         .bind(&template.day_of_week)
         .bind(template.goal_amount)
+        .bind(&template.progress_milestones)
+        .bind(&template.tag_ids)
+        .bind(template.goal_direction)
+        .bind(&template.skip_dates)
+        .bind(template.version)
+        .bind(template.archived_at)
         .bind(&template.updated_at)
+        .bind(&template.recurrence_rule)
         .execute(&self.pool)
         .await?;
 
         Ok(())
     }
 
+    pub async fn get_archived_tide_templates(&self) -> Result<Vec<TideTemplate>> {
+        let templates = sqlx::query_as::<_, TideTemplate>(
+            "SELECT * FROM tide_template WHERE archived_at IS NOT NULL ORDER BY archived_at DESC"
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_validation_error)?;
+
+        Ok(templates)
+    }
+
+    /// Soft-delete a template so its tides keep a valid parent
+    pub async fn archive_tide_template(&self, id: &str, archived_at: OffsetDateTime) -> Result<()> {
+        sqlx::query("UPDATE tide_template SET archived_at = ?2, updated_at = ?2 WHERE id = ?1")
+            .bind(id)
+            .bind(archived_at)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Permanently delete a template
+    /// Tides reference their template, so templates with tides should be archived instead
     pub async fn delete_tide_template(&self, id: &str) -> Result<()> {
         sqlx::query("DELETE FROM tide_template WHERE id = ?1")
             .bind(id)
@@ -83,6 +141,8 @@ impl TideTemplateRepo {
 #[cfg(test)]
 mod tests {
     use crate::db_manager;
+    use crate::db::models::tide_types::{GoalDirection, MetricsType, TideFrequency};
+    use crate::db::tide_template_validation::TideTemplateError;
     use time::macros::datetime;
 
     use super::*;
@@ -90,8 +150,8 @@ mod tests {
     fn create_test_template() -> TideTemplate {
         let first_tide = datetime!(2025-01-01 0:00 UTC);
         TideTemplate::new(
-            "creating".to_string(),
-            "daily".to_string(),
+            MetricsType::Creating,
+            TideFrequency::Daily,
             100.0,
             first_tide,
             None,
@@ -110,8 +170,8 @@ mod tests {
         assert!(retrieved.is_some());
         let retrieved = retrieved.unwrap();
         assert_eq!(retrieved.id, template.id);
-        assert_eq!(retrieved.metrics_type, "creating");
-        assert_eq!(retrieved.tide_frequency, "daily");
+        assert_eq!(retrieved.metrics_type, MetricsType::Creating);
+        assert_eq!(retrieved.tide_frequency, TideFrequency::Daily);
         assert_eq!(retrieved.goal_amount, 100.0);
         
         Ok(())
@@ -123,8 +183,8 @@ mod tests {
         let repo = TideTemplateRepo::new(pool);
         
         let first_tide = datetime!(2025-01-01 0:00 UTC);
-        let template1 = TideTemplate::new("creating".to_string(), "daily".to_string(), 100.0, first_tide, None);
-        let template2 = TideTemplate::new("learning".to_string(), "weekly".to_string(), 500.0, first_tide, None);
+        let template1 = TideTemplate::new(MetricsType::Creating, TideFrequency::Daily, 100.0, first_tide, None);
+        let template2 = TideTemplate::new(MetricsType::Consuming, TideFrequency::Weekly, 500.0, first_tide, None);
         
         repo.create_tide_template(&template1).await?;
         repo.create_tide_template(&template2).await?;
@@ -144,13 +204,41 @@ mod tests {
         repo.create_tide_template(&template).await?;
         
         template.goal_amount = 150.0;
+        template.goal_direction = GoalDirection::Limit;
+        template.progress_milestones = Some("25,100".to_string());
+        template.skip_dates = Some("2025-01-06".to_string());
         template.updated_at = time::OffsetDateTime::now_utc();
         repo.update_tide_template(&template).await?;
         
         let updated = repo.get_tide_template(&template.id).await?;
         assert!(updated.is_some());
-        assert_eq!(updated.unwrap().goal_amount, 150.0);
-        
+        let updated = updated.unwrap();
+        assert_eq!(updated.goal_amount, 150.0);
+        assert_eq!(updated.goal_direction, GoalDirection::Limit);
+        assert_eq!(updated.progress_milestones, Some("25,100".to_string()));
+        assert_eq!(updated.skip_dates, Some("2025-01-06".to_string()));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_tag_template_round_trip() -> Result<()> {
+        let pool = db_manager::create_test_db().await;
+        let repo = TideTemplateRepo::new(pool);
+
+        let template = TideTemplate::new_for_tags(
+            vec!["coding-tag-id".to_string(), "writing-tag-id".to_string()],
+            TideFrequency::Weekly,
+            600.0,
+            datetime!(2025-01-01 0:00 UTC),
+            None,
+        );
+        repo.create_tide_template(&template).await?;
+
+        let retrieved = repo.get_tide_template(&template.id).await?.unwrap();
+        assert_eq!(retrieved.metrics_type, MetricsType::Tags);
+        assert_eq!(retrieved.get_tag_ids(), vec!["coding-tag-id", "writing-tag-id"]);
+
         Ok(())
     }
 
@@ -169,4 +257,89 @@ mod tests {
         
         Ok(())
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_archived_templates_are_excluded_but_still_readable() -> Result<()> {
+        let pool = db_manager::create_test_db().await;
+        let repo = TideTemplateRepo::new(pool);
+
+        let template = create_test_template();
+        repo.create_tide_template(&template).await?;
+        let before = repo.get_all_tide_templates().await?.len();
+
+        repo.archive_tide_template(&template.id, datetime!(2025-02-01 0:00 UTC)).await?;
+
+        assert_eq!(repo.get_all_tide_templates().await?.len(), before - 1);
+        let archived = repo.get_archived_tide_templates().await?;
+        assert_eq!(archived.len(), 1);
+        assert_eq!(archived[0].archived_at, Some(datetime!(2025-02-01 0:00 UTC)));
+        assert!(repo.get_tide_template(&template.id).await?.unwrap().is_archived());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_writes_are_validated_and_normalized() -> Result<()> {
+        let pool = db_manager::create_test_db().await;
+        let repo = TideTemplateRepo::new(pool);
+
+        let mut template = create_test_template();
+        template.goal_amount = 0.0;
+        let error = repo.create_tide_template(&template).await.unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<TideTemplateError>(),
+            Some(TideTemplateError::InvalidGoalAmount { .. })
+        ));
+        assert!(repo.get_tide_template(&template.id).await?.is_none());
+
+        template.goal_amount = 120.0;
+        template.day_of_week = Some("5,1,3,1".to_string());
+        repo.create_tide_template(&template).await?;
+        let stored = repo.get_tide_template(&template.id).await?.unwrap();
+        assert_eq!(stored.day_of_week, Some("1,3,5".to_string()));
+
+        template.day_of_week = Some("7".to_string());
+        assert!(repo.update_tide_template(&template).await.is_err());
+        let stored = repo.get_tide_template(&template.id).await?.unwrap();
+        assert_eq!(stored.day_of_week, Some("1,3,5".to_string()));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_invalid_stored_values_surface_as_validation_errors() -> Result<()> {
+        let pool = db_manager::create_test_db().await;
+        let repo = TideTemplateRepo::new(pool.clone());
+
+        sqlx::query(
+            "INSERT INTO tide_template (id, metrics_type, tide_frequency, first_tide, goal_amount)
+             VALUES ('bad-template', 'creating', 'yearly', '2025-01-01 00:00:00', 100.0)"
+        )
+        .execute(&pool)
+        .await?;
+
+        let error = repo.get_tide_template("bad-template").await.unwrap_err();
+        assert_eq!(
+            error.downcast_ref::<TideValueError>(),
+            Some(&TideValueError::InvalidFrequency("yearly".to_string()))
+        );
+
+        let error = repo.get_all_tide_templates().await.unwrap_err();
+        assert!(error.downcast_ref::<TideValueError>().is_some());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_existing_text_values_still_decode() -> Result<()> {
+        let pool = db_manager::create_test_db().await;
+        let repo = TideTemplateRepo::new(pool);
+
+        // Seeded by migration 20 as plain text
+        let template = repo.get_tide_template("default-weekly-template").await?.unwrap();
+        assert_eq!(template.metrics_type, MetricsType::Creating);
+        assert_eq!(template.tide_frequency, TideFrequency::Weekly);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,282 @@
+use thiserror::Error;
+use time::OffsetDateTime;
+
+use crate::db::models::{
+    tide_template::{parse_date, parse_tag_ids, TideTemplate},
+    tide_types::MetricsType,
+};
+
+/// Why a template was rejected before being written
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum TideTemplateError {
+    #[error("Invalid goal amount: {goal_amount} (must be a positive number)")]
+    InvalidGoalAmount { goal_amount: f64 },
+    #[error("Invalid days of week: '{day_of_week}' (expected comma-separated days 0-6)")]
+    InvalidDaysOfWeek { day_of_week: String },
+    #[error("First tide is in the future: {first_tide}")]
+    FirstTideInFuture { first_tide: OffsetDateTime },
+    #[error("Invalid progress milestones: '{progress_milestones}' (expected comma-separated percentages 1-100)")]
+    InvalidProgressMilestones { progress_milestones: String },
+    #[error("Invalid tag ids for {metrics_type} tide: '{tag_ids}' (tag tides need at least one tag id, other metrics take none)")]
+    InvalidTagIds { metrics_type: MetricsType, tag_ids: String },
+    #[error("Invalid skip dates: '{skip_dates}' (expected comma-separated dates YYYY-MM-DD)")]
+    InvalidSkipDates { skip_dates: String },
+}
+
+pub type Result<T> = std::result::Result<T, TideTemplateError>;
+
+/// Validate a template before it is written and return the normalized copy to store
+/// Runs on every write through `TideTemplateRepo`. Unknown frequencies and metrics types are
+/// already rejected when the template is decoded
+pub fn validate_template(template: &TideTemplate, now: OffsetDateTime) -> Result<TideTemplate> {
+    validate_goal_amount(template.goal_amount)?;
+
+    if template.first_tide > now {
+        return Err(TideTemplateError::FirstTideInFuture {
+            first_tide: template.first_tide,
+        });
+    }
+
+    let day_of_week = normalize_day_of_week(template.day_of_week.as_deref())?;
+    let progress_milestones = normalize_progress_milestones(template.progress_milestones.as_deref())?;
+    let tag_ids = normalize_tag_ids(template.metrics_type, template.tag_ids.as_deref())?;
+    let skip_dates = normalize_skip_dates(template.skip_dates.as_deref())?;
+
+    Ok(TideTemplate {
+        day_of_week,
+        progress_milestones,
+        tag_ids,
+        skip_dates,
+        ..template.clone()
+    })
+}
+
+/// Goals must be a positive, finite number, in the unit of the template's metric
+pub fn validate_goal_amount(goal_amount: f64) -> Result<()> {
+    if !goal_amount.is_finite() || goal_amount <= 0.0 {
+        return Err(TideTemplateError::InvalidGoalAmount { goal_amount });
+    }
+    Ok(())
+}
+
+/// Parse a comma-separated day_of_week string (0=Sunday, 6=Saturday) into its stored form
+/// Days are de-duplicated and sorted, and all seven days normalize to None.
+/// Empty strings and out-of-range entries are rejected rather than read as "never".
+pub fn normalize_day_of_week(day_of_week: Option<&str>) -> Result<Option<String>> {
+    let Some(day_of_week) = day_of_week else {
+        return Ok(None);
+    };
+
+    let invalid = || TideTemplateError::InvalidDaysOfWeek {
+        day_of_week: day_of_week.to_string(),
+    };
+
+    let mut days = day_of_week
+        .split(',')
+        .map(|day| match day.trim().parse::<u8>() {
+            Ok(day) if day <= 6 => Ok(day),
+            _ => Err(invalid()),
+        })
+        .collect::<Result<Vec<u8>>>()?;
+    days.sort_unstable();
+    days.dedup();
+
+    if days.is_empty() {
+        return Err(invalid());
+    }
+
+    Ok(TideTemplate::set_days_of_week(days))
+}
+
+/// Parse a comma-separated progress_milestones string (percentages of the goal) into its stored form
+/// Milestones are de-duplicated and sorted, and the defaults normalize to None.
+pub fn normalize_progress_milestones(progress_milestones: Option<&str>) -> Result<Option<String>> {
+    let Some(progress_milestones) = progress_milestones else {
+        return Ok(None);
+    };
+
+    let invalid = || TideTemplateError::InvalidProgressMilestones {
+        progress_milestones: progress_milestones.to_string(),
+    };
+
+    let mut milestones = progress_milestones
+        .split(',')
+        .map(|milestone| match milestone.trim().parse::<u8>() {
+            Ok(milestone) if (1..=100).contains(&milestone) => Ok(milestone),
+            _ => Err(invalid()),
+        })
+        .collect::<Result<Vec<u8>>>()?;
+    milestones.sort_unstable();
+    milestones.dedup();
+
+    Ok(TideTemplate::set_progress_milestones(milestones))
+}
+
+/// Parse a comma-separated tag_ids string into its stored form
+/// Tag tides must name at least one tag; ids are de-duplicated and sorted. Other metrics types
+/// measure a default tag by name, so they must not carry tag ids.
+pub fn normalize_tag_ids(metrics_type: MetricsType, tag_ids: Option<&str>) -> Result<Option<String>> {
+    let mut parsed = parse_tag_ids(tag_ids);
+    parsed.sort_unstable();
+    parsed.dedup();
+
+    let valid = match metrics_type {
+        MetricsType::Tags => !parsed.is_empty(),
+        _ => tag_ids.is_none(),
+    };
+    if !valid {
+        return Err(TideTemplateError::InvalidTagIds {
+            metrics_type,
+            tag_ids: tag_ids.unwrap_or_default().to_string(),
+        });
+    }
+
+    Ok(TideTemplate::set_tag_ids(parsed))
+}
+
+/// Parse a comma-separated skip_dates string ("YYYY-MM-DD" local dates) into its stored form
+/// Dates are de-duplicated and sorted. Malformed dates are rejected rather than silently ignored.
+pub fn normalize_skip_dates(skip_dates: Option<&str>) -> Result<Option<String>> {
+    let Some(skip_dates) = skip_dates else {
+        return Ok(None);
+    };
+
+    let dates = skip_dates
+        .split(',')
+        .map(|date| {
+            parse_date(date.trim()).ok_or_else(|| TideTemplateError::InvalidSkipDates {
+                skip_dates: skip_dates.to_string(),
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(TideTemplate::set_skip_dates(dates))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::models::tide_types::TideFrequency;
+    use time::macros::datetime;
+
+    fn template_with(goal_amount: f64, day_of_week: Option<&str>) -> TideTemplate {
+        TideTemplate::new(
+            MetricsType::Creating,
+            TideFrequency::Daily,
+            goal_amount,
+            datetime!(2025-01-01 00:00 UTC),
+            day_of_week.map(str::to_string),
+        )
+    }
+
+    #[test]
+    fn test_validate_template_accepts_and_normalizes() {
+        let template = template_with(120.0, Some("5, 1,3,1"));
+        let validated = validate_template(&template, datetime!(2025-01-06 00:00 UTC)).unwrap();
+
+        assert_eq!(validated.id, template.id);
+        assert_eq!(validated.goal_amount, 120.0);
+        assert_eq!(validated.day_of_week, Some("1,3,5".to_string()));
+    }
+
+    #[test]
+    fn test_validate_template_rejects_bad_goal() {
+        let now = datetime!(2025-01-06 00:00 UTC);
+        for goal_amount in [0.0, -30.0, f64::NAN, f64::INFINITY] {
+            let result = validate_template(&template_with(goal_amount, None), now);
+            assert!(matches!(result, Err(TideTemplateError::InvalidGoalAmount { .. })));
+        }
+    }
+
+    #[test]
+    fn test_validate_template_rejects_future_first_tide() {
+        let template = template_with(120.0, None);
+        let result = validate_template(&template, datetime!(2024-12-31 00:00 UTC));
+
+        assert!(matches!(
+            result,
+            Err(TideTemplateError::FirstTideInFuture { first_tide }) if first_tide == template.first_tide
+        ));
+    }
+
+    #[test]
+    fn test_normalize_progress_milestones() {
+        assert_eq!(normalize_progress_milestones(None).unwrap(), None);
+        assert_eq!(normalize_progress_milestones(Some("100,50,90")).unwrap(), None);
+        assert_eq!(normalize_progress_milestones(Some("75, 25,75")).unwrap(), Some("25,75".to_string()));
+
+        for bad in ["", "0", "101", "half", "50,,90"] {
+            assert!(
+                matches!(
+                    normalize_progress_milestones(Some(bad)),
+                    Err(TideTemplateError::InvalidProgressMilestones { .. })
+                ),
+                "expected '{}' to be rejected",
+                bad
+            );
+        }
+    }
+
+    #[test]
+    fn test_normalize_day_of_week() {
+        assert_eq!(normalize_day_of_week(None).unwrap(), None);
+        assert_eq!(normalize_day_of_week(Some("0,1,2,3,4,5,6")).unwrap(), None);
+        assert_eq!(normalize_day_of_week(Some("6,0")).unwrap(), Some("0,6".to_string()));
+
+        for bad in ["", " ", "1,,2", "7", "mon", "-1"] {
+            assert!(
+                matches!(
+                    normalize_day_of_week(Some(bad)),
+                    Err(TideTemplateError::InvalidDaysOfWeek { .. })
+                ),
+                "expected '{}' to be rejected",
+                bad
+            );
+        }
+    }
+
+    #[test]
+    fn test_normalize_tag_ids() {
+        assert_eq!(
+            normalize_tag_ids(MetricsType::Tags, Some("meeting-tag-id, coding-tag-id,meeting-tag-id")).unwrap(),
+            Some("coding-tag-id,meeting-tag-id".to_string())
+        );
+        assert_eq!(normalize_tag_ids(MetricsType::Creating, None).unwrap(), None);
+
+        for (metrics_type, bad) in [
+            (MetricsType::Tags, None),
+            (MetricsType::Tags, Some("")),
+            (MetricsType::Tags, Some(" , ")),
+            (MetricsType::Creating, Some("coding-tag-id")),
+        ] {
+            assert!(
+                matches!(
+                    normalize_tag_ids(metrics_type, bad),
+                    Err(TideTemplateError::InvalidTagIds { .. })
+                ),
+                "expected {:?} for {} to be rejected",
+                bad,
+                metrics_type
+            );
+        }
+    }
+
+    #[test]
+    fn test_normalize_skip_dates() {
+        assert_eq!(normalize_skip_dates(None).unwrap(), None);
+        assert_eq!(
+            normalize_skip_dates(Some("2025-01-07, 2025-01-06,2025-01-07")).unwrap(),
+            Some("2025-01-06,2025-01-07".to_string())
+        );
+        for bad in ["", "2025-02-30", "01/06/2025", "2025-01-06,"] {
+            assert!(
+                matches!(
+                    normalize_skip_dates(Some(bad)),
+                    Err(TideTemplateError::InvalidSkipDates { .. })
+                ),
+                "expected '{}' to be rejected",
+                bad
+            );
+        }
+    }
+}
@@ -0,0 +1,110 @@
+use sqlx::{Pool, Sqlite};
+use time::OffsetDateTime;
+
+use crate::db::models::tide_template_version::TideTemplateVersion;
+
+pub type Result<T> = std::result::Result<T, Box<dyn std::error::Error + Send + Sync>>;
+
+pub struct TideTemplateVersionRepo {
+    pool: Pool<Sqlite>,
+}
+
+impl TideTemplateVersionRepo {
+    pub fn new(pool: Pool<Sqlite>) -> Self {
+        Self { pool }
+    }
+
+    pub async fn create_version(&self, version: &TideTemplateVersion) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO tide_template_version (tide_template_id, version, goal_amount, goal_direction, day_of_week, tag_ids, effective_from, recurrence_rule)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)"
+        )
+        .bind(&version.tide_template_id)
+        .bind(version.version)
+        .bind(version.goal_amount)
+        .bind(version.goal_direction)
+        .bind(&version.day_of_week)
+        .bind(&version.tag_ids)
+        .bind(version.effective_from)
+        .bind(&version.recurrence_rule)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Get every recorded version of a template, oldest first
+    pub async fn get_versions(&self, tide_template_id: &str) -> Result<Vec<TideTemplateVersion>> {
+        let versions = sqlx::query_as::<_, TideTemplateVersion>(
+            "SELECT * FROM tide_template_version WHERE tide_template_id = ?1 ORDER BY version ASC"
+        )
+        .bind(tide_template_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(versions)
+    }
+
+    /// Get the version of a template that was in effect at a given time
+    pub async fn get_version_at(
+        &self,
+        tide_template_id: &str,
+        time: OffsetDateTime,
+    ) -> Result<Option<TideTemplateVersion>> {
+        let version = sqlx::query_as::<_, TideTemplateVersion>(
+            "SELECT * FROM tide_template_version
+             WHERE tide_template_id = ?1 AND effective_from <= ?2
+             ORDER BY version DESC
+             LIMIT 1"
+        )
+        .bind(tide_template_id)
+        .bind(time)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(version)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::models::tide_template::TideTemplate;
+    use crate::db::models::tide_types::{MetricsType, TideFrequency};
+    use crate::db::tide_template_repo::TideTemplateRepo;
+    use crate::db_manager;
+    use time::macros::datetime;
+
+    #[tokio::test]
+    async fn test_versions_are_listed_and_resolved_by_time() -> Result<()> {
+        let pool = db_manager::create_test_db().await;
+        let repo = TideTemplateVersionRepo::new(pool.clone());
+
+        let mut template = TideTemplate::new(
+            MetricsType::Creating,
+            TideFrequency::Daily,
+            60.0,
+            datetime!(2025-01-01 0:00 UTC),
+            None,
+        );
+        TideTemplateRepo::new(pool).create_tide_template(&template).await?;
+        repo.create_version(&TideTemplateVersion::from_template(&template, datetime!(2025-01-01 0:00 UTC)))
+            .await?;
+
+        template.version = 2;
+        template.goal_amount = 90.0;
+        repo.create_version(&TideTemplateVersion::from_template(&template, datetime!(2025-01-10 12:00 UTC)))
+            .await?;
+
+        let versions = repo.get_versions(&template.id).await?;
+        assert_eq!(versions.iter().map(|v| v.version).collect::<Vec<_>>(), vec![1, 2]);
+
+        let before = repo.get_version_at(&template.id, datetime!(2025-01-05 0:00 UTC)).await?.unwrap();
+        assert_eq!(before.goal_amount, 60.0);
+        let after = repo.get_version_at(&template.id, datetime!(2025-01-11 0:00 UTC)).await?.unwrap();
+        assert_eq!(after.goal_amount, 90.0);
+        assert!(repo.get_version_at(&template.id, datetime!(2024-12-31 0:00 UTC)).await?.is_none());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,189 @@
+use sqlx::{Pool, Sqlite};
+use time::OffsetDateTime;
+
+use crate::db::models::workflow::{Workflow, WorkflowSettings};
+
+pub type Result<T> = std::result::Result<T, Box<dyn std::error::Error + Send + Sync>>;
+
+/// Workflows are also written by the frontend, so times are stored as ISO strings like its
+/// `toISOString()` and read back with either format
+pub struct WorkflowRepo {
+    pool: Pool<Sqlite>,
+}
+
+impl WorkflowRepo {
+    pub fn new(pool: Pool<Sqlite>) -> Self {
+        Self { pool }
+    }
+
+    pub async fn create_workflow(&self, workflow: &Workflow) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO workflow (id, name, settings, last_selected, created_at, updated_at)
+             VALUES (
+                ?1, ?2, ?3,
+                strftime('%Y-%m-%dT%H:%M:%fZ', ?4),
+                strftime('%Y-%m-%dT%H:%M:%fZ', ?5),
+                strftime('%Y-%m-%dT%H:%M:%fZ', ?6)
+             )"
+        )
+        .bind(&workflow.id)
+        .bind(&workflow.name)
+        .bind(&workflow.settings)
+        .bind(workflow.last_selected)
+        .bind(workflow.created_at)
+        .bind(workflow.updated_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn get_workflow(&self, id: &str) -> Result<Option<Workflow>> {
+        let workflow = sqlx::query_as::<_, Workflow>("SELECT * FROM workflow WHERE id = ?1")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(workflow)
+    }
+
+    /// Get all workflows, most recently selected first
+    pub async fn get_workflows(&self) -> Result<Vec<Workflow>> {
+        let workflows = sqlx::query_as::<_, Workflow>(
+            "SELECT * FROM workflow ORDER BY last_selected DESC"
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(workflows)
+    }
+
+    /// Get the most recently selected workflow
+    pub async fn get_latest_workflow(&self) -> Result<Option<Workflow>> {
+        let workflow = sqlx::query_as::<_, Workflow>(
+            "SELECT * FROM workflow ORDER BY last_selected DESC LIMIT 1"
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(workflow)
+    }
+
+    /// Get the workflow chosen for smart focus sessions in a device's profile
+    /// None if no workflow is set or the one set no longer exists
+    pub async fn get_smart_default_workflow(&self, device_id: &str) -> Result<Option<Workflow>> {
+        let workflow = sqlx::query_as::<_, Workflow>(
+            "SELECT w.* FROM workflow w
+             JOIN device_profile dp
+               ON w.id = json_extract(dp.preferences, '$.smart_focus_settings.workflow_id')
+             WHERE dp.device_id = ?1"
+        )
+        .bind(device_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(workflow)
+    }
+
+    pub async fn update_workflow(
+        &self,
+        id: &str,
+        name: &str,
+        settings: &WorkflowSettings,
+    ) -> Result<()> {
+        sqlx::query(
+            "UPDATE workflow
+             SET name = ?2, settings = ?3, updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', ?4)
+             WHERE id = ?1"
+        )
+        .bind(id)
+        .bind(name)
+        .bind(settings)
+        .bind(OffsetDateTime::now_utc())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn update_last_selected(&self, id: &str, last_selected: OffsetDateTime) -> Result<()> {
+        sqlx::query(
+            "UPDATE workflow
+             SET last_selected = strftime('%Y-%m-%dT%H:%M:%fZ', ?2),
+                 updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', ?3)
+             WHERE id = ?1"
+        )
+        .bind(id)
+        .bind(last_selected)
+        .bind(OffsetDateTime::now_utc())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn delete_workflow(&self, id: &str) -> Result<()> {
+        sqlx::query("DELETE FROM workflow WHERE id = ?1")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db_manager;
+    use time::macros::datetime;
+
+    #[tokio::test]
+    async fn test_workflow_round_trip_with_frontend_rows() -> Result<()> {
+        let pool = db_manager::create_test_db().await;
+        let repo = WorkflowRepo::new(pool.clone());
+
+        // Saved by the frontend, with a settings key this version doesn't model
+        sqlx::query(
+            "INSERT INTO workflow (id, name, settings, last_selected, created_at, updated_at)
+             VALUES ('frontend', 'Deep Work', ?1, '2025-01-06T09:00:00.000Z', '2025-01-01T00:00:00.000Z', '2025-01-01T00:00:00.000Z')"
+        )
+        .bind(r#"{"typewriterMode":false,"hasBreathing":true,"hasMusic":false,"defaultDuration":50,"futureSetting":1}"#)
+        .execute(&pool)
+        .await?;
+
+        let settings = WorkflowSettings {
+            default_duration: Some(25),
+            ..WorkflowSettings::default()
+        };
+        let workflow = Workflow::new("Light Work".to_string(), settings);
+        repo.create_workflow(&workflow).await?;
+        repo.update_last_selected(&workflow.id, datetime!(2025-01-05 09:00 UTC)).await?;
+
+        let workflows = repo.get_workflows().await?;
+        assert_eq!(
+            workflows.iter().map(|w| w.name.as_str()).collect::<Vec<_>>(),
+            vec!["Deep Work", "Light Work"]
+        );
+        assert_eq!(repo.get_latest_workflow().await?.unwrap().id, "frontend");
+
+        let mut frontend = repo.get_workflow("frontend").await?.unwrap();
+        assert_eq!(frontend.settings.default_duration, Some(50));
+        assert_eq!(frontend.last_selected, Some(datetime!(2025-01-06 09:00 UTC)));
+
+        // Unknown settings survive an update from Rust
+        frontend.settings.has_music = true;
+        repo.update_workflow(&frontend.id, "Deep Work", &frontend.settings).await?;
+        let settings_json: String = sqlx::query_scalar("SELECT settings FROM workflow WHERE id = 'frontend'")
+            .fetch_one(&pool)
+            .await?;
+        let settings_json: serde_json::Value = serde_json::from_str(&settings_json)?;
+        assert_eq!(settings_json["futureSetting"], 1);
+        assert_eq!(settings_json["hasMusic"], true);
+
+        repo.delete_workflow(&workflow.id).await?;
+        assert!(repo.get_workflow(&workflow.id).await?.is_none());
+
+        Ok(())
+    }
+}
@@ -3,3 +3,5 @@ pub mod db_manager;
 pub mod migrations;
 pub mod services;
 pub mod shared_sql_plugin;
+#[cfg(any(test, feature = "test-utils"))]
+pub mod test_utils;
@@ -334,6 +334,136 @@ pub fn get_migrations() -> Vec<Migration> {
             "#,
             kind: MigrationKind::Up,
         },
+        Migration {
+            version: 21,
+            description: "create_tide_streak_snapshot",
+            sql: r#"
+            CREATE TABLE IF NOT EXISTS tide_streak_snapshot (
+                tide_template_id TEXT PRIMARY KEY NOT NULL,
+                current_streak INTEGER NOT NULL DEFAULT 0,
+                longest_streak INTEGER NOT NULL DEFAULT 0,
+                last_tide_start DATETIME,
+                day_of_week TEXT,
+                created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+                updated_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+                FOREIGN KEY (tide_template_id) REFERENCES tide_template (id)
+            );
+            "#,
+            kind: MigrationKind::Up,
+        },
+        Migration {
+            version: 22,
+            description: "add_tide_status",
+            sql: r#"
+            ALTER TABLE tide ADD COLUMN status TEXT;
+            "#,
+            kind: MigrationKind::Up,
+        },
+        Migration {
+            version: 23,
+            description: "add_tide_template_progress_milestones",
+            sql: r#"
+            ALTER TABLE tide_template ADD COLUMN progress_milestones TEXT;
+            "#,
+            kind: MigrationKind::Up,
+        },
+        Migration {
+            version: 24,
+            description: "create_tide_milestone",
+            sql: r#"
+            CREATE TABLE IF NOT EXISTS tide_milestone (
+                tide_id TEXT NOT NULL,
+                threshold INTEGER NOT NULL,
+                reached_at DATETIME NOT NULL,
+                PRIMARY KEY (tide_id, threshold),
+                FOREIGN KEY (tide_id) REFERENCES tide (id)
+            );
+            "#,
+            kind: MigrationKind::Up,
+        },
+        Migration {
+            version: 25,
+            description: "create_tide_progress_checkpoint",
+            sql: r#"
+            CREATE TABLE IF NOT EXISTS tide_progress_checkpoint (
+                tide_id TEXT PRIMARY KEY NOT NULL,
+                amount REAL NOT NULL,
+                last_evaluation_time DATETIME NOT NULL,
+                updated_at DATETIME NOT NULL
+            );
+            "#,
+            kind: MigrationKind::Up,
+        },
+        Migration {
+            version: 26,
+            description: "add_tide_tag_ids",
+            sql: r#"
+            ALTER TABLE tide_template ADD COLUMN tag_ids TEXT;
+            ALTER TABLE tide ADD COLUMN tag_ids TEXT;
+            "#,
+            kind: MigrationKind::Up,
+        },
+        Migration {
+            version: 27,
+            description: "add_tide_goal_direction",
+            sql: r#"
+            ALTER TABLE tide_template ADD COLUMN goal_direction TEXT NOT NULL DEFAULT 'minimum';
+            ALTER TABLE tide ADD COLUMN goal_direction TEXT NOT NULL DEFAULT 'minimum';
+            "#,
+            kind: MigrationKind::Up,
+        },
+        Migration {
+            version: 28,
+            description: "create_tide_pause_and_skip_dates",
+            sql: r#"
+            CREATE TABLE IF NOT EXISTS tide_pause (
+                id TEXT PRIMARY KEY NOT NULL,
+                start_date TEXT NOT NULL,
+                end_date TEXT NOT NULL,
+                reason TEXT,
+                created_at DATETIME NOT NULL,
+                updated_at DATETIME NOT NULL
+            );
+
+            ALTER TABLE tide_template ADD COLUMN skip_dates TEXT;
+            "#,
+            kind: MigrationKind::Up,
+        },
+        Migration {
+            version: 29,
+            description: "add_tide_template_versions_and_archive",
+            sql: r#"
+            ALTER TABLE tide_template ADD COLUMN version INTEGER NOT NULL DEFAULT 1;
+            ALTER TABLE tide_template ADD COLUMN archived_at DATETIME;
+
+            CREATE TABLE IF NOT EXISTS tide_template_version (
+                tide_template_id TEXT NOT NULL,
+                version INTEGER NOT NULL,
+                goal_amount REAL NOT NULL,
+                goal_direction TEXT NOT NULL,
+                day_of_week TEXT,
+                tag_ids TEXT,
+                effective_from DATETIME NOT NULL,
+                PRIMARY KEY (tide_template_id, version),
+                FOREIGN KEY (tide_template_id) REFERENCES tide_template (id)
+            );
+
+            -- Existing templates start at version 1, effective since they were created
+            INSERT INTO tide_template_version (tide_template_id, version, goal_amount, goal_direction, day_of_week, tag_ids, effective_from)
+            SELECT id, 1, goal_amount, goal_direction, day_of_week, tag_ids, created_at FROM tide_template;
+            "#,
+            kind: MigrationKind::Up,
+        },
+        Migration {
+            version: 30,
+            description: "add_tide_template_recurrence_rule",
+            sql: r#"
+            ALTER TABLE tide_template ADD COLUMN recurrence_rule TEXT;
+            ALTER TABLE tide_template_version ADD COLUMN recurrence_rule TEXT;
+            ALTER TABLE tide_streak_snapshot ADD COLUMN recurrence_rule TEXT;
+            "#,
+            kind: MigrationKind::Up,
+        },
     ]
 }
 
@@ -387,6 +517,11 @@ mod tests {
             "user_notification",
             "tide_template",
             "tide",
+            "tide_streak_snapshot",
+            "tide_milestone",
+            "tide_progress_checkpoint",
+            "tide_pause",
+            "tide_template_version",
         ];
 
         for table_name in tables_to_check {
@@ -1 +1,3 @@
+pub mod blocking_service;
 pub mod device_service;
+pub mod flow_session_service;
@@ -0,0 +1,120 @@
+use sqlx::{Pool, Sqlite};
+
+use crate::db::{
+    app_repo::AppRepo,
+    blocking_preference_repo::BlockingPreferenceRepo,
+    models::blocking_preference::BlockableApp,
+    workflow_repo::WorkflowRepo,
+};
+
+pub type Result<T> = std::result::Result<T, Box<dyn std::error::Error + Send + Sync>>;
+
+/// What to block for a workflow, ready to hand to the OS blocker
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct WorkflowBlocking {
+    pub apps: Vec<BlockableApp>,
+    /// True when `apps` are blocked, false when they are the only apps allowed
+    pub is_block_list: bool,
+}
+
+/// Resolves a workflow's blocking preferences into concrete apps
+/// Preferences live in the ebb database, apps and their tags in the CodeClimbers database
+pub struct BlockingService {
+    blocking_preference_repo: BlockingPreferenceRepo,
+    workflow_repo: WorkflowRepo,
+    app_repo: AppRepo,
+}
+
+impl BlockingService {
+    pub fn new_with_pools(ebb_pool: Pool<Sqlite>, codeclimbers_pool: Pool<Sqlite>) -> Self {
+        Self {
+            blocking_preference_repo: BlockingPreferenceRepo::new(ebb_pool.clone()),
+            workflow_repo: WorkflowRepo::new(ebb_pool),
+            app_repo: AppRepo::new(codeclimbers_pool),
+        }
+    }
+
+    /// Resolve what a workflow blocks, expanding category tag preferences to the apps tagged with them
+    pub async fn resolve_workflow_blocking(&self, workflow_id: &str) -> Result<WorkflowBlocking> {
+        let workflow = self
+            .workflow_repo
+            .get_workflow(workflow_id)
+            .await?
+            .ok_or_else(|| format!("Workflow not found: {}", workflow_id))?;
+
+        let preferences = self
+            .blocking_preference_repo
+            .get_workflow_preferences(workflow_id)
+            .await?;
+        let app_ids: Vec<String> = preferences.iter().filter_map(|p| p.app_id.clone()).collect();
+        let tag_ids: Vec<String> = preferences.iter().filter_map(|p| p.tag_id.clone()).collect();
+        let apps = self.app_repo.get_blockable_apps(&app_ids, &tag_ids).await?;
+
+        Ok(WorkflowBlocking {
+            apps,
+            is_block_list: !workflow.settings.is_allow_list.unwrap_or(false),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::models::{
+        blocking_preference::BlockingPreference,
+        workflow::{Workflow, WorkflowSettings},
+    };
+    use crate::db_manager;
+
+    async fn seed_codeclimbers_apps(pool: &Pool<Sqlite>) -> Result<()> {
+        sqlx::query(
+            "CREATE TABLE app (id TEXT PRIMARY KEY NOT NULL, app_external_id TEXT, is_browser BOOLEAN NOT NULL DEFAULT FALSE);
+             CREATE TABLE app_tag (id TEXT PRIMARY KEY NOT NULL, app_id TEXT NOT NULL, tag_id TEXT NOT NULL);
+             CREATE TABLE tag (id TEXT PRIMARY KEY NOT NULL, name TEXT NOT NULL, tag_type TEXT NOT NULL);
+             INSERT INTO tag VALUES ('entertainment', 'entertainment', 'category');
+             INSERT INTO app VALUES ('slack', 'com.tinyspeck.slackmacgap', FALSE), ('youtube', 'youtube.com', TRUE);
+             INSERT INTO app_tag VALUES ('at-1', 'youtube', 'entertainment');"
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_resolve_workflow_blocking() -> Result<()> {
+        let ebb_pool = db_manager::create_test_db().await;
+        let codeclimbers_pool = db_manager::create_test_db().await;
+        seed_codeclimbers_apps(&codeclimbers_pool).await?;
+        let service = BlockingService::new_with_pools(ebb_pool.clone(), codeclimbers_pool);
+
+        let workflow = Workflow::new(
+            "Deep Work".to_string(),
+            WorkflowSettings {
+                is_allow_list: Some(true),
+                ..WorkflowSettings::default()
+            },
+        );
+        WorkflowRepo::new(ebb_pool.clone()).create_workflow(&workflow).await?;
+        BlockingPreferenceRepo::new(ebb_pool)
+            .save_workflow_preferences(
+                &workflow.id,
+                &[
+                    BlockingPreference::for_app(&workflow.id, "slack"),
+                    BlockingPreference::for_tag(&workflow.id, "entertainment"),
+                ],
+            )
+            .await?;
+
+        let blocking = service.resolve_workflow_blocking(&workflow.id).await?;
+        assert!(!blocking.is_block_list);
+        assert_eq!(
+            blocking.apps.iter().map(|app| app.app_external_id.as_str()).collect::<Vec<_>>(),
+            vec!["com.tinyspeck.slackmacgap", "youtube.com"]
+        );
+        assert!(blocking.apps[1].is_browser);
+
+        assert!(service.resolve_workflow_blocking("missing").await.is_err());
+
+        Ok(())
+    }
+}
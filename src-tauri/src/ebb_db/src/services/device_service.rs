@@ -4,7 +4,11 @@ use time::OffsetDateTime;
 use crate::db::{
     device_profile_repo::DeviceProfileRepo,
     device_repo::DeviceRepo,
-    models::device_profile::{DevicePreference, DeviceProfile},
+    models::{
+        device_profile::{DevicePreference, DeviceProfile},
+        workflow::Workflow,
+    },
+    workflow_repo::WorkflowRepo,
 };
 
 #[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
@@ -29,6 +33,7 @@ pub type Result<T> = std::result::Result<T, Box<dyn std::error::Error + Send + S
 pub struct DeviceService {
     device_repo: DeviceRepo,
     device_profile_repo: DeviceProfileRepo,
+    workflow_repo: WorkflowRepo,
 }
 
 impl DeviceService {
@@ -36,6 +41,7 @@ impl DeviceService {
         Self {
             device_repo: DeviceRepo::new(pool.clone()),
             device_profile_repo: DeviceProfileRepo::new(pool.clone()),
+            workflow_repo: WorkflowRepo::new(pool.clone()),
         }
     }
 
@@ -110,6 +116,34 @@ impl DeviceService {
         Ok(())
     }
 
+    /// Get the IANA timezone name (e.g. "America/Chicago") used for tide boundaries, if set
+    pub async fn get_timezone(&self) -> Result<Option<String>> {
+        let timezone = self
+            .get_current_device_preference::<String>("timezone")
+            .await?;
+        Ok(timezone)
+    }
+
+    pub async fn set_timezone(&self, timezone: &str) -> Result<()> {
+        self.set_current_device_preference("timezone", timezone)
+            .await?;
+        Ok(())
+    }
+
+    /// Get the day weekly tides start on (0=Sunday, 6=Saturday), defaulting to Monday
+    pub async fn get_week_start_day(&self) -> Result<u8> {
+        let week_start_day = self
+            .get_current_device_preference::<u8>("week_start_day")
+            .await?;
+        Ok(week_start_day.unwrap_or(1))
+    }
+
+    pub async fn set_week_start_day(&self, week_start_day: u8) -> Result<()> {
+        self.set_current_device_preference("week_start_day", week_start_day)
+            .await?;
+        Ok(())
+    }
+
     pub async fn get_smart_focus_settings(&self) -> Result<Option<SmartFocusSettings>> {
         let settings = self
             .get_current_device_preference::<SmartFocusSettings>("smart_focus_settings")
@@ -117,12 +151,29 @@ impl DeviceService {
         Ok(settings)
     }
 
+    /// Save the smart focus settings, rejecting a `workflow_id` that isn't an existing workflow
     pub async fn set_smart_focus_settings(&self, settings: SmartFocusSettings) -> Result<()> {
+        if let Some(workflow_id) = &settings.workflow_id
+            && self.workflow_repo.get_workflow(workflow_id).await?.is_none()
+        {
+            return Err(format!("Workflow not found: {}", workflow_id).into());
+        }
         self.set_current_device_preference("smart_focus_settings", settings)
             .await?;
         Ok(())
     }
 
+    /// Get the workflow smart focus sessions start with
+    /// None if smart focus has no workflow set or the one set has since been deleted
+    pub async fn get_smart_default_workflow(&self) -> Result<Option<Workflow>> {
+        let device = self.device_repo.get_device().await?;
+        let workflow = self
+            .workflow_repo
+            .get_smart_default_workflow(&device.id)
+            .await?;
+        Ok(workflow)
+    }
+
     pub async fn get_device_profile(&self) -> Result<DeviceProfile> {
         let device = self.device_repo.get_device().await?;
         let profile = self
@@ -211,13 +262,15 @@ mod tests {
     #[tokio::test]
     async fn set_get_smart_focus_settings() -> Result<()> {
         let pool = db_manager::create_test_db().await;
-        let service = DeviceService::new_with_pool(pool);
+        let service = DeviceService::new_with_pool(pool.clone());
+        let workflow = Workflow::new("Deep Work".to_string(), Default::default());
+        WorkflowRepo::new(pool).create_workflow(&workflow).await?;
 
         let settings = SmartFocusSettings {
             enabled: true,
             trigger_duration_minutes: 15,
             doomscroll_duration_minutes: 30,
-            workflow_id: Some("test-workflow-id".to_string()),
+            workflow_id: Some(workflow.id.clone()),
         };
 
         if let Err(e) = service.set_smart_focus_settings(settings.clone()).await {
@@ -241,4 +294,65 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn smart_focus_workflow_must_exist() -> Result<()> {
+        let pool = db_manager::create_test_db().await;
+        let service = DeviceService::new_with_pool(pool.clone());
+        let workflow_repo = WorkflowRepo::new(pool);
+
+        let mut settings = SmartFocusSettings {
+            enabled: true,
+            trigger_duration_minutes: 10,
+            doomscroll_duration_minutes: 30,
+            workflow_id: Some("missing-workflow".to_string()),
+        };
+        assert!(service.set_smart_focus_settings(settings.clone()).await.is_err());
+        assert!(service.get_smart_focus_settings().await?.is_none());
+        assert!(service.get_smart_default_workflow().await?.is_none());
+
+        let workflow = Workflow::new("Deep Work".to_string(), Default::default());
+        workflow_repo.create_workflow(&workflow).await?;
+        settings.workflow_id = Some(workflow.id.clone());
+        service.set_smart_focus_settings(settings).await?;
+        assert_eq!(
+            service.get_smart_default_workflow().await?.map(|w| w.id),
+            Some(workflow.id.clone())
+        );
+
+        // A workflow deleted after it was chosen no longer resolves
+        workflow_repo.delete_workflow(&workflow.id).await?;
+        assert!(service.get_smart_default_workflow().await?.is_none());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn set_get_timezone() -> Result<()> {
+        let pool = db_manager::create_test_db().await;
+        let service = DeviceService::new_with_pool(pool);
+
+        assert_eq!(service.get_timezone().await?, None);
+
+        service.set_timezone("America/Chicago").await?;
+        assert_eq!(
+            service.get_timezone().await?,
+            Some("America/Chicago".to_string())
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn set_get_week_start_day() -> Result<()> {
+        let pool = db_manager::create_test_db().await;
+        let service = DeviceService::new_with_pool(pool);
+
+        assert_eq!(service.get_week_start_day().await?, 1);
+
+        service.set_week_start_day(0).await?;
+        assert_eq!(service.get_week_start_day().await?, 0);
+
+        Ok(())
+    }
 }
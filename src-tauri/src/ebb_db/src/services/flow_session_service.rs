@@ -0,0 +1,231 @@
+use sqlx::{Pool, Sqlite};
+use thiserror::Error;
+use time::OffsetDateTime;
+
+use crate::db::{
+    flow_session_repo::FlowSessionRepo,
+    models::flow_session::{FlowSession, FlowSessionType},
+};
+
+#[derive(Error, Debug)]
+pub enum FlowSessionError {
+    #[error("Database error: {0}")]
+    Database(#[from] Box<dyn std::error::Error + Send + Sync>),
+    #[error("Flow session already in progress: {session_id}")]
+    AlreadyInProgress { session_id: String },
+    #[error("No flow session in progress")]
+    NoSessionInProgress,
+    #[error("Flow session not found: {session_id}")]
+    NotFound { session_id: String },
+    #[error("Flow session already ended: {session_id}")]
+    AlreadyEnded { session_id: String },
+    #[error("Invalid end for flow session {session_id}: {end} is before its start {start}")]
+    EndBeforeStart {
+        session_id: String,
+        start: OffsetDateTime,
+        end: OffsetDateTime,
+    },
+}
+
+pub type Result<T> = std::result::Result<T, FlowSessionError>;
+
+/// Starts and ends flow sessions, keeping to a single session in progress at a time
+pub struct FlowSessionService {
+    flow_session_repo: FlowSessionRepo,
+}
+
+impl FlowSessionService {
+    pub fn new_with_pool(pool: Pool<Sqlite>) -> Self {
+        Self {
+            flow_session_repo: FlowSessionRepo::new(pool),
+        }
+    }
+
+    /// Start a session, failing with `AlreadyInProgress` if one is already running
+    pub async fn start_session(
+        &self,
+        objective: &str,
+        session_type: FlowSessionType,
+        workflow_id: Option<String>,
+        duration: Option<i64>,
+        start: OffsetDateTime,
+    ) -> Result<FlowSession> {
+        if let Some(active) = self.flow_session_repo.get_active_session().await? {
+            return Err(FlowSessionError::AlreadyInProgress { session_id: active.id });
+        }
+
+        let session = FlowSession::new(objective.to_string(), session_type, workflow_id, duration, start);
+        if let Err(e) = self.flow_session_repo.create_session(&session).await {
+            // The frontend can start a session between the check and the insert
+            if is_unique_violation(e.as_ref())
+                && let Some(active) = self.flow_session_repo.get_active_session().await?
+            {
+                return Err(FlowSessionError::AlreadyInProgress { session_id: active.id });
+            }
+            return Err(e.into());
+        }
+
+        Ok(session)
+    }
+
+    /// End the session in progress
+    pub async fn end_active_session(&self, end: OffsetDateTime) -> Result<FlowSession> {
+        let session = self
+            .flow_session_repo
+            .get_active_session()
+            .await?
+            .ok_or(FlowSessionError::NoSessionInProgress)?;
+
+        self.end_session(&session.id, end).await
+    }
+
+    /// End a session by id
+    pub async fn end_session(&self, session_id: &str, end: OffsetDateTime) -> Result<FlowSession> {
+        let mut session = self.get_session(session_id).await?.ok_or_else(|| {
+            FlowSessionError::NotFound {
+                session_id: session_id.to_string(),
+            }
+        })?;
+        if !session.is_active() {
+            return Err(FlowSessionError::AlreadyEnded {
+                session_id: session.id,
+            });
+        }
+        if end < session.start {
+            return Err(FlowSessionError::EndBeforeStart {
+                session_id: session.id,
+                start: session.start,
+                end,
+            });
+        }
+
+        if !self.flow_session_repo.end_session(session_id, end).await? {
+            // Ended elsewhere since it was read
+            return Err(FlowSessionError::AlreadyEnded {
+                session_id: session.id,
+            });
+        }
+        session.end = Some(end);
+        Ok(session)
+    }
+
+    pub async fn get_session(&self, session_id: &str) -> Result<Option<FlowSession>> {
+        let session = self.flow_session_repo.get_session(session_id).await?;
+        Ok(session)
+    }
+
+    pub async fn get_active_session(&self) -> Result<Option<FlowSession>> {
+        let session = self.flow_session_repo.get_active_session().await?;
+        Ok(session)
+    }
+
+    /// Get the sessions overlapping a date range, oldest first
+    pub async fn get_sessions_in_range(
+        &self,
+        start_time: OffsetDateTime,
+        end_time: OffsetDateTime,
+    ) -> Result<Vec<FlowSession>> {
+        let sessions = self
+            .flow_session_repo
+            .get_sessions_in_range(start_time, end_time)
+            .await?;
+        Ok(sessions)
+    }
+}
+
+fn is_unique_violation(error: &(dyn std::error::Error + 'static)) -> bool {
+    matches!(
+        error.downcast_ref::<sqlx::Error>(),
+        Some(sqlx::Error::Database(db_error)) if db_error.is_unique_violation()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::db_manager;
+
+    use super::*;
+    use time::macros::datetime;
+
+    #[tokio::test]
+    async fn test_single_session_in_progress() -> Result<()> {
+        let pool = db_manager::create_test_db().await;
+        let service = FlowSessionService::new_with_pool(pool);
+
+        assert!(matches!(
+            service.end_active_session(datetime!(2025-01-06 09:00 UTC)).await,
+            Err(FlowSessionError::NoSessionInProgress)
+        ));
+
+        let session = service
+            .start_session("Write", FlowSessionType::Manual, None, Some(1500), datetime!(2025-01-06 09:00 UTC))
+            .await?;
+        match service
+            .start_session("Read", FlowSessionType::Smart, None, None, datetime!(2025-01-06 09:05 UTC))
+            .await
+        {
+            Err(FlowSessionError::AlreadyInProgress { session_id }) => assert_eq!(session_id, session.id),
+            other => panic!("Expected AlreadyInProgress, got {:?}", other.map(|s| s.id)),
+        }
+
+        assert!(matches!(
+            service.end_active_session(datetime!(2025-01-06 08:59 UTC)).await,
+            Err(FlowSessionError::EndBeforeStart { .. })
+        ));
+
+        let ended = service.end_active_session(datetime!(2025-01-06 09:25 UTC)).await?;
+        assert_eq!(ended.id, session.id);
+        assert_eq!(ended.end, Some(datetime!(2025-01-06 09:25 UTC)));
+        assert!(matches!(
+            service.end_session(&session.id, datetime!(2025-01-06 09:30 UTC)).await,
+            Err(FlowSessionError::AlreadyEnded { .. })
+        ));
+        assert!(matches!(
+            service.end_session("missing", datetime!(2025-01-06 09:30 UTC)).await,
+            Err(FlowSessionError::NotFound { .. })
+        ));
+
+        // A new session can start once the previous one has ended
+        service
+            .start_session("Read", FlowSessionType::Smart, None, None, datetime!(2025-01-06 09:30 UTC))
+            .await?;
+        assert_eq!(
+            service
+                .get_sessions_in_range(datetime!(2025-01-06 00:00 UTC), datetime!(2025-01-07 00:00 UTC))
+                .await?
+                .len(),
+            2
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_unique_index_violation_is_detected() -> Result<()> {
+        let pool = db_manager::create_test_db().await;
+        let repo = FlowSessionRepo::new(pool.clone());
+        let frontend_session = FlowSession::new(
+            "Plan".to_string(),
+            FlowSessionType::Manual,
+            None,
+            None,
+            datetime!(2025-01-06 09:00 UTC),
+        );
+        repo.create_session(&frontend_session).await?;
+
+        // What start_session sees if the frontend starts a session between its check and insert
+        let error = repo
+            .create_session(&FlowSession::new(
+                "Write".to_string(),
+                FlowSessionType::Manual,
+                None,
+                None,
+                datetime!(2025-01-06 09:05 UTC),
+            ))
+            .await
+            .unwrap_err();
+        assert!(is_unique_violation(error.as_ref()));
+
+        Ok(())
+    }
+}
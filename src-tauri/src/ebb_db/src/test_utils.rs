@@ -0,0 +1,18 @@
+//! Helpers shared by tests in this crate and, through the `test-utils` feature, in crates built on it
+
+/// Small deterministic xorshift generator so failing randomized cases can be replayed from their seed
+pub struct TestRng(u64);
+
+impl TestRng {
+    pub fn new(seed: u64) -> Self {
+        Self(seed.wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1)
+    }
+
+    /// Next value in `0..bound`
+    pub fn next_below(&mut self, bound: u64) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0 % bound
+    }
+}
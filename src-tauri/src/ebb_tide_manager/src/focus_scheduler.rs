@@ -0,0 +1,454 @@
+use ebb_db::{
+    db::{
+        flow_session_repo::FlowSessionRepo,
+        focus_schedule_repo::FocusScheduleRepo,
+        models::focus_schedule::{FocusSchedule, RecurrenceType},
+    },
+    db_manager::{self, DbManager},
+    services::device_service::DeviceService,
+};
+use serde::Serialize;
+use std::sync::{Arc, Mutex};
+use thiserror::Error;
+use time::{Duration, OffsetDateTime};
+use time_tz::{OffsetDateTimeExt, Tz};
+use tokio::sync::broadcast;
+
+use crate::tide_scheduler::{TideScheduler, TideSchedulerError, TideSchedulerEvent};
+use crate::time_helpers;
+
+#[derive(Error, Debug)]
+pub enum FocusSchedulerError {
+    #[error("Database error: {0}")]
+    Database(#[from] Box<dyn std::error::Error + Send + Sync>),
+    #[error("Scheduler error: {0}")]
+    Scheduler(#[from] TideSchedulerError),
+}
+
+pub type Result<T> = std::result::Result<T, FocusSchedulerError>;
+
+/// How late a scheduled session can still be started, e.g. when the app launches just after it
+const START_GRACE: Duration = Duration::minutes(2);
+
+/// A scheduled focus session that is due to start
+/// Serializes with `workflow_id` so it can be emitted as the "start-flow" payload as is. The
+/// frontend checks the license before starting it and records `schedule_id` on the session
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ScheduledFlowStart {
+    pub schedule_id: String,
+    pub workflow_id: String,
+    pub label: Option<String>,
+    #[serde(with = "time::serde::rfc3339")]
+    pub scheduled_time: OffsetDateTime,
+}
+
+impl ScheduledFlowStart {
+    fn new(schedule: &FocusSchedule, scheduled_time: OffsetDateTime) -> Self {
+        Self {
+            schedule_id: schedule.id.clone(),
+            workflow_id: schedule.workflow_id.clone(),
+            label: schedule.label.clone(),
+            scheduled_time,
+        }
+    }
+}
+
+/// Get the first occurrence of a schedule strictly after `after`
+/// Recurring schedules run at the local time of day of `scheduled_time` in `tz`, on the dates of
+/// their recurrence rule starting from `scheduled_time`'s date, so they keep their wall clock time
+/// across DST changes. Weekly schedules without any days never run, matching the frontend
+pub fn next_occurrence(schedule: &FocusSchedule, after: OffsetDateTime, tz: &Tz) -> Option<OffsetDateTime> {
+    let first = schedule.scheduled_time;
+    if schedule.recurrence_type() == RecurrenceType::None {
+        return (first > after).then_some(first);
+    }
+    let rule = schedule.recurrence_settings.as_ref()?.to_rule()?;
+
+    let local_first = first.to_timezone(tz);
+    rule.occurrences_from(local_first.date(), after.to_timezone(tz).date())
+        .map(|date| time_helpers::local_datetime(date, local_first.time(), tz))
+        .find(|occurrence| *occurrence > after && *occurrence >= first)
+}
+
+/// Finds the scheduled sessions that came due since the last check
+struct DueScheduleChecker {
+    focus_schedule_repo: FocusScheduleRepo,
+    flow_session_repo: FlowSessionRepo,
+    device_service: DeviceService,
+    last_check: Mutex<Option<OffsetDateTime>>,
+    _db_manager: Arc<DbManager>, // Keep reference to ensure connection pool stays alive
+}
+
+impl DueScheduleChecker {
+    /// Get the session to start for occurrences between the last check and `now`
+    /// Only one session can run, so when several came due the latest one wins
+    /// Nothing starts while a session is already in progress
+    async fn due_start(&self, now: OffsetDateTime) -> Result<Option<ScheduledFlowStart>> {
+        let window_start = {
+            let mut last_check = self.last_check.lock().unwrap();
+            let window_start = last_check.map_or(now - START_GRACE, |last| last.max(now - START_GRACE));
+            *last_check = Some(now);
+            window_start
+        };
+        if window_start >= now {
+            return Ok(None);
+        }
+
+        let schedules = self.focus_schedule_repo.get_active_schedules().await?;
+        if schedules.is_empty() {
+            return Ok(None);
+        }
+
+        let timezone = self.device_service.get_timezone().await?;
+        let tz = time_helpers::resolve_timezone(timezone.as_deref());
+
+        let due = schedules
+            .iter()
+            .filter_map(|schedule| {
+                next_occurrence(schedule, window_start, tz)
+                    .filter(|occurrence| *occurrence <= now)
+                    .map(|occurrence| (schedule, occurrence))
+            })
+            .max_by_key(|(_, occurrence)| *occurrence);
+        let Some((schedule, scheduled_time)) = due else {
+            return Ok(None);
+        };
+
+        if let Some(session) = self.flow_session_repo.get_active_session().await? {
+            log::info!(
+                "Not starting focus schedule {}, session {} is already in progress",
+                schedule.id,
+                session.id
+            );
+            return Ok(None);
+        }
+
+        Ok(Some(ScheduledFlowStart::new(schedule, scheduled_time)))
+    }
+
+    /// Get the first start of an active schedule after `after`, in the device's timezone
+    async fn next_start(&self, schedule_id: &str, after: OffsetDateTime) -> Result<Option<ScheduledFlowStart>> {
+        let Some(schedule) = self.focus_schedule_repo.get_schedule(schedule_id).await? else {
+            return Ok(None);
+        };
+        if !schedule.is_active {
+            return Ok(None);
+        }
+
+        let timezone = self.device_service.get_timezone().await?;
+        let tz = time_helpers::resolve_timezone(timezone.as_deref());
+        Ok(next_occurrence(&schedule, after, tz).map(|scheduled_time| ScheduledFlowStart::new(&schedule, scheduled_time)))
+    }
+}
+
+/// FocusScheduler starts scheduled focus sessions on time, independent of the frontend
+/// It checks active focus schedules on every scheduler tick and emits a `ScheduledFlowStart`
+/// when one came due
+pub struct FocusScheduler {
+    scheduler: Arc<TideScheduler>,
+    checker: Arc<DueScheduleChecker>,
+    events: broadcast::Sender<ScheduledFlowStart>,
+}
+
+impl FocusScheduler {
+    /// Create a new FocusScheduler checking every 30 seconds
+    pub async fn new() -> Result<Self> {
+        let db_manager = db_manager::DbManager::get_shared_ebb()
+            .await
+            .map_err(|e| FocusSchedulerError::Database(Box::new(e)))?;
+
+        Self::new_with_manager(db_manager, 30)
+    }
+
+    pub fn new_with_manager(db_manager: Arc<DbManager>, interval_seconds: u64) -> Result<Self> {
+        let scheduler = Arc::new(TideScheduler::new(interval_seconds)?);
+        let checker = Arc::new(DueScheduleChecker {
+            focus_schedule_repo: FocusScheduleRepo::new(db_manager.pool.clone()),
+            flow_session_repo: FlowSessionRepo::new(db_manager.pool.clone()),
+            device_service: DeviceService::new_with_pool(db_manager.pool.clone()),
+            last_check: Mutex::new(None),
+            _db_manager: db_manager,
+        });
+        let (events, _) = broadcast::channel(100); // Buffer for 100 events
+
+        Ok(Self { scheduler, checker, events })
+    }
+
+    /// Subscribe to scheduled session starts - returns a receiver
+    pub fn subscribe(&self) -> broadcast::Receiver<ScheduledFlowStart> {
+        self.events.subscribe()
+    }
+
+    /// Start checking schedules - the first check runs immediately
+    pub async fn start(&self) -> Result<()> {
+        // Subscribe before starting so the immediate check isn't missed
+        let mut receiver = self.scheduler.subscribe();
+        self.scheduler.start().await?;
+
+        let scheduler = Arc::clone(&self.scheduler);
+        let checker = Arc::clone(&self.checker);
+        let events = self.events.clone();
+
+        tokio::spawn(async move {
+            while scheduler.is_running() {
+                match receiver.recv().await {
+                    Ok(TideSchedulerEvent::Check { .. }) => {
+                        match checker.due_start(OffsetDateTime::now_utc()).await {
+                            Ok(Some(start)) => {
+                                log::info!(
+                                    "Starting scheduled session {} with workflow {}",
+                                    start.schedule_id,
+                                    start.workflow_id
+                                );
+                                let _ = events.send(start); // No subscribers is fine
+                            }
+                            Ok(None) => {}
+                            Err(e) => log::error!("Error checking focus schedules: {}", e),
+                        }
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                    Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                }
+            }
+        });
+
+        Ok(())
+    }
+
+    /// Stop the FocusScheduler - delegates to scheduler
+    pub fn stop(&self) -> Result<()> {
+        self.scheduler.stop()?;
+        Ok(())
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.scheduler.is_running()
+    }
+
+    /// Check for a session that came due since the last check, as of `now`
+    pub async fn check(&self, now: OffsetDateTime) -> Result<Option<ScheduledFlowStart>> {
+        self.checker.due_start(now).await
+    }
+
+    /// Get the next start of a schedule, None if it's inactive or won't run again
+    /// Lets the frontend remind about schedules with rules it can't evaluate itself
+    pub async fn get_next_start(&self, schedule_id: &str) -> Result<Option<ScheduledFlowStart>> {
+        self.checker.next_start(schedule_id, OffsetDateTime::now_utc()).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::create_test_db_manager;
+    use ebb_db::db::{
+        models::{
+            flow_session::{FlowSession, FlowSessionType},
+            focus_schedule::RecurrenceSettings,
+            workflow::{Workflow, WorkflowSettings},
+        },
+        workflow_repo::WorkflowRepo,
+    };
+    use time::macros::datetime;
+    use time_tz::timezones;
+
+    fn new_york() -> &'static Tz {
+        timezones::db::america::NEW_YORK
+    }
+
+    fn schedule(scheduled_time: OffsetDateTime, recurrence: Option<RecurrenceSettings>) -> FocusSchedule {
+        FocusSchedule::new("workflow-1".to_string(), scheduled_time, recurrence, None)
+    }
+
+    async fn create_workflow(db_manager: &DbManager) -> Result<Workflow> {
+        let workflow = Workflow::new("Deep Work".to_string(), WorkflowSettings::default());
+        WorkflowRepo::new(db_manager.pool.clone()).create_workflow(&workflow).await?;
+        Ok(workflow)
+    }
+
+    #[test]
+    fn test_next_occurrence_one_off() {
+        let one_off = schedule(datetime!(2025-03-10 13:00 UTC), None);
+        assert_eq!(
+            next_occurrence(&one_off, datetime!(2025-03-10 12:00 UTC), new_york()),
+            Some(datetime!(2025-03-10 13:00 UTC))
+        );
+        assert_eq!(next_occurrence(&one_off, datetime!(2025-03-10 13:00 UTC), new_york()), None);
+    }
+
+    #[test]
+    fn test_next_occurrence_daily_keeps_local_time_across_dst() {
+        // 09:00 EST, which stays 09:00 local once EDT starts on 2025-03-09
+        let daily = schedule(datetime!(2025-03-07 14:00 UTC), Some(RecurrenceSettings::daily()));
+
+        let mut after = datetime!(2025-03-07 00:00 UTC);
+        let mut occurrences = Vec::new();
+        for _ in 0..4 {
+            after = next_occurrence(&daily, after, new_york()).unwrap();
+            occurrences.push(after);
+        }
+        assert_eq!(
+            occurrences,
+            vec![
+                datetime!(2025-03-07 14:00 UTC),
+                datetime!(2025-03-08 14:00 UTC),
+                datetime!(2025-03-09 13:00 UTC),
+                datetime!(2025-03-10 13:00 UTC),
+            ]
+        );
+
+        // And back to EST after 2025-11-02
+        assert_eq!(
+            next_occurrence(&daily, datetime!(2025-11-01 13:00 UTC), new_york()),
+            Some(datetime!(2025-11-02 14:00 UTC))
+        );
+    }
+
+    #[test]
+    fn test_next_occurrence_daily_in_dst_gap() {
+        // 02:30 doesn't exist on 2025-03-09, so that day's session runs at 03:30 EDT
+        let daily = schedule(datetime!(2025-03-08 07:30 UTC), Some(RecurrenceSettings::daily()));
+        assert_eq!(
+            next_occurrence(&daily, datetime!(2025-03-08 07:30 UTC), new_york()),
+            Some(datetime!(2025-03-09 07:30 UTC))
+        );
+        assert_eq!(
+            next_occurrence(&daily, datetime!(2025-03-09 07:30 UTC), new_york()),
+            Some(datetime!(2025-03-10 06:30 UTC))
+        );
+    }
+
+    #[test]
+    fn test_next_occurrence_weekly_across_dst() {
+        // Mondays and Fridays at 18:00 local, first scheduled on Friday 2025-10-31 (EDT)
+        let weekly = schedule(datetime!(2025-10-31 22:00 UTC), Some(RecurrenceSettings::weekly(vec![1, 5])));
+
+        assert_eq!(
+            next_occurrence(&weekly, datetime!(2025-10-20 00:00 UTC), new_york()),
+            Some(datetime!(2025-10-31 22:00 UTC))
+        );
+        // Monday after the switch to EST
+        assert_eq!(
+            next_occurrence(&weekly, datetime!(2025-10-31 22:00 UTC), new_york()),
+            Some(datetime!(2025-11-03 23:00 UTC))
+        );
+        // Late Monday evening in New York is already Tuesday in UTC
+        assert_eq!(
+            next_occurrence(&weekly, datetime!(2025-11-04 01:00 UTC), new_york()),
+            Some(datetime!(2025-11-07 23:00 UTC))
+        );
+
+        let no_days = schedule(datetime!(2025-10-31 22:00 UTC), Some(RecurrenceSettings::weekly(vec![])));
+        assert_eq!(next_occurrence(&no_days, datetime!(2025-10-20 00:00 UTC), new_york()), None);
+    }
+
+    #[test]
+    fn test_next_occurrence_custom_rule_across_dst() {
+        // First Sunday of the month at 10:00 local, until the end of the year
+        let rule = "RRULE:FREQ=MONTHLY;BYDAY=1SU;UNTIL=20251231".parse().unwrap();
+        let monthly = schedule(datetime!(2025-10-05 14:00 UTC), Some(RecurrenceSettings::custom(rule)));
+
+        assert_eq!(
+            next_occurrence(&monthly, datetime!(2025-10-05 14:00 UTC), new_york()),
+            Some(datetime!(2025-11-02 15:00 UTC))
+        );
+        assert_eq!(
+            next_occurrence(&monthly, datetime!(2025-11-02 15:00 UTC), new_york()),
+            Some(datetime!(2025-12-07 15:00 UTC))
+        );
+        assert_eq!(next_occurrence(&monthly, datetime!(2025-12-07 15:00 UTC), new_york()), None);
+    }
+
+    #[test]
+    fn test_next_occurrence_weekly_same_day_next_week() {
+        // Only Mondays, checked just after this Monday's session
+        let weekly = schedule(datetime!(2025-01-06 14:00 UTC), Some(RecurrenceSettings::weekly(vec![1])));
+        assert_eq!(
+            next_occurrence(&weekly, datetime!(2025-01-06 14:01 UTC), new_york()),
+            Some(datetime!(2025-01-13 14:00 UTC))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_check_emits_each_due_session_once() -> Result<()> {
+        let db_manager = create_test_db_manager().await;
+        DeviceService::new_with_pool(db_manager.pool.clone())
+            .set_timezone("America/New_York")
+            .await?;
+        let workflow = create_workflow(&db_manager).await?;
+        let daily = FocusSchedule::new(
+            workflow.id.clone(),
+            datetime!(2025-03-07 14:00 UTC),
+            Some(RecurrenceSettings::daily()),
+            Some("Morning focus".to_string()),
+        );
+        FocusScheduleRepo::new(db_manager.pool.clone()).create_schedule(&daily).await?;
+
+        let focus_scheduler = FocusScheduler::new_with_manager(db_manager.clone(), 30)?;
+
+        // Not due yet
+        assert_eq!(focus_scheduler.check(datetime!(2025-03-10 12:59 UTC)).await?, None);
+
+        let start = focus_scheduler.check(datetime!(2025-03-10 13:00:20 UTC)).await?.unwrap();
+        assert_eq!(start.schedule_id, daily.id);
+        assert_eq!(start.workflow_id, workflow.id);
+        assert_eq!(start.label.as_deref(), Some("Morning focus"));
+        assert_eq!(start.scheduled_time, datetime!(2025-03-10 13:00 UTC));
+        assert_eq!(focus_scheduler.check(datetime!(2025-03-10 13:00:50 UTC)).await?, None);
+
+        // Missed while the app was closed, and too late to start by the next check
+        assert_eq!(focus_scheduler.check(datetime!(2025-03-11 15:00 UTC)).await?, None);
+
+        // Started shortly after launch, within the grace period
+        let relaunched = FocusScheduler::new_with_manager(db_manager.clone(), 30)?;
+        assert!(relaunched.check(datetime!(2025-03-12 13:01 UTC)).await?.is_some());
+
+        // Nothing starts over a session that's already running
+        FlowSessionRepo::new(db_manager.pool.clone())
+            .create_session(&FlowSession::new(
+                "Deep work".to_string(),
+                FlowSessionType::Manual,
+                None,
+                None,
+                datetime!(2025-03-13 12:30 UTC),
+            ))
+            .await?;
+        assert_eq!(relaunched.check(datetime!(2025-03-13 13:00:30 UTC)).await?, None);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_next_start_uses_device_timezone() -> Result<()> {
+        let db_manager = create_test_db_manager().await;
+        DeviceService::new_with_pool(db_manager.pool.clone())
+            .set_timezone("America/New_York")
+            .await?;
+        let workflow = create_workflow(&db_manager).await?;
+        // First Monday of the month at 09:00 local
+        let rule = "RRULE:FREQ=MONTHLY;BYDAY=1MO".parse().unwrap();
+        let monthly = FocusSchedule::new(
+            workflow.id.clone(),
+            datetime!(2025-01-06 14:00 UTC),
+            Some(RecurrenceSettings::custom(rule)),
+            None,
+        );
+        let repo = FocusScheduleRepo::new(db_manager.pool.clone());
+        repo.create_schedule(&monthly).await?;
+
+        let focus_scheduler = FocusScheduler::new_with_manager(db_manager.clone(), 30)?;
+        let start = focus_scheduler
+            .checker
+            .next_start(&monthly.id, datetime!(2025-02-10 00:00 UTC))
+            .await?
+            .unwrap();
+        assert_eq!(start.workflow_id, workflow.id);
+        assert_eq!(start.scheduled_time, datetime!(2025-03-03 14:00 UTC));
+
+        repo.deactivate_schedule(&monthly.id).await?;
+        assert_eq!(focus_scheduler.checker.next_start(&monthly.id, datetime!(2025-02-10 00:00 UTC)).await?, None);
+        assert_eq!(focus_scheduler.checker.next_start("missing", datetime!(2025-02-10 00:00 UTC)).await?, None);
+
+        Ok(())
+    }
+}
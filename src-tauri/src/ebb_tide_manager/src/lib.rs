@@ -1,14 +1,31 @@
+pub mod focus_scheduler;
+pub mod tide_history;
+pub mod tide_metrics;
 pub mod tide_scheduler;
 pub mod tide_service;
 pub mod tide_progress;
+pub mod tide_streak;
+pub mod tide_suggestion;
 pub mod time_helpers;
 
+use ebb_db::db::models::{
+    recurrence_rule::RecurrenceRule,
+    tide::TideStatus,
+    tide_pause::TidePause,
+    tide_template::TideTemplate,
+    tide_types::{GoalDirection, MetricsType, TideFrequency},
+};
+use serde::Serialize;
 use std::sync::Arc;
 use thiserror::Error;
-use time::OffsetDateTime;
+use time::{Date, OffsetDateTime};
+use tokio::sync::broadcast;
 use tide_scheduler::{TideScheduler, TideSchedulerError, TideSchedulerEvent};
 use tide_service::{TideService, TideServiceError};
 use tide_progress::{TideProgress, TideProgressError};
+use tide_history::{HistoryEntry, HistoryRollup, RollupPeriod};
+use tide_streak::{StreakPeriod, StreakSummary, TideStreak, TideStreakError};
+use tide_suggestion::{GoalSuggestion, SuggestionOptions};
 
 #[derive(Error, Debug)]
 pub enum TideManagerError {
@@ -18,6 +35,8 @@ pub enum TideManagerError {
     Scheduler(#[from] TideSchedulerError),
     #[error("Progress error: {0}")]
     Progress(#[from] TideProgressError),
+    #[error("Streak error: {0}")]
+    Streak(#[from] TideStreakError),
     #[error("Manager already running")]
     AlreadyRunning,
     #[error("Manager not running")]
@@ -28,12 +47,86 @@ pub enum TideManagerError {
 
 pub type Result<T> = std::result::Result<T, TideManagerError>;
 
+/// Events emitted by the TideManager as tides change state
+/// Serialized with a `type` tag so the shell can forward them to the UI as-is
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TideEvent {
+    /// A tide was created for the current period
+    Created {
+        tide_id: String,
+        tide_template_id: String,
+        #[serde(with = "time::serde::rfc3339")]
+        start: OffsetDateTime,
+        #[serde(with = "time::serde::rfc3339::option")]
+        end: Option<OffsetDateTime>,
+        goal_amount: f64,
+    },
+    /// A tide's progress changed since the last check
+    ProgressUpdated {
+        tide_id: String,
+        tide_template_id: String,
+        actual_amount: f64,
+        goal_amount: f64,
+    },
+    /// A tide crossed one of its template's progress milestones (percent of goal) for the first time
+    MilestoneReached {
+        tide_id: String,
+        tide_template_id: String,
+        metrics_type: MetricsType,
+        milestone: u8,
+        actual_amount: f64,
+        goal_amount: f64,
+    },
+    /// A limit tide crossed one of its template's progress milestones (percent of the limit) for the first time
+    LimitApproaching {
+        tide_id: String,
+        tide_template_id: String,
+        metrics_type: MetricsType,
+        milestone: u8,
+        actual_amount: f64,
+        goal_amount: f64,
+    },
+    /// A limit tide went over its limit and was closed out as missed
+    LimitExceeded {
+        tide_id: String,
+        tide_template_id: String,
+        actual_amount: f64,
+        goal_amount: f64,
+    },
+    /// A tide reached its goal before its period ended
+    GoalReached {
+        tide_id: String,
+        tide_template_id: String,
+        actual_amount: f64,
+        goal_amount: f64,
+    },
+    /// A tide's period ended with its goal reached
+    Expired {
+        tide_id: String,
+        tide_template_id: String,
+        status: TideStatus,
+        actual_amount: f64,
+        goal_amount: f64,
+    },
+    /// A tide's period ended short of its goal (status is partial or missed)
+    Missed {
+        tide_id: String,
+        tide_template_id: String,
+        status: TideStatus,
+        actual_amount: f64,
+        goal_amount: f64,
+    },
+}
+
 /// TideManager handles lifecycle management activities for tides
 /// This includes scheduling, automatic generation, and complex business workflows
 pub struct TideManager {
     scheduler: Arc<TideScheduler>,
     service: Arc<TideService>,
     progress: Arc<TideProgress>,
+    streak: TideStreak,
+    events: broadcast::Sender<TideEvent>,
 }
 
 impl TideManager {
@@ -48,8 +141,15 @@ impl TideManager {
         let scheduler = Arc::new(TideScheduler::new(interval_seconds)?);
         let service = Arc::new(TideService::new().await?);
         let progress = Arc::new(TideProgress::new().await?);
+        let streak = TideStreak::new().await?;
+        let (events, _) = broadcast::channel(100); // Buffer for 100 events
+
+        Ok(Self { scheduler, service, progress, streak, events })
+    }
 
-        Ok(Self { scheduler, service, progress })
+    /// Subscribe to tide events - returns a receiver
+    pub fn subscribe(&self) -> broadcast::Receiver<TideEvent> {
+        self.events.subscribe()
     }
 
     /// Start the TideManager - begins listening to scheduler events
@@ -63,12 +163,15 @@ impl TideManager {
         let service = Arc::clone(&self.service);
         let progress = Arc::clone(&self.progress);
         let scheduler = Arc::clone(&self.scheduler);
+        let events = self.events.clone();
 
         tokio::spawn(async move {
             while scheduler.is_running() {
                 match receiver.recv().await {
                     Ok(event) => {
-                        if let Err(e) = Self::handle_scheduler_event(event, &service, &progress).await {
+                        if let Err(e) =
+                            Self::handle_scheduler_event(event, &service, &progress, &events).await
+                        {
                             eprintln!("Error handling scheduler event: {}", e);
                         }
                     }
@@ -99,52 +202,402 @@ impl TideManager {
         self.scheduler.is_running()
     }
 
+    /// Change the day weekly tides start on (0=Sunday, 6=Saturday)
+    /// Open weekly tides are re-ended at the next boundary under the new week start
+    pub async fn set_week_start_day(&self, week_start_day: u8) -> Result<()> {
+        let migrated_tides = self
+            .service
+            .set_week_start_day(week_start_day, OffsetDateTime::now_utc())
+            .await?;
+
+        for tide in &migrated_tides {
+            self.progress.clear_tide_cache(&tide.id).await;
+        }
+
+        Ok(())
+    }
+
+    /// Update a template's goal and days of week through the validated service path
+    /// A new goal is versioned and also applies to the template's running tide
+    pub async fn update_template(
+        &self,
+        template_id: &str,
+        goal_amount: f64,
+        day_of_week: Option<String>,
+    ) -> Result<TideTemplate> {
+        let mut template = self.service.get_template(template_id).await?.ok_or_else(|| {
+            TideServiceError::TemplateNotFound {
+                template_id: template_id.to_string(),
+            }
+        })?;
+
+        template.goal_amount = goal_amount;
+        template.day_of_week = day_of_week;
+        template.updated_at = OffsetDateTime::now_utc();
+
+        Ok(self.service.update_template(&template).await?)
+    }
+
+    /// Create a template through the validated service path, with its first tide starting now
+    /// `tag_ids` select the measured tags for `MetricsType::Tags` templates and must be None otherwise
+    pub async fn create_template(
+        &self,
+        metrics_type: MetricsType,
+        tag_ids: Option<Vec<String>>,
+        tide_frequency: TideFrequency,
+        goal_amount: f64,
+        goal_direction: GoalDirection,
+        day_of_week: Option<String>,
+    ) -> Result<TideTemplate> {
+        let mut template = TideTemplate::new(
+            metrics_type,
+            tide_frequency,
+            goal_amount,
+            OffsetDateTime::now_utc(),
+            day_of_week,
+        );
+        template.tag_ids = tag_ids.map(|tag_ids| tag_ids.join(","));
+        template.goal_direction = goal_direction;
+
+        Ok(self.service.create_template(&template).await?)
+    }
+
+    /// Set the percentages of the goal that trigger milestone notifications for a template
+    pub async fn set_template_milestones(
+        &self,
+        template_id: &str,
+        progress_milestones: Vec<u8>,
+    ) -> Result<TideTemplate> {
+        let mut template = self.service.get_template(template_id).await?.ok_or_else(|| {
+            TideServiceError::TemplateNotFound {
+                template_id: template_id.to_string(),
+            }
+        })?;
+
+        template.progress_milestones = Some(
+            progress_milestones
+                .iter()
+                .map(|milestone| milestone.to_string())
+                .collect::<Vec<_>>()
+                .join(","),
+        );
+        template.updated_at = OffsetDateTime::now_utc();
+
+        Ok(self.service.update_template(&template).await?)
+    }
+
+    /// Set or clear the recurrence rule deciding which periods of a template get a tide
+    /// The rule is versioned like the goal, so history keeps the rule each period was scheduled with
+    pub async fn set_template_recurrence_rule(
+        &self,
+        template_id: &str,
+        recurrence_rule: Option<RecurrenceRule>,
+    ) -> Result<TideTemplate> {
+        let mut template = self.service.get_template(template_id).await?.ok_or_else(|| {
+            TideServiceError::TemplateNotFound {
+                template_id: template_id.to_string(),
+            }
+        })?;
+
+        template.recurrence_rule = recurrence_rule;
+        template.updated_at = OffsetDateTime::now_utc();
+
+        Ok(self.service.update_template(&template).await?)
+    }
+
+    /// Pause tides for a range of local dates (inclusive), e.g. "I'm on vacation next week"
+    /// Tides starting during the pause are excused, so streaks carry over it
+    pub async fn create_pause(
+        &self,
+        start_date: Date,
+        end_date: Date,
+        reason: Option<String>,
+    ) -> Result<TidePause> {
+        let (pause, excused_tides) = self
+            .service
+            .create_pause(start_date, end_date, reason, OffsetDateTime::now_utc())
+            .await?;
+
+        for tide in &excused_tides {
+            self.progress.clear_tide_cache(&tide.id).await;
+        }
+
+        Ok(pause)
+    }
+
+    pub async fn get_pauses(&self) -> Result<Vec<TidePause>> {
+        Ok(self.service.get_pauses().await?)
+    }
+
+    pub async fn delete_pause(&self, pause_id: &str) -> Result<()> {
+        Ok(self.service.delete_pause(pause_id).await?)
+    }
+
+    /// Skip (or un-skip) the period starting on a local date for one template
+    pub async fn set_template_skip_date(
+        &self,
+        template_id: &str,
+        date: Date,
+        skipped: bool,
+    ) -> Result<TideTemplate> {
+        Ok(self.service.set_template_skip_date(template_id, date, skipped).await?)
+    }
+
+    /// Archive a template so it stops creating tides, keeping its history
+    /// Its running tide is ended now and finalized on the next check
+    pub async fn archive_template(&self, template_id: &str) -> Result<()> {
+        let ended_tides = self
+            .service
+            .archive_template(template_id, OffsetDateTime::now_utc())
+            .await?;
+
+        for tide in &ended_tides {
+            self.progress.clear_tide_cache(&tide.id).await;
+        }
+
+        Ok(())
+    }
+
+    /// Suggest a goal for a template from its recent history with the default options
+    /// Returns None when there isn't enough history; the template itself is left unchanged
+    pub async fn suggest_goal(&self, template_id: &str) -> Result<Option<GoalSuggestion>> {
+        Ok(self
+            .service
+            .suggest_goal(
+                template_id,
+                self.progress.metrics(),
+                OffsetDateTime::now_utc(),
+                &SuggestionOptions::default(),
+            )
+            .await?)
+    }
+
+    /// Get a template's history between two local dates (inclusive), one entry per expected period
+    pub async fn get_template_history(
+        &self,
+        template_id: &str,
+        start_date: Date,
+        end_date: Date,
+    ) -> Result<Vec<HistoryEntry>> {
+        Ok(self
+            .service
+            .get_template_history(template_id, start_date, end_date, OffsetDateTime::now_utc())
+            .await?)
+    }
+
+    /// Get a template's history between two local dates (inclusive) rolled up into weeks or months
+    pub async fn get_template_rollups(
+        &self,
+        template_id: &str,
+        start_date: Date,
+        end_date: Date,
+        period: RollupPeriod,
+    ) -> Result<Vec<HistoryRollup>> {
+        Ok(self
+            .service
+            .get_template_rollups(template_id, start_date, end_date, period, OffsetDateTime::now_utc())
+            .await?)
+    }
+
+    /// Get the current and longest streak of finalized tides for a template
+    pub async fn get_streak(&self, template_id: &str) -> Result<StreakSummary> {
+        Ok(self.streak.get_streak(template_id).await?)
+    }
+
+    /// Get the hit/miss status of every counted period of a template, oldest first
+    pub async fn get_streak_history(&self, template_id: &str) -> Result<Vec<StreakPeriod>> {
+        Ok(self.streak.get_streak_history(template_id).await?)
+    }
+
     /// Handle scheduler events (private method)
     async fn handle_scheduler_event(
         event: TideSchedulerEvent,
         service: &TideService,
         progress: &TideProgress,
+        events: &broadcast::Sender<TideEvent>,
     ) -> Result<()> {
         match event {
             TideSchedulerEvent::Check { timestamp: _ } => {
-                // Placeholder for tide lifecycle operations
-                Self::perform_tide_check(service, progress).await?;
+                Self::perform_tide_check(service, progress, events).await?;
             }
         }
         Ok(())
     }
 
     /// Perform tide lifecycle checks - the core tide management logic
-    async fn perform_tide_check(service: &TideService, progress: &TideProgress) -> Result<()> {
+    async fn perform_tide_check(
+        service: &TideService,
+        progress: &TideProgress,
+        events: &broadcast::Sender<TideEvent>,
+    ) -> Result<()> {
         let evaluation_time = OffsetDateTime::now_utc();
 
-        // Get or create active tides for current period
-        let active_tides = service.get_or_create_active_tides_for_period(evaluation_time).await?;
-        println!("Processing {} tides at evaluation_time: {:?}", active_tides.len(), evaluation_time);
-        println!("Active tides: {:?}", active_tides);
+        // Close out tides whose period ended since the last check
+        Self::finalize_expired_tides(service, progress, events, evaluation_time).await?;
+
+        // Fill in tides for periods that ended while the app was not running
+        // These are historical, so no events are sent for them
+        let backfilled_tides = service
+            .backfill_missed_tides(progress.metrics(), evaluation_time)
+            .await?;
+        if !backfilled_tides.is_empty() {
+            println!("Backfilled {} missed tides", backfilled_tides.len());
+        }
+
+        // Get active tides and create any missing ones for the current period
+        let mut active_tides = service.get_active_tides(evaluation_time).await?;
+        let created_tides = service
+            .create_missing_tides_for_period(&active_tides, evaluation_time)
+            .await?;
+        for tide in &created_tides {
+            let _ = events.send(TideEvent::Created {
+                tide_id: tide.id.clone(),
+                tide_template_id: tide.tide_template_id.clone(),
+                start: tide.start,
+                end: tide.end,
+                goal_amount: tide.goal_amount,
+            });
+        }
+        active_tides.extend(created_tides);
 
         for mut tide in active_tides {
             // Update progress
+            let previous_amount = tide.actual_amount;
             let current_progress = progress.update_tide_progress(&mut tide, service, evaluation_time).await?;
 
-            println!("Tide {} progress: {}/{}", tide.id, current_progress, tide.goal_amount);
+            if current_progress != previous_amount {
+                let _ = events.send(TideEvent::ProgressUpdated {
+                    tide_id: tide.id.clone(),
+                    tide_template_id: tide.tide_template_id.clone(),
+                    actual_amount: current_progress,
+                    goal_amount: tide.goal_amount,
+                });
+            }
+
+            // Excused tides still record progress, but nothing is celebrated or failed during a pause
+            if tide.is_excused() {
+                continue;
+            }
+
+            // Milestones are persisted, so each fires once per tide even across restarts
+            // For limit tides they are warnings, which stop once the limit has been crossed
+            let milestones = service
+                .record_reached_milestones(&tide, current_progress, evaluation_time)
+                .await?;
+            for milestone in milestones {
+                let event = match tide.goal_direction {
+                    GoalDirection::Minimum => TideEvent::MilestoneReached {
+                        tide_id: tide.id.clone(),
+                        tide_template_id: tide.tide_template_id.clone(),
+                        metrics_type: tide.metrics_type,
+                        milestone,
+                        actual_amount: current_progress,
+                        goal_amount: tide.goal_amount,
+                    },
+                    GoalDirection::Limit
+                        if !tide.is_finalized() && current_progress <= tide.goal_amount =>
+                    {
+                        TideEvent::LimitApproaching {
+                            tide_id: tide.id.clone(),
+                            tide_template_id: tide.tide_template_id.clone(),
+                            metrics_type: tide.metrics_type,
+                            milestone,
+                            actual_amount: current_progress,
+                            goal_amount: tide.goal_amount,
+                        }
+                    }
+                    GoalDirection::Limit => continue,
+                };
+                let _ = events.send(event);
+            }
+
+            // Limit tides fail as soon as they go over, without waiting for their period to end
+            if progress.has_exceeded_limit(&tide, evaluation_time).await? {
+                service.fail_tide(&tide, current_progress).await?;
+                let _ = events.send(TideEvent::LimitExceeded {
+                    tide_id: tide.id,
+                    tide_template_id: tide.tide_template_id,
+                    actual_amount: current_progress,
+                    goal_amount: tide.goal_amount,
+                });
+                continue;
+            }
 
             // Check if tide should be completed
             if progress.should_complete_tide(&tide, evaluation_time).await? {
-                println!("Completing tide {}", tide.id);
                 service.complete_tide(&tide.id).await?;
+                let _ = events.send(TideEvent::GoalReached {
+                    tide_id: tide.id,
+                    tide_template_id: tide.tide_template_id,
+                    actual_amount: current_progress,
+                    goal_amount: tide.goal_amount,
+                });
             }
         }
 
         Ok(())
     }
+
+    /// Recompute the exact amount for each expired tide up to its end and store a terminal status
+    async fn finalize_expired_tides(
+        service: &TideService,
+        progress: &TideProgress,
+        events: &broadcast::Sender<TideEvent>,
+        evaluation_time: OffsetDateTime,
+    ) -> Result<()> {
+        for tide in service.get_expired_tides(evaluation_time).await? {
+            let Some(end) = tide.end else { continue };
+
+            let final_amount = progress.calculate_tide_progress(&tide, end).await?;
+            let status = service.finalize_tide(&tide, final_amount).await?;
+            progress.clear_tide_cache(&tide.id).await;
+
+            println!("Finalized tide {} as {}: {}/{}", tide.id, status.as_str(), final_amount, tide.goal_amount);
+
+            // No subscribers is fine
+            let tide_id = tide.id;
+            let tide_template_id = tide.tide_template_id;
+            let goal_amount = tide.goal_amount;
+            let event = match status {
+                TideStatus::Completed => TideEvent::Expired {
+                    tide_id,
+                    tide_template_id,
+                    status,
+                    actual_amount: final_amount,
+                    goal_amount,
+                },
+                TideStatus::Partial | TideStatus::Missed => TideEvent::Missed {
+                    tide_id,
+                    tide_template_id,
+                    status,
+                    actual_amount: final_amount,
+                    goal_amount,
+                },
+                // Excused tides are finalized when created, so they never expire here
+                TideStatus::Excused => continue,
+            };
+            let _ = events.send(event);
+        }
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
 pub mod test_helpers {
+    use crate::time_helpers;
+    use ebb_db::db::models::{tide::Tide, tide_template::TideTemplate};
     use ebb_db::db_manager::DbManager;
     use sqlx::sqlite::SqlitePoolOptions;
     use std::sync::Arc;
+    use time::{OffsetDateTime, Weekday};
+    use time_tz::timezones;
+
+    /// Create a tide for the template's period starting at `start`, ending at the next UTC period boundary
+    pub fn create_test_tide(template: &TideTemplate, start: OffsetDateTime) -> Tide {
+        let end = time_helpers::get_period_end(template.tide_frequency, start, timezones::db::UTC, Weekday::Monday);
+        Tide::from_template_with_end(template, start, end)
+    }
 
     /// Create a test database with all necessary tables for TideManager tests
     pub async fn create_test_db_manager() -> Arc<DbManager> {
@@ -223,6 +676,8 @@ pub mod test_helpers {
 mod tests {
     use super::*;
     use super::test_helpers::create_test_db_manager;
+    use ebb_db::db_manager::DbManager;
+    use ebb_db::db::models::tide_types::{MetricsType, TideFrequency};
 
     #[tokio::test]
     async fn test_perform_tide_check_workflow() -> Result<()> {
@@ -240,8 +695,8 @@ mod tests {
         let tide = ebb_db::db::models::tide::Tide::new(
             tide_start,
             Some(tide_end),
-            "creating".to_string(),
-            "daily".to_string(),
+            MetricsType::Creating,
+            TideFrequency::Daily,
             60.0, // Goal is 60 minutes - easy to exceed
             "default-daily-template".to_string(),
         );
@@ -255,8 +710,8 @@ mod tests {
         .bind(tide.start)
         .bind(tide.end)
         .bind(tide.completed_at)
-        .bind(&tide.metrics_type)
-        .bind(&tide.tide_frequency)
+        .bind(tide.metrics_type)
+        .bind(tide.tide_frequency)
         .bind(tide.goal_amount)
         .bind(tide.actual_amount)
         .bind(&tide.tide_template_id)
@@ -318,7 +773,9 @@ mod tests {
         assert_eq!(tide_before.actual_amount, 0.0, "Tide should start with 0 progress");
 
         // Run the tide check workflow
-        TideManager::perform_tide_check(&service, &progress).await?;
+        let (events, mut receiver) = broadcast::channel(100);
+        let mut milestone_receiver = events.subscribe();
+        TideManager::perform_tide_check(&service, &progress, &events).await?;
 
         // Verify the workflow worked:
         // 1. Progress was updated in database
@@ -327,6 +784,343 @@ mod tests {
         assert!(tide_after.completed_at.is_some(), "Tide should be completed after workflow");
         assert!((tide_after.actual_amount - 90.0).abs() < 0.01, "Tide actual_amount should be ~90 minutes");
 
+        // 3. Progress and goal events were sent for the tide, in that order
+        let mut tide_events = Vec::new();
+        while let Ok(event) = receiver.try_recv() {
+            match &event {
+                TideEvent::ProgressUpdated { tide_id, .. } | TideEvent::GoalReached { tide_id, .. }
+                    if *tide_id == tide.id =>
+                {
+                    tide_events.push(event)
+                }
+                _ => {}
+            }
+        }
+        assert_eq!(tide_events.len(), 2);
+        assert!(matches!(tide_events[0], TideEvent::ProgressUpdated { actual_amount, .. } if (actual_amount - 90.0).abs() < 0.01));
+        assert!(matches!(tide_events[1], TideEvent::GoalReached { goal_amount, .. } if goal_amount == 60.0));
+
+        // 4. All default milestones were crossed at once (90 of 60 minutes)
+        let milestones = received_milestones(&mut milestone_receiver, &tide.id);
+        assert_eq!(milestones, vec![50, 90, 100]);
+
+        // A second check with no new activity sends nothing for the completed tide
+        TideManager::perform_tide_check(&service, &progress, &events).await?;
+        while let Ok(event) = receiver.try_recv() {
+            assert!(!matches!(
+                event,
+                TideEvent::ProgressUpdated { ref tide_id, .. } | TideEvent::GoalReached { ref tide_id, .. }
+                    if *tide_id == tide.id
+            ));
+        }
+        assert!(received_milestones(&mut milestone_receiver, &tide.id).is_empty());
+
+        Ok(())
+    }
+
+    fn received_milestones(receiver: &mut broadcast::Receiver<TideEvent>, id: &str) -> Vec<u8> {
+        let mut milestones = Vec::new();
+        while let Ok(event) = receiver.try_recv() {
+            if let TideEvent::MilestoneReached { tide_id, milestone, .. } = event
+                && tide_id == id
+            {
+                milestones.push(milestone);
+            }
+        }
+        milestones
+    }
+
+    async fn insert_consuming_activity(
+        db_manager: &DbManager,
+        id: i64,
+        start_time: OffsetDateTime,
+        minutes: i64,
+    ) {
+        sqlx::query("INSERT OR IGNORE INTO tag (id, name, tag_type) VALUES ('consuming-tag', 'consuming', 'activity')")
+            .execute(&db_manager.pool)
+            .await
+            .unwrap();
+        sqlx::query(
+            "INSERT INTO activity_state (id, state, app_switches, start_time, end_time)
+             VALUES (?1, 'ACTIVE', 0, ?2, ?3)"
+        )
+        .bind(id)
+        .bind(start_time)
+        .bind(start_time + time::Duration::minutes(minutes))
+        .execute(&db_manager.pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO activity_state_tag (activity_state_id, tag_id) VALUES (?1, 'consuming-tag')"
+        )
+        .bind(id.to_string())
+        .execute(&db_manager.pool)
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_perform_tide_check_limit_tide() -> Result<()> {
+        use ebb_db::db::models::tide::Tide;
+
+        let db_manager = create_test_db_manager().await;
+        let service = TideService::new_with_manager(db_manager.clone());
+        let progress = TideProgress::new_with_db_manager(db_manager.clone());
+        let (events, mut receiver) = broadcast::channel(100);
+
+        // No more than 60 minutes of consuming
+        let now = OffsetDateTime::now_utc();
+        let tide_start = now - time::Duration::hours(2);
+        let mut tide = Tide::new(
+            tide_start,
+            Some(now + time::Duration::hours(2)),
+            MetricsType::Consuming,
+            TideFrequency::Daily,
+            60.0,
+            "default-daily-template".to_string(),
+        );
+        tide.goal_direction = GoalDirection::Limit;
+        ebb_db::db::tide_repo::TideRepo::new(db_manager.pool.clone())
+            .create_tide(&tide)
+            .await
+            .map_err(TideServiceError::Database)?;
+
+        // 30 of 60 minutes warns at the 50% milestone, but doesn't complete the tide
+        insert_consuming_activity(&db_manager, 1, tide_start + time::Duration::minutes(10), 30).await;
+        TideManager::perform_tide_check(&service, &progress, &events).await?;
+
+        let mut warnings = Vec::new();
+        while let Ok(event) = receiver.try_recv() {
+            match event {
+                TideEvent::LimitApproaching { tide_id, milestone, .. } if tide_id == tide.id => warnings.push(milestone),
+                TideEvent::GoalReached { tide_id, .. } | TideEvent::LimitExceeded { tide_id, .. } => {
+                    assert_ne!(tide_id, tide.id)
+                }
+                _ => {}
+            }
+        }
+        assert_eq!(warnings, vec![50]);
+        let tide_after = service.get_tide(&tide.id).await?.unwrap();
+        assert!(tide_after.completed_at.is_none());
+        assert!(tide_after.status.is_none());
+
+        // Going over the limit fails the tide right away, without more warnings
+        // Rows are recorded when they end, so this one ends after the previous check
+        let activity_end = OffsetDateTime::now_utc() + time::Duration::milliseconds(20);
+        insert_consuming_activity(&db_manager, 2, activity_end - time::Duration::minutes(40), 40).await;
+        tokio::time::sleep(std::time::Duration::from_millis(40)).await;
+        TideManager::perform_tide_check(&service, &progress, &events).await?;
+
+        let mut exceeded = 0;
+        while let Ok(event) = receiver.try_recv() {
+            match event {
+                TideEvent::LimitExceeded { tide_id, actual_amount, .. } if tide_id == tide.id => {
+                    assert!((actual_amount - 70.0).abs() < 0.01);
+                    exceeded += 1;
+                }
+                TideEvent::LimitApproaching { tide_id, .. } => assert_ne!(tide_id, tide.id),
+                _ => {}
+            }
+        }
+        assert_eq!(exceeded, 1);
+        let tide_after = service.get_tide(&tide.id).await?.unwrap();
+        assert_eq!(tide_after.status, Some(TideStatus::Missed));
+
+        // An already failed tide isn't failed again
+        TideManager::perform_tide_check(&service, &progress, &events).await?;
+        while let Ok(event) = receiver.try_recv() {
+            assert!(!matches!(event, TideEvent::LimitExceeded { .. }));
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_finalize_expired_limit_tide_under_limit() -> Result<()> {
+        use ebb_db::db::models::tide::Tide;
+        use time::macros::datetime;
+
+        let db_manager = create_test_db_manager().await;
+        let service = TideService::new_with_manager(db_manager.clone());
+        let progress = TideProgress::new_with_db_manager(db_manager.clone());
+        let (events, mut receiver) = broadcast::channel(100);
+
+        let tide_start = datetime!(2025-01-06 00:00 UTC);
+        let mut tide = Tide::new(
+            tide_start,
+            Some(tide_start + time::Duration::days(1)),
+            MetricsType::Consuming,
+            TideFrequency::Daily,
+            60.0,
+            "default-daily-template".to_string(),
+        );
+        tide.goal_direction = GoalDirection::Limit;
+        ebb_db::db::tide_repo::TideRepo::new(db_manager.pool.clone())
+            .create_tide(&tide)
+            .await
+            .map_err(TideServiceError::Database)?;
+        insert_consuming_activity(&db_manager, 1, datetime!(2025-01-06 09:00 UTC), 45).await;
+
+        TideManager::finalize_expired_tides(&service, &progress, &events, datetime!(2025-01-07 12:00 UTC)).await?;
+
+        let finalized = service.get_tide(&tide.id).await?.unwrap();
+        assert_eq!(finalized.status, Some(TideStatus::Completed));
+        assert_eq!(finalized.completed_at, tide.end);
+        assert!(matches!(
+            receiver.try_recv(),
+            Ok(TideEvent::Expired { status: TideStatus::Completed, actual_amount, .. }) if (actual_amount - 45.0).abs() < 0.01
+        ));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_perform_tide_check_sends_created_events() -> Result<()> {
+        use ebb_db::db::models::tide_template::TideTemplate;
+
+        let db_manager = create_test_db_manager().await;
+        let service = TideService::new_with_manager(db_manager.clone());
+        let progress = TideProgress::new_with_db_manager(db_manager.clone());
+        let (events, mut receiver) = broadcast::channel(100);
+
+        let template = TideTemplate::new(
+            MetricsType::Creating,
+            TideFrequency::Indefinite,
+            60.0,
+            OffsetDateTime::now_utc() - time::Duration::days(1),
+            None,
+        );
+        service.create_template(&template).await?;
+
+        TideManager::perform_tide_check(&service, &progress, &events).await?;
+
+        let mut created_template_ids = Vec::new();
+        while let Ok(event) = receiver.try_recv() {
+            if let TideEvent::Created { tide_template_id, .. } = event {
+                created_template_ids.push(tide_template_id);
+            }
+        }
+        assert!(created_template_ids.contains(&template.id));
+
+        // The tide already exists on the next check
+        TideManager::perform_tide_check(&service, &progress, &events).await?;
+        while let Ok(event) = receiver.try_recv() {
+            assert!(!matches!(event, TideEvent::Created { .. }));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_tide_event_serializes_with_type_tag() {
+        let event = TideEvent::Missed {
+            tide_id: "tide-1".to_string(),
+            tide_template_id: "template-1".to_string(),
+            status: TideStatus::Partial,
+            actual_amount: 30.0,
+            goal_amount: 60.0,
+        };
+
+        let value = serde_json::to_value(&event).unwrap();
+        assert_eq!(value["type"], "missed");
+        assert_eq!(value["status"], "partial");
+        assert_eq!(value["tide_id"], "tide-1");
+    }
+
+    #[tokio::test]
+    async fn test_finalize_expired_tides() -> Result<()> {
+        use ebb_db::db::models::tide::Tide;
+        use time::macros::datetime;
+
+        let db_manager = create_test_db_manager().await;
+        let service = TideService::new_with_manager(db_manager.clone());
+        let progress = TideProgress::new_with_db_manager(db_manager.clone());
+        let (events, mut receiver) = broadcast::channel(100);
+
+        let insert_tide = |start: OffsetDateTime| {
+            let tide = Tide::new(
+                start,
+                Some(start + time::Duration::days(1)),
+                MetricsType::Creating,
+                TideFrequency::Daily,
+                60.0,
+                "default-daily-template".to_string(),
+            );
+            let pool = db_manager.pool.clone();
+            async move {
+                sqlx::query(
+                    "INSERT INTO tide (id, start, end, metrics_type, tide_frequency, goal_amount, actual_amount, tide_template_id)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)"
+                )
+                .bind(&tide.id)
+                .bind(tide.start)
+                .bind(tide.end)
+                .bind(tide.metrics_type)
+                .bind(tide.tide_frequency)
+                .bind(tide.goal_amount)
+                .bind(tide.actual_amount)
+                .bind(&tide.tide_template_id)
+                .execute(&pool)
+                .await
+                .unwrap();
+                tide
+            }
+        };
+
+        let partial_tide = insert_tide(datetime!(2025-01-06 00:00 UTC)).await;
+        let missed_tide = insert_tide(datetime!(2025-01-07 00:00 UTC)).await;
+        let active_tide = insert_tide(datetime!(2025-01-08 00:00 UTC)).await;
+
+        // 30 minutes of creating on the first day, plus activity after its end that must not count
+        sqlx::query("INSERT INTO tag (id, name, tag_type) VALUES ('creating-tag', 'creating', 'activity')")
+            .execute(&db_manager.pool)
+            .await
+            .unwrap();
+        for (id, start, end) in [
+            (1, datetime!(2025-01-06 23:00 UTC), datetime!(2025-01-06 23:30 UTC)),
+            (2, datetime!(2025-01-08 09:00 UTC), datetime!(2025-01-08 10:00 UTC)),
+        ] {
+            sqlx::query(
+                "INSERT INTO activity_state (id, state, app_switches, start_time, end_time)
+                 VALUES (?1, 'ACTIVE', 0, ?2, ?3)"
+            )
+            .bind(id)
+            .bind(start)
+            .bind(end)
+            .execute(&db_manager.pool)
+            .await
+            .unwrap();
+            sqlx::query("INSERT INTO activity_state_tag (activity_state_id, tag_id) VALUES (?1, 'creating-tag')")
+                .bind(id.to_string())
+                .execute(&db_manager.pool)
+                .await
+                .unwrap();
+        }
+
+        let evaluation_time = datetime!(2025-01-08 12:00 UTC);
+        TideManager::finalize_expired_tides(&service, &progress, &events, evaluation_time).await?;
+
+        let partial = service.get_tide(&partial_tide.id).await?.unwrap();
+        assert_eq!(partial.status, Some(TideStatus::Partial));
+        assert!((partial.actual_amount - 30.0).abs() < 0.01);
+        assert!(partial.completed_at.is_none());
+
+        let missed = service.get_tide(&missed_tide.id).await?.unwrap();
+        assert_eq!(missed.status, Some(TideStatus::Missed));
+
+        let active = service.get_tide(&active_tide.id).await?.unwrap();
+        assert!(active.status.is_none());
+
+        let mut finalized_ids = Vec::new();
+        while let Ok(TideEvent::Missed { tide_id, .. }) = receiver.try_recv() {
+            finalized_ids.push(tide_id);
+        }
+        assert_eq!(finalized_ids, vec![partial_tide.id.clone(), missed_tide.id.clone()]);
+
+        // Running again doesn't touch already finalized tides
+        TideManager::finalize_expired_tides(&service, &progress, &events, evaluation_time).await?;
+        assert!(receiver.try_recv().is_err());
+
         Ok(())
     }
 }
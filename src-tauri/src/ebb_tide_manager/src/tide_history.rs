@@ -0,0 +1,288 @@
+use ebb_db::db::models::{
+    tide::{Tide, TideStatus},
+    tide_template::TideTemplate,
+    tide_template_version::TideTemplateVersion,
+    tide_types::{GoalDirection, TideFrequency},
+};
+use serde::{Deserialize, Serialize};
+use time::{OffsetDateTime, Weekday};
+use time_tz::Tz;
+
+use crate::time_helpers;
+
+/// Outcome of one expected period in a template's history
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HistoryStatus {
+    Completed,
+    Partial,
+    Missed,
+    /// The period fell in a pause
+    Excused,
+    /// The tide's period hasn't been finalized yet
+    InProgress,
+    /// A tide was expected for the period but none was recorded
+    NoTide,
+}
+
+impl HistoryStatus {
+    /// Read the status of a recorded tide; unfinalized tides are in progress
+    pub fn for_tide(tide: &Tide) -> Self {
+        match tide.status {
+            Some(TideStatus::Completed) => HistoryStatus::Completed,
+            Some(TideStatus::Partial) => HistoryStatus::Partial,
+            Some(TideStatus::Missed) => HistoryStatus::Missed,
+            Some(TideStatus::Excused) => HistoryStatus::Excused,
+            None => HistoryStatus::InProgress,
+        }
+    }
+
+    /// Whether the period counts toward hit rates and averages
+    /// Excused and unfinished periods are left out; periods without a tide count as misses
+    pub fn is_scored(&self) -> bool {
+        !matches!(self, HistoryStatus::Excused | HistoryStatus::InProgress)
+    }
+}
+
+/// One expected period of a template, whether or not a tide was recorded for it
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct HistoryEntry {
+    #[serde(with = "time::serde::rfc3339")]
+    pub period_start: OffsetDateTime,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub period_end: Option<OffsetDateTime>,
+    /// The recorded tide, None for periods without one
+    pub tide_id: Option<String>,
+    pub goal_amount: f64,
+    pub goal_direction: GoalDirection,
+    pub actual_amount: f64,
+    pub status: HistoryStatus,
+    /// Actual amount as a percent of the goal, not capped at 100
+    pub percent: f64,
+}
+
+impl HistoryEntry {
+    pub fn from_tide(tide: &Tide) -> Self {
+        Self {
+            period_start: tide.start,
+            period_end: tide.end,
+            tide_id: Some(tide.id.clone()),
+            goal_amount: tide.goal_amount,
+            goal_direction: tide.goal_direction,
+            actual_amount: tide.actual_amount,
+            status: HistoryStatus::for_tide(tide),
+            percent: percent_of_goal(tide.actual_amount, tide.goal_amount),
+        }
+    }
+
+    /// Entry for an expected period that has no tide, against the goal the template had then
+    pub fn without_tide(
+        template: &TideTemplate,
+        period_start: OffsetDateTime,
+        period_end: Option<OffsetDateTime>,
+        status: HistoryStatus,
+    ) -> Self {
+        Self {
+            period_start,
+            period_end,
+            tide_id: None,
+            goal_amount: template.goal_amount,
+            goal_direction: template.goal_direction,
+            actual_amount: 0.0,
+            status,
+            percent: 0.0,
+        }
+    }
+}
+
+/// Calendar period history entries are rolled up into
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RollupPeriod {
+    Week,
+    Month,
+}
+
+impl RollupPeriod {
+    fn tide_frequency(&self) -> TideFrequency {
+        match self {
+            RollupPeriod::Week => TideFrequency::Weekly,
+            RollupPeriod::Month => TideFrequency::Monthly,
+        }
+    }
+}
+
+/// Summary of the history entries starting in one week or month
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct HistoryRollup {
+    #[serde(with = "time::serde::rfc3339")]
+    pub period_start: OffsetDateTime,
+    #[serde(with = "time::serde::rfc3339")]
+    pub period_end: OffsetDateTime,
+    /// Expected periods, including excused and unfinished ones
+    pub periods: usize,
+    /// Periods counted toward the hit rate and average
+    pub scored: usize,
+    pub completed: usize,
+    pub excused: usize,
+    /// Share of scored periods that were completed, None when nothing was scored
+    pub hit_rate: Option<f64>,
+    /// Average actual amount over scored periods, None when nothing was scored
+    pub average_actual: Option<f64>,
+}
+
+/// The template as it was at `time`, with the goal, days and tags of the version in effect then
+/// Times before the first recorded version use the first version, since a template's `first_tide`
+/// can be earlier than its creation. `versions` are expected in version order.
+pub fn template_as_of(
+    template: &TideTemplate,
+    versions: &[TideTemplateVersion],
+    time: OffsetDateTime,
+) -> TideTemplate {
+    let mut template_as_of = template.clone();
+    let version = versions
+        .iter()
+        .rev()
+        .find(|version| version.effective_from <= time)
+        .or(versions.first());
+    if let Some(version) = version {
+        template_as_of.version = version.version;
+        template_as_of.goal_amount = version.goal_amount;
+        template_as_of.goal_direction = version.goal_direction;
+        template_as_of.day_of_week = version.day_of_week.clone();
+        template_as_of.recurrence_rule = version.recurrence_rule.clone();
+        template_as_of.tag_ids = version.tag_ids.clone();
+    }
+    template_as_of
+}
+
+/// Actual amount as a percent of the goal; a zero goal reports 0
+pub fn percent_of_goal(actual_amount: f64, goal_amount: f64) -> f64 {
+    if goal_amount > 0.0 {
+        actual_amount / goal_amount * 100.0
+    } else {
+        0.0
+    }
+}
+
+/// Group history entries by the local week or month their period starts in
+/// Entries are expected in chronological order; only weeks and months with entries are returned
+pub fn rollup(
+    entries: &[HistoryEntry],
+    period: RollupPeriod,
+    tz: &Tz,
+    week_start: Weekday,
+) -> Vec<HistoryRollup> {
+    let frequency = period.tide_frequency();
+    let mut rollups: Vec<(HistoryRollup, f64)> = Vec::new();
+
+    for entry in entries {
+        let rollup_start = time_helpers::get_period_start(frequency, entry.period_start, tz, week_start);
+        if rollups.last().map(|(rollup, _)| rollup.period_start) != Some(rollup_start) {
+            let rollup_end = time_helpers::get_period_end(frequency, rollup_start, tz, week_start)
+                .expect("weeks and months always end");
+            rollups.push((
+                HistoryRollup {
+                    period_start: rollup_start,
+                    period_end: rollup_end,
+                    periods: 0,
+                    scored: 0,
+                    completed: 0,
+                    excused: 0,
+                    hit_rate: None,
+                    average_actual: None,
+                },
+                0.0,
+            ));
+        }
+
+        let (rollup, scored_actual) = rollups.last_mut().expect("rollup was just pushed");
+        rollup.periods += 1;
+        match entry.status {
+            HistoryStatus::Completed => rollup.completed += 1,
+            HistoryStatus::Excused => rollup.excused += 1,
+            _ => {}
+        }
+        if entry.status.is_scored() {
+            rollup.scored += 1;
+            *scored_actual += entry.actual_amount;
+        }
+    }
+
+    rollups
+        .into_iter()
+        .map(|(mut rollup, scored_actual)| {
+            if rollup.scored > 0 {
+                rollup.hit_rate = Some(rollup.completed as f64 / rollup.scored as f64);
+                rollup.average_actual = Some(scored_actual / rollup.scored as f64);
+            }
+            rollup
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::datetime;
+    use time_tz::timezones;
+
+    fn entry(period_start: OffsetDateTime, actual_amount: f64, status: HistoryStatus) -> HistoryEntry {
+        HistoryEntry {
+            period_start,
+            period_end: Some(period_start + time::Duration::days(1)),
+            tide_id: None,
+            goal_amount: 60.0,
+            goal_direction: GoalDirection::Minimum,
+            actual_amount,
+            status,
+            percent: percent_of_goal(actual_amount, 60.0),
+        }
+    }
+
+    #[test]
+    fn test_rollup_by_week_skips_excused_and_unfinished_periods() {
+        let tz = timezones::db::UTC;
+        // Thursday 2025-01-02 through Tuesday 2025-01-07, weeks starting Sunday
+        let entries = vec![
+            entry(datetime!(2025-01-02 00:00 UTC), 60.0, HistoryStatus::Completed),
+            entry(datetime!(2025-01-03 00:00 UTC), 30.0, HistoryStatus::Partial),
+            entry(datetime!(2025-01-04 00:00 UTC), 0.0, HistoryStatus::Excused),
+            entry(datetime!(2025-01-05 00:00 UTC), 90.0, HistoryStatus::Completed),
+            entry(datetime!(2025-01-06 00:00 UTC), 0.0, HistoryStatus::NoTide),
+            entry(datetime!(2025-01-07 00:00 UTC), 20.0, HistoryStatus::InProgress),
+        ];
+
+        let rollups = rollup(&entries, RollupPeriod::Week, tz, Weekday::Sunday);
+        assert_eq!(rollups.len(), 2);
+
+        assert_eq!(rollups[0].period_start, datetime!(2024-12-29 00:00 UTC));
+        assert_eq!(rollups[0].period_end, datetime!(2025-01-05 00:00 UTC));
+        assert_eq!(rollups[0].periods, 3);
+        assert_eq!(rollups[0].scored, 2);
+        assert_eq!(rollups[0].excused, 1);
+        assert_eq!(rollups[0].hit_rate, Some(0.5));
+        assert_eq!(rollups[0].average_actual, Some(45.0));
+
+        assert_eq!(rollups[1].periods, 3);
+        assert_eq!(rollups[1].scored, 2);
+        assert_eq!(rollups[1].hit_rate, Some(0.5));
+        assert_eq!(rollups[1].average_actual, Some(45.0));
+
+        let months = rollup(&entries, RollupPeriod::Month, tz, Weekday::Sunday);
+        assert_eq!(months.len(), 1);
+        assert_eq!(months[0].period_start, datetime!(2025-01-01 00:00 UTC));
+        assert_eq!(months[0].scored, 4);
+        assert_eq!(months[0].hit_rate, Some(0.5));
+    }
+
+    #[test]
+    fn test_rollup_without_scored_periods_has_no_rates() {
+        let entries = vec![entry(datetime!(2025-01-06 00:00 UTC), 10.0, HistoryStatus::InProgress)];
+
+        let rollups = rollup(&entries, RollupPeriod::Week, timezones::db::UTC, Weekday::Monday);
+        assert_eq!(rollups[0].periods, 1);
+        assert_eq!(rollups[0].hit_rate, None);
+        assert_eq!(rollups[0].average_actual, None);
+    }
+}
@@ -0,0 +1,398 @@
+use ebb_db::{
+    db::{
+        activity_state_repo::ActivityStateRepo,
+        flow_session_repo::FlowSessionRepo,
+        models::{tide::Tide, tide_types::MetricsType},
+    },
+    db_manager::DbManager,
+};
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use time::OffsetDateTime;
+
+pub type MetricResult<T> = std::result::Result<T, Box<dyn std::error::Error + Send + Sync>>;
+
+/// Boxed future returned by `MetricProvider` methods so providers can be stored as trait objects
+pub type MetricFuture<'a, T> = Pin<Box<dyn Future<Output = MetricResult<T>> + Send + 'a>>;
+
+/// Measures the amount a tide has made toward its goal over a time range
+/// Each `MetricsType` is backed by one provider registered in `TideMetrics`
+pub trait MetricProvider: Send + Sync {
+    /// Measure the tide's metric between two times
+    fn measure<'a>(
+        &'a self,
+        tide: &'a Tide,
+        start_time: OffsetDateTime,
+        end_time: OffsetDateTime,
+    ) -> MetricFuture<'a, f64>;
+
+    /// Whether measurements over adjacent ranges can be summed
+    /// Progress for additive metrics is cached and extended incrementally; anything else
+    /// (like a rate) is recalculated over the whole tide on every check
+    fn is_additive(&self) -> bool {
+        true
+    }
+
+    /// Whether data ending between `start_time` and `end_time` was recorded or changed after `since`
+    /// Used to discard progress checkpoints that no longer match the data. Providers that can't
+    /// detect edits report no changes.
+    ///
+    /// Detection is limited to what the rows record: deleted rows leave no trace, and edits only
+    /// show up where the source stamps them. Code that deletes or rewrites measured data behind a
+    /// provider's back should clear the affected tides' cached progress itself.
+    fn has_changed_since<'a>(
+        &'a self,
+        _start_time: OffsetDateTime,
+        _end_time: OffsetDateTime,
+        _since: OffsetDateTime,
+    ) -> MetricFuture<'a, bool> {
+        Box::pin(async { Ok(false) })
+    }
+}
+
+/// Minutes of activity tagged with the tide's tag
+/// Default metrics match their tag by name; `MetricsType::Tags` tides match the tide's tags by id
+pub struct TaggedMinutes {
+    activity_state_repo: ActivityStateRepo,
+}
+
+impl TaggedMinutes {
+    pub fn new(activity_state_repo: ActivityStateRepo) -> Self {
+        Self { activity_state_repo }
+    }
+}
+
+impl MetricProvider for TaggedMinutes {
+    fn measure<'a>(
+        &'a self,
+        tide: &'a Tide,
+        start_time: OffsetDateTime,
+        end_time: OffsetDateTime,
+    ) -> MetricFuture<'a, f64> {
+        Box::pin(async move {
+            match tide.metrics_type {
+                MetricsType::Tags => {
+                    self.activity_state_repo
+                        .calculate_tag_set_duration_in_range(&tide.get_tag_ids(), start_time, end_time)
+                        .await
+                }
+                metrics_type => {
+                    self.activity_state_repo
+                        .calculate_tagged_duration_in_range(metrics_type.as_str(), start_time, end_time)
+                        .await
+                }
+            }
+        })
+    }
+
+    fn has_changed_since<'a>(
+        &'a self,
+        start_time: OffsetDateTime,
+        end_time: OffsetDateTime,
+        since: OffsetDateTime,
+    ) -> MetricFuture<'a, bool> {
+        Box::pin(async move {
+            self.activity_state_repo
+                .has_activity_changed_since(start_time, end_time, since)
+                .await
+        })
+    }
+}
+
+/// App switches per hour of active time
+/// A rate over the whole tide, so it isn't additive; no active time counts as zero switches
+pub struct AppSwitchRate {
+    activity_state_repo: ActivityStateRepo,
+}
+
+impl AppSwitchRate {
+    pub fn new(activity_state_repo: ActivityStateRepo) -> Self {
+        Self { activity_state_repo }
+    }
+}
+
+impl MetricProvider for AppSwitchRate {
+    fn measure<'a>(
+        &'a self,
+        _tide: &'a Tide,
+        start_time: OffsetDateTime,
+        end_time: OffsetDateTime,
+    ) -> MetricFuture<'a, f64> {
+        Box::pin(async move {
+            let (app_switches, active_minutes) = self
+                .activity_state_repo
+                .get_app_switch_totals_in_range(start_time, end_time)
+                .await?;
+            if active_minutes <= 0.0 {
+                return Ok(0.0);
+            }
+            Ok(app_switches as f64 / (active_minutes / 60.0))
+        })
+    }
+
+    fn is_additive(&self) -> bool {
+        false
+    }
+}
+
+/// Minutes spent in flow sessions that finished during the range
+pub struct FlowSessionMinutes {
+    flow_session_repo: FlowSessionRepo,
+}
+
+impl FlowSessionMinutes {
+    pub fn new(flow_session_repo: FlowSessionRepo) -> Self {
+        Self { flow_session_repo }
+    }
+}
+
+impl MetricProvider for FlowSessionMinutes {
+    fn measure<'a>(
+        &'a self,
+        _tide: &'a Tide,
+        start_time: OffsetDateTime,
+        end_time: OffsetDateTime,
+    ) -> MetricFuture<'a, f64> {
+        Box::pin(async move {
+            let (_, minutes) = self
+                .flow_session_repo
+                .get_session_totals_in_range(start_time, end_time)
+                .await?;
+            Ok(minutes)
+        })
+    }
+
+    fn has_changed_since<'a>(
+        &'a self,
+        start_time: OffsetDateTime,
+        end_time: OffsetDateTime,
+        since: OffsetDateTime,
+    ) -> MetricFuture<'a, bool> {
+        Box::pin(async move {
+            self.flow_session_repo
+                .has_session_changed_since(start_time, end_time, since)
+                .await
+        })
+    }
+}
+
+/// Number of flow sessions that finished during the range
+pub struct FlowSessionCount {
+    flow_session_repo: FlowSessionRepo,
+}
+
+impl FlowSessionCount {
+    pub fn new(flow_session_repo: FlowSessionRepo) -> Self {
+        Self { flow_session_repo }
+    }
+}
+
+impl MetricProvider for FlowSessionCount {
+    fn measure<'a>(
+        &'a self,
+        _tide: &'a Tide,
+        start_time: OffsetDateTime,
+        end_time: OffsetDateTime,
+    ) -> MetricFuture<'a, f64> {
+        Box::pin(async move {
+            let (sessions, _) = self
+                .flow_session_repo
+                .get_session_totals_in_range(start_time, end_time)
+                .await?;
+            Ok(sessions as f64)
+        })
+    }
+
+    fn has_changed_since<'a>(
+        &'a self,
+        start_time: OffsetDateTime,
+        end_time: OffsetDateTime,
+        since: OffsetDateTime,
+    ) -> MetricFuture<'a, bool> {
+        Box::pin(async move {
+            self.flow_session_repo
+                .has_session_changed_since(start_time, end_time, since)
+                .await
+        })
+    }
+}
+
+/// Registry of the metric providers tides are measured with, keyed by `MetricsType`
+#[derive(Clone)]
+pub struct TideMetrics {
+    providers: HashMap<MetricsType, Arc<dyn MetricProvider>>,
+}
+
+impl TideMetrics {
+    /// Create a registry with the default providers
+    /// Activity metrics read from the CodeClimbers database, flow sessions from the ebb database
+    pub fn new(codeclimbers_db: &DbManager, ebb_db: &DbManager) -> Self {
+        let tagged_minutes: Arc<dyn MetricProvider> =
+            Arc::new(TaggedMinutes::new(ActivityStateRepo::new(codeclimbers_db.pool.clone())));
+
+        let mut metrics = Self {
+            providers: HashMap::new(),
+        };
+        for metrics_type in [
+            MetricsType::Creating,
+            MetricsType::Consuming,
+            MetricsType::Neutral,
+            MetricsType::Idle,
+            MetricsType::Tags,
+        ] {
+            metrics.register(metrics_type, tagged_minutes.clone());
+        }
+        metrics.register(
+            MetricsType::AppSwitchRate,
+            Arc::new(AppSwitchRate::new(ActivityStateRepo::new(codeclimbers_db.pool.clone()))),
+        );
+        metrics.register(
+            MetricsType::FlowSessionMinutes,
+            Arc::new(FlowSessionMinutes::new(FlowSessionRepo::new(ebb_db.pool.clone()))),
+        );
+        metrics.register(
+            MetricsType::FlowSessionCount,
+            Arc::new(FlowSessionCount::new(FlowSessionRepo::new(ebb_db.pool.clone()))),
+        );
+
+        metrics
+    }
+
+    /// Register the provider for a metrics type, replacing any existing one
+    pub fn register(&mut self, metrics_type: MetricsType, provider: Arc<dyn MetricProvider>) {
+        self.providers.insert(metrics_type, provider);
+    }
+
+    fn provider(&self, metrics_type: MetricsType) -> MetricResult<&Arc<dyn MetricProvider>> {
+        self.providers
+            .get(&metrics_type)
+            .ok_or_else(|| format!("No metric provider registered for {}", metrics_type).into())
+    }
+
+    /// Measure a tide's metric between two times with the provider for its metrics type
+    pub async fn measure(
+        &self,
+        tide: &Tide,
+        start_time: OffsetDateTime,
+        end_time: OffsetDateTime,
+    ) -> MetricResult<f64> {
+        self.provider(tide.metrics_type)?
+            .measure(tide, start_time, end_time)
+            .await
+    }
+
+    /// Whether progress for a metrics type can be extended incrementally
+    /// Unregistered types report false so they are never cached
+    pub fn is_additive(&self, metrics_type: MetricsType) -> bool {
+        self.providers
+            .get(&metrics_type)
+            .is_some_and(|provider| provider.is_additive())
+    }
+
+    /// Whether the data behind a metrics type changed between two times after `since`
+    pub async fn has_changed_since(
+        &self,
+        metrics_type: MetricsType,
+        start_time: OffsetDateTime,
+        end_time: OffsetDateTime,
+        since: OffsetDateTime,
+    ) -> MetricResult<bool> {
+        self.provider(metrics_type)?
+            .has_changed_since(start_time, end_time, since)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::{create_test_db_manager, create_test_tide};
+    use ebb_db::db::models::{tide_template::TideTemplate, tide_types::TideFrequency};
+    use time::macros::datetime;
+
+    fn tide_for(metrics_type: MetricsType) -> Tide {
+        let tide_start = datetime!(2025-01-06 00:00 UTC);
+        create_test_tide(
+            &TideTemplate::new(metrics_type, TideFrequency::Daily, 10.0, tide_start, None),
+            tide_start,
+        )
+    }
+
+    async fn insert_active_state(
+        db_manager: &DbManager,
+        app_switches: i64,
+        start_time: OffsetDateTime,
+        end_time: OffsetDateTime,
+    ) {
+        sqlx::query(
+            "INSERT INTO activity_state (state, app_switches, start_time, end_time)
+             VALUES ('ACTIVE', ?1, ?2, ?3)"
+        )
+        .bind(app_switches)
+        .bind(start_time)
+        .bind(end_time)
+        .execute(&db_manager.pool)
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_app_switch_rate_is_per_active_hour() -> MetricResult<()> {
+        let db_manager = create_test_db_manager().await;
+        let metrics = TideMetrics::new(&db_manager, &db_manager);
+        let tide = tide_for(MetricsType::AppSwitchRate);
+
+        assert_eq!(
+            metrics
+                .measure(&tide, datetime!(2025-01-06 00:00 UTC), datetime!(2025-01-07 00:00 UTC))
+                .await?,
+            0.0
+        );
+
+        insert_active_state(&db_manager, 12, datetime!(2025-01-06 09:00 UTC), datetime!(2025-01-06 09:30 UTC)).await;
+        insert_active_state(&db_manager, 3, datetime!(2025-01-06 09:30 UTC), datetime!(2025-01-06 10:00 UTC)).await;
+
+        let rate = metrics
+            .measure(&tide, datetime!(2025-01-06 00:00 UTC), datetime!(2025-01-07 00:00 UTC))
+            .await?;
+        assert!((rate - 15.0).abs() < 1e-9, "Expected 15 switches per hour, got {}", rate);
+        assert!(!metrics.is_additive(MetricsType::AppSwitchRate));
+        assert!(metrics.is_additive(MetricsType::Creating));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_registered_provider_replaces_default() -> MetricResult<()> {
+        struct Fixed(f64);
+
+        impl MetricProvider for Fixed {
+            fn measure<'a>(
+                &'a self,
+                _tide: &'a Tide,
+                _start_time: OffsetDateTime,
+                _end_time: OffsetDateTime,
+            ) -> MetricFuture<'a, f64> {
+                let amount = self.0;
+                Box::pin(async move { Ok(amount) })
+            }
+        }
+
+        let db_manager = create_test_db_manager().await;
+        let mut metrics = TideMetrics::new(&db_manager, &db_manager);
+        metrics.register(MetricsType::FlowSessionCount, Arc::new(Fixed(3.0)));
+
+        let amount = metrics
+            .measure(
+                &tide_for(MetricsType::FlowSessionCount),
+                datetime!(2025-01-06 00:00 UTC),
+                datetime!(2025-01-07 00:00 UTC),
+            )
+            .await?;
+        assert_eq!(amount, 3.0);
+
+        Ok(())
+    }
+}
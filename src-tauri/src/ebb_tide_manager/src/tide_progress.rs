@@ -1,6 +1,10 @@
+use crate::tide_metrics::TideMetrics;
 use crate::tide_service::{TideService, TideServiceError};
 use ebb_db::{
-    db::{activity_state_repo::ActivityStateRepo, models::tide::Tide},
+    db::{
+        models::{tide::Tide, tide_progress_checkpoint::TideProgressCheckpoint, tide_types::GoalDirection},
+        tide_progress_checkpoint_repo::TideProgressCheckpointRepo,
+    },
     db_manager::{self, DbManager},
 };
 use log;
@@ -38,9 +42,11 @@ impl CachedProgress {
     }
 }
 
-/// TideProgress handles querying tide progress data from the CodeClimbers database
+/// TideProgress handles measuring tide progress with the metric provider for each tide
+/// Incremental progress is checkpointed in the ebb database so it survives restarts
 pub struct TideProgress {
-    activity_state_repo: ActivityStateRepo,
+    metrics: TideMetrics,
+    checkpoint_repo: TideProgressCheckpointRepo,
     progress_cache: Arc<Mutex<HashMap<String, CachedProgress>>>,
 }
 
@@ -50,22 +56,36 @@ impl TideProgress {
         let codeclimbers_db = db_manager::DbManager::get_shared_codeclimbers()
             .await
             .map_err(|e| TideProgressError::Database(Box::new(e)))?;
+        let ebb_db = db_manager::DbManager::get_shared_ebb()
+            .await
+            .map_err(|e| TideProgressError::Database(Box::new(e)))?;
 
-        Ok(Self {
-            activity_state_repo: ActivityStateRepo::new(codeclimbers_db.pool.clone()),
-            progress_cache: Arc::new(Mutex::new(HashMap::new())),
-        })
+        Ok(Self::new_with_db_managers(codeclimbers_db, ebb_db))
     }
 
     /// Create a new TideProgress instance with a specific database manager
+    /// Activity and checkpoints are both read from this database
     pub fn new_with_db_manager(codeclimbers_db: Arc<DbManager>) -> Self {
+        Self::new_with_db_managers(codeclimbers_db.clone(), codeclimbers_db)
+    }
+
+    /// Create a new TideProgress instance reading activity from one database and checkpoints
+    /// and flow sessions from another
+    pub fn new_with_db_managers(codeclimbers_db: Arc<DbManager>, ebb_db: Arc<DbManager>) -> Self {
         Self {
-            activity_state_repo: ActivityStateRepo::new(codeclimbers_db.pool.clone()),
+            metrics: TideMetrics::new(&codeclimbers_db, &ebb_db),
+            checkpoint_repo: TideProgressCheckpointRepo::new(ebb_db.pool.clone()),
             progress_cache: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 
+    /// Get the metric providers used to score tides
+    pub fn metrics(&self) -> &TideMetrics {
+        &self.metrics
+    }
+
     /// Get the current progress for a tide, using cache with incremental calculation
+    /// Metrics that aren't additive (like rates) are always recalculated over the whole tide
     pub async fn get_tide_progress_cached(
         &self,
         tide: &Tide,
@@ -74,52 +94,48 @@ impl TideProgress {
     ) -> Result<f64> {
         let tide_id = &tide.id;
 
-        // Check cache first and clone the data if found
-        let cached_data = {
-            let cache = self.progress_cache.lock().await;
-            cache.get(tide_id).cloned()
+        // Check cache first, falling back to the persisted checkpoint after a restart
+        let cached_data = if skip_cache || !self.metrics.is_additive(tide.metrics_type) {
+            None
+        } else {
+            let in_memory = {
+                let cache = self.progress_cache.lock().await;
+                cache.get(tide_id).cloned()
+            };
+            match in_memory {
+                Some(cached) => Some(cached),
+                None => self.load_checkpoint(tide, evaluation_time).await?,
+            }
         };
 
         if let Some(cached) = cached_data {
-            if !skip_cache {
-                // Cache hit - calculate incremental progress
-                let time_diff = evaluation_time - cached.last_evaluation_time;
-                log::debug!(
-                    "Cache check: tide_id={}, cached_time={:?}, eval_time={:?}, diff={:?} ({} seconds)",
-                    tide_id,
-                    cached.last_evaluation_time,
-                    evaluation_time,
-                    time_diff,
-                    time_diff.whole_seconds()
-                );
-                log::debug!(
-                    "Cache hit for tide: incremental progress for tide with start and end times: {:?}, {:?}",
-                    cached.last_evaluation_time,
-                    evaluation_time
-                );
-                let delta_minutes = self
-                    .activity_state_repo
-                    .calculate_tagged_duration_in_range(
-                        &tide.metrics_type,
-                        cached.last_evaluation_time,
-                        evaluation_time,
-                    )
-                    .await
-                    .map_err(|e| TideProgressError::Database(e))?;
-
-                let new_total = cached.amount + delta_minutes;
-
-                // Update cache with new values
-                {
-                    let mut cache = self.progress_cache.lock().await;
-                    cache.insert(
-                        tide_id.clone(),
-                        CachedProgress::new(new_total, evaluation_time),
-                    );
-                }
+            // Cache hit - calculate incremental progress
+            let time_diff = evaluation_time - cached.last_evaluation_time;
+            log::debug!(
+                "Cache check: tide_id={}, cached_time={:?}, eval_time={:?}, diff={:?} ({} seconds)",
+                tide_id,
+                cached.last_evaluation_time,
+                evaluation_time,
+                time_diff,
+                time_diff.whole_seconds()
+            );
+            log::debug!(
+                "Cache hit for tide: incremental progress for tide with start and end times: {:?}, {:?}",
+                cached.last_evaluation_time,
+                evaluation_time
+            );
+            let delta_minutes = self
+                .metrics
+                .measure(tide, cached.last_evaluation_time, evaluation_time)
+                .await
+                .map_err(|e| TideProgressError::Database(e))?;
 
-                return Ok(new_total);
-            }
+            let new_total = cached.amount + delta_minutes;
+
+            // Update cache with new values
+            self.store_progress(tide_id, new_total, evaluation_time).await?;
+
+            return Ok(new_total);
         }
 
         // Cache miss - calculate full range from tide start
@@ -131,15 +147,73 @@ impl TideProgress {
         let total_minutes = self.calculate_tide_progress(tide, evaluation_time).await?;
 
         // Store in cache
+        self.store_progress(tide_id, total_minutes, evaluation_time).await?;
+
+        Ok(total_minutes)
+    }
+
+    /// Load a persisted checkpoint into the in-memory cache if it is still valid for the tide
+    /// Checkpoints outside the tide's period, or with activity inserted or re-tagged before them
+    /// since they were written, are dropped so progress is recalculated from the tide start
+    async fn load_checkpoint(
+        &self,
+        tide: &Tide,
+        evaluation_time: OffsetDateTime,
+    ) -> Result<Option<CachedProgress>> {
+        let Some(checkpoint) = self.checkpoint_repo.get_checkpoint(&tide.id).await? else {
+            return Ok(None);
+        };
+
+        let in_period = checkpoint.last_evaluation_time >= tide.start
+            && checkpoint.last_evaluation_time <= evaluation_time
+            && tide.end.is_none_or(|end| checkpoint.last_evaluation_time <= end);
+        let valid = in_period
+            && checkpoint.amount.is_finite()
+            && checkpoint.amount >= 0.0
+            && !self
+                .metrics
+                .has_changed_since(
+                    tide.metrics_type,
+                    tide.start,
+                    checkpoint.last_evaluation_time,
+                    checkpoint.updated_at,
+                )
+                .await?;
+
+        if !valid {
+            log::debug!("Discarding stale progress checkpoint for tide {}", tide.id);
+            self.checkpoint_repo.delete_checkpoint(&tide.id).await?;
+            return Ok(None);
+        }
+
+        let cached = CachedProgress::new(checkpoint.amount, checkpoint.last_evaluation_time);
+        let mut cache = self.progress_cache.lock().await;
+        cache.insert(tide.id.clone(), cached.clone());
+
+        Ok(Some(cached))
+    }
+
+    /// Record progress in the in-memory cache and persist it as the tide's checkpoint
+    async fn store_progress(
+        &self,
+        tide_id: &str,
+        amount: f64,
+        evaluation_time: OffsetDateTime,
+    ) -> Result<()> {
         {
             let mut cache = self.progress_cache.lock().await;
-            cache.insert(
-                tide_id.clone(),
-                CachedProgress::new(total_minutes, evaluation_time),
-            );
+            cache.insert(tide_id.to_string(), CachedProgress::new(amount, evaluation_time));
         }
 
-        Ok(total_minutes)
+        self.checkpoint_repo
+            .upsert_checkpoint(&TideProgressCheckpoint::new(
+                tide_id.to_string(),
+                amount,
+                evaluation_time,
+            ))
+            .await?;
+
+        Ok(())
     }
 
     /// Calculate the current progress for a tide by querying the database
@@ -149,36 +223,47 @@ impl TideProgress {
         tide: &Tide,
         evaluation_time: OffsetDateTime,
     ) -> Result<f64> {
-        // Use the repository to calculate the tagged duration from tide start to evaluation time
+        // Measure the tide's metric from tide start to evaluation time
         let total_minutes = self
-            .activity_state_repo
-            .calculate_tagged_duration_in_range(&tide.metrics_type, tide.start, evaluation_time)
+            .metrics
+            .measure(tide, tide.start, evaluation_time)
             .await
             .map_err(|e| TideProgressError::Database(e))?;
 
         Ok(total_minutes)
     }
 
-    /// Clear the progress cache for a specific tide
+    /// Clear the progress cache and persisted checkpoint for a specific tide
     pub async fn clear_tide_cache(&self, tide_id: &str) {
-        let mut cache = self.progress_cache.lock().await;
-        cache.remove(tide_id);
+        {
+            let mut cache = self.progress_cache.lock().await;
+            cache.remove(tide_id);
+        }
+        if let Err(e) = self.checkpoint_repo.delete_checkpoint(tide_id).await {
+            log::error!("Failed to delete progress checkpoint for tide {}: {}", tide_id, e);
+        }
     }
 
-    /// Clear the entire progress cache
+    /// Clear the entire progress cache and all persisted checkpoints
     pub async fn clear_all_cache(&self) {
-        let mut cache = self.progress_cache.lock().await;
-        cache.clear();
+        {
+            let mut cache = self.progress_cache.lock().await;
+            cache.clear();
+        }
+        if let Err(e) = self.checkpoint_repo.delete_all_checkpoints().await {
+            log::error!("Failed to delete progress checkpoints: {}", e);
+        }
     }
 
     /// Check if a tide should be marked as complete
+    /// Limit tides only succeed when their period ends, so they are never completed early
     pub async fn should_complete_tide(
         &self,
         tide: &Tide,
         evaluation_time: OffsetDateTime,
     ) -> Result<bool> {
         // if tide is completed, return true
-        if tide.is_completed() {
+        if tide.is_completed() || tide.goal_direction == GoalDirection::Limit {
             return Ok(false);
         }
 
@@ -195,6 +280,29 @@ impl TideProgress {
         }
     }
 
+    /// Check if a limit tide has gone over its limit and should be failed
+    /// Like completion, a cached amount over the limit is confirmed with a full recalculation
+    pub async fn has_exceeded_limit(
+        &self,
+        tide: &Tide,
+        evaluation_time: OffsetDateTime,
+    ) -> Result<bool> {
+        if tide.goal_direction != GoalDirection::Limit || tide.is_finalized() {
+            return Ok(false);
+        }
+
+        let current_progress = self
+            .get_tide_progress_cached(tide, evaluation_time, false)
+            .await?;
+
+        if current_progress > tide.goal_amount {
+            let validated_progress = self.calculate_tide_progress(tide, evaluation_time).await?;
+            Ok(validated_progress > tide.goal_amount)
+        } else {
+            Ok(false)
+        }
+    }
+
     /// Update a tide's progress in the database and cache
     pub async fn update_tide_progress(
         &self,
@@ -221,10 +329,13 @@ impl TideProgress {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use ebb_db::db::activity_state_repo::ActivityStateRepo;
     use ebb_db::db::models::tide_template::TideTemplate;
+    use ebb_db::db::models::tide_types::{MetricsType, TideFrequency};
+    use ebb_db::test_utils::TestRng;
     use time::macros::datetime;
 
-    use crate::test_helpers::create_test_db_manager;
+    use crate::test_helpers::{create_test_db_manager, create_test_tide};
 
     #[tokio::test]
     async fn test_cached_progress_creation() -> Result<()> {
@@ -334,10 +445,10 @@ mod tests {
 
         // Create a test tide
         let tide_start = datetime!(2025-01-06 08:00 UTC);
-        let tide = Tide::from_template(
+        let tide = create_test_tide(
             &TideTemplate::new(
-                "creating".to_string(),
-                "daily".to_string(),
+                MetricsType::Creating,
+                TideFrequency::Daily,
                 120.0, // 2 hours goal
                 tide_start,
                 None,
@@ -434,10 +545,10 @@ mod tests {
         .map_err(|e| TideProgressError::Database(Box::new(e)))?;
 
         let tide_start = datetime!(2025-01-06 08:00 UTC);
-        let tide = Tide::from_template(
+        let tide = create_test_tide(
             &TideTemplate::new(
-                "creating".to_string(),
-                "daily".to_string(),
+                MetricsType::Creating,
+                TideFrequency::Daily,
                 120.0,
                 tide_start,
                 None,
@@ -445,22 +556,32 @@ mod tests {
             tide_start,
         );
 
-        // Test evaluation at 09:30 - should capture the full 60 minutes
+        // Test evaluation at 09:30 - neither state has ended yet, so nothing is credited
         let partial_evaluation = datetime!(2025-01-06 09:30 UTC);
         let progress = tide_progress
             .calculate_tide_progress(&tide, partial_evaluation)
             .await?;
         assert!(
-            (progress - 60.0).abs() < 0.01,
-            "Expected ~60 minutes, got {}",
+            progress.abs() < 0.01,
+            "Expected 0 minutes, got {}",
             progress
         );
 
-        // Test evaluation at 10:30 - should capture the full 120 minutes
+        // Test evaluation at 10:30 - the first state ended, so its full 60 minutes count
         let mid_evaluation = datetime!(2025-01-06 10:30 UTC);
         let progress = tide_progress
             .calculate_tide_progress(&tide, mid_evaluation)
             .await?;
+        assert!(
+            (progress - 60.0).abs() < 0.01,
+            "Expected ~60 minutes, got {}",
+            progress
+        );
+
+        // Test evaluation at 11:00 - both states ended, capturing the full 120 minutes
+        let progress = tide_progress
+            .calculate_tide_progress(&tide, end_time)
+            .await?;
         assert!(
             (progress - 120.0).abs() < 0.01,
             "Expected ~120 minutes, got {}",
@@ -520,10 +641,10 @@ mod tests {
         .map_err(|e| TideProgressError::Database(Box::new(e)))?;
 
         let tide_start = datetime!(2025-01-06 08:00 UTC);
-        let tide = Tide::from_template(
+        let tide = create_test_tide(
             &TideTemplate::new(
-                "creating".to_string(),
-                "daily".to_string(),
+                MetricsType::Creating,
+                TideFrequency::Daily,
                 120.0,
                 tide_start,
                 None,
@@ -593,10 +714,10 @@ mod tests {
         .map_err(|e| TideProgressError::Database(Box::new(e)))?;
 
         let tide_start = datetime!(2025-01-06 08:00 UTC);
-        let tide = Tide::from_template(
+        let tide = create_test_tide(
             &TideTemplate::new(
-                "creating".to_string(),
-                "daily".to_string(),
+                MetricsType::Creating,
+                TideFrequency::Daily,
                 120.0,
                 tide_start,
                 None,
@@ -697,10 +818,10 @@ mod tests {
         .await
         .map_err(|e| TideProgressError::Database(Box::new(e)))?;
 
-        let tide = Tide::from_template(
+        let tide = create_test_tide(
             &TideTemplate::new(
-                "creating".to_string(),
-                "daily".to_string(),
+                MetricsType::Creating,
+                TideFrequency::Daily,
                 120.0,
                 datetime!(2025-01-06 08:00 UTC),
                 None,
@@ -796,10 +917,10 @@ mod tests {
         .map_err(|e| TideProgressError::Database(Box::new(e)))?;
 
         // Create two different tides
-        let tide1 = Tide::from_template(
+        let tide1 = create_test_tide(
             &TideTemplate::new(
-                "creating".to_string(),
-                "daily".to_string(),
+                MetricsType::Creating,
+                TideFrequency::Daily,
                 120.0,
                 datetime!(2025-01-06 08:00 UTC),
                 None,
@@ -807,10 +928,10 @@ mod tests {
             datetime!(2025-01-06 08:00 UTC),
         );
 
-        let tide2 = Tide::from_template(
+        let tide2 = create_test_tide(
             &TideTemplate::new(
-                "creating".to_string(),
-                "weekly".to_string(),
+                MetricsType::Creating,
+                TideFrequency::Weekly,
                 300.0,
                 datetime!(2025-01-06 08:00 UTC),
                 None,
@@ -872,10 +993,10 @@ mod tests {
         let db_manager = create_test_db_manager().await;
         let tide_progress = TideProgress::new_with_db_manager(db_manager);
 
-        let tide = Tide::from_template(
+        let tide = create_test_tide(
             &TideTemplate::new(
-                "creating".to_string(),
-                "daily".to_string(),
+                MetricsType::Creating,
+                TideFrequency::Daily,
                 120.0, // Goal is 120 minutes
                 datetime!(2025-01-06 08:00 UTC),
                 None,
@@ -950,10 +1071,10 @@ mod tests {
         .await
         .map_err(|e| TideProgressError::Database(Box::new(e)))?;
 
-        let tide = Tide::from_template(
+        let tide = create_test_tide(
             &TideTemplate::new(
-                "creating".to_string(),
-                "daily".to_string(),
+                MetricsType::Creating,
+                TideFrequency::Daily,
                 120.0, // Goal is 120 minutes
                 datetime!(2025-01-06 08:00 UTC),
                 None,
@@ -1028,10 +1149,10 @@ mod tests {
         .await
         .map_err(|e| TideProgressError::Database(Box::new(e)))?;
 
-        let tide = Tide::from_template(
+        let tide = create_test_tide(
             &TideTemplate::new(
-                "creating".to_string(),
-                "daily".to_string(),
+                MetricsType::Creating,
+                TideFrequency::Daily,
                 120.0, // Goal is 120 minutes
                 datetime!(2025-01-06 08:00 UTC),
                 None,
@@ -1113,8 +1234,8 @@ mod tests {
         let mut tide = Tide::new(
             datetime!(2025-01-06 08:00 UTC),
             Some(datetime!(2025-01-07 08:00 UTC)), // daily tide ends next day
-            "creating".to_string(),
-            "daily".to_string(),
+            MetricsType::Creating,
+            TideFrequency::Daily,
             120.0, // Goal is 120 minutes (different from seeded template's 180)
             "default-daily-template".to_string(), // Use the seeded template ID
         );
@@ -1128,8 +1249,8 @@ mod tests {
         .bind(tide.start)
         .bind(tide.end)
         .bind(tide.completed_at)
-        .bind(&tide.metrics_type)
-        .bind(&tide.tide_frequency)
+        .bind(tide.metrics_type)
+        .bind(tide.tide_frequency)
         .bind(tide.goal_amount)
         .bind(tide.actual_amount)
         .bind(&tide.tide_template_id)
@@ -1181,8 +1302,8 @@ mod tests {
         let mut tide = Tide::new(
             datetime!(2025-01-06 08:00 UTC),
             Some(datetime!(2025-01-07 08:00 UTC)),
-            "creating".to_string(),
-            "daily".to_string(),
+            MetricsType::Creating,
+            TideFrequency::Daily,
             120.0,
             "default-daily-template".to_string(),
         );
@@ -1196,8 +1317,8 @@ mod tests {
         .bind(tide.start)
         .bind(tide.end)
         .bind(tide.completed_at)
-        .bind(&tide.metrics_type)
-        .bind(&tide.tide_frequency)
+        .bind(tide.metrics_type)
+        .bind(tide.tide_frequency)
         .bind(tide.goal_amount)
         .bind(tide.actual_amount)
         .bind(&tide.tide_template_id)
@@ -1244,10 +1365,10 @@ mod tests {
         let tide_progress = TideProgress::new_with_db_manager(db_manager);
 
         // Create a tide that is already completed
-        let mut tide = Tide::from_template(
+        let mut tide = create_test_tide(
             &TideTemplate::new(
-                "creating".to_string(),
-                "daily".to_string(),
+                MetricsType::Creating,
+                TideFrequency::Daily,
                 120.0, // Goal is 120 minutes
                 datetime!(2025-01-06 08:00 UTC),
                 None,
@@ -1316,10 +1437,10 @@ mod tests {
         .map_err(|e| TideProgressError::Database(Box::new(e)))?;
 
         // Create a tide that is NOT completed but has sufficient progress
-        let tide = Tide::from_template(
+        let tide = create_test_tide(
             &TideTemplate::new(
-                "creating".to_string(),
-                "daily".to_string(),
+                MetricsType::Creating,
+                TideFrequency::Daily,
                 120.0, // Goal is 120 minutes
                 datetime!(2025-01-06 08:00 UTC),
                 None,
@@ -1388,10 +1509,10 @@ mod tests {
         .map_err(|e| TideProgressError::Database(Box::new(e)))?;
 
         // Create a tide that is NOT completed with insufficient progress
-        let tide = Tide::from_template(
+        let tide = create_test_tide(
             &TideTemplate::new(
-                "creating".to_string(),
-                "daily".to_string(),
+                MetricsType::Creating,
+                TideFrequency::Daily,
                 120.0, // Goal is 120 minutes
                 datetime!(2025-01-06 08:00 UTC),
                 None,
@@ -1460,10 +1581,10 @@ mod tests {
         .map_err(|e| TideProgressError::Database(Box::new(e)))?;
 
         // Create a tide that is ALREADY completed with high progress
-        let mut tide = Tide::from_template(
+        let mut tide = create_test_tide(
             &TideTemplate::new(
-                "creating".to_string(),
-                "daily".to_string(),
+                MetricsType::Creating,
+                TideFrequency::Daily,
                 120.0, // Goal is 120 minutes
                 datetime!(2025-01-06 08:00 UTC),
                 None,
@@ -1534,10 +1655,10 @@ mod tests {
         .await
         .map_err(|e| TideProgressError::Database(Box::new(e)))?;
 
-        let tide = Tide::from_template(
+        let tide = create_test_tide(
             &TideTemplate::new(
-                "creating".to_string(),
-                "daily".to_string(),
+                MetricsType::Creating,
+                TideFrequency::Daily,
                 120.0,
                 datetime!(2025-01-06 08:00 UTC),
                 None,
@@ -1618,10 +1739,10 @@ mod tests {
         .await
         .map_err(|e| TideProgressError::Database(Box::new(e)))?;
 
-        let tide = Tide::from_template(
+        let tide = create_test_tide(
             &TideTemplate::new(
-                "creating".to_string(),
-                "daily".to_string(),
+                MetricsType::Creating,
+                TideFrequency::Daily,
                 120.0,
                 datetime!(2025-01-06 08:00 UTC),
                 None,
@@ -1712,10 +1833,10 @@ mod tests {
         .await
         .map_err(|e| TideProgressError::Database(Box::new(e)))?;
 
-        let tide = Tide::from_template(
+        let tide = create_test_tide(
             &TideTemplate::new(
-                "creating".to_string(),
-                "daily".to_string(),
+                MetricsType::Creating,
+                TideFrequency::Daily,
                 120.0,
                 datetime!(2025-01-06 08:00 UTC),
                 None,
@@ -1835,10 +1956,10 @@ mod tests {
         .await
         .map_err(|e| TideProgressError::Database(Box::new(e)))?;
 
-        let tide = Tide::from_template(
+        let tide = create_test_tide(
             &TideTemplate::new(
-                "creating".to_string(),
-                "daily".to_string(),
+                MetricsType::Creating,
+                TideFrequency::Daily,
                 120.0,
                 datetime!(2025-01-06 08:00 UTC),
                 None,
@@ -1881,7 +2002,6 @@ mod tests {
     #[tokio::test]
     async fn test_incremental_cache_overlap_scenario() -> Result<()> {
         let db_manager = create_test_db_manager().await;
-        let tide_progress = TideProgress::new_with_db_manager(db_manager.clone());
 
         // Create test tag - "creating" with "default" type
         let tag_id = "test-creating-tag";
@@ -1952,8 +2072,7 @@ mod tests {
 
         // Test the incremental calculation that matches the logs
         // This should find the overlap between the activity and the query range
-        let progress = tide_progress
-            .activity_state_repo
+        let progress = ActivityStateRepo::new(db_manager.pool.clone())
             .calculate_tagged_duration_in_range("creating", cached_time, eval_time)
             .await
             .map_err(|e| TideProgressError::Database(e))?;
@@ -1975,4 +2094,309 @@ mod tests {
 
         Ok(())
     }
+
+    async fn insert_creating_activity(
+        db_manager: &DbManager,
+        id: i64,
+        start_time: OffsetDateTime,
+        end_time: OffsetDateTime,
+        recorded_at: OffsetDateTime,
+    ) {
+        sqlx::query("INSERT OR IGNORE INTO tag (id, name, tag_type) VALUES ('creating-tag', 'creating', 'activity')")
+            .execute(&db_manager.pool)
+            .await
+            .unwrap();
+        sqlx::query(
+            "INSERT INTO activity_state (id, state, app_switches, start_time, end_time, created_at)
+             VALUES (?1, 'ACTIVE', 0, ?2, ?3, ?4)"
+        )
+        .bind(id)
+        .bind(start_time)
+        .bind(end_time)
+        .bind(recorded_at)
+        .execute(&db_manager.pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO activity_state_tag (activity_state_id, tag_id, created_at, updated_at)
+             VALUES (?1, 'creating-tag', ?2, ?2)"
+        )
+        .bind(id.to_string())
+        .bind(recorded_at)
+        .execute(&db_manager.pool)
+        .await
+        .unwrap();
+    }
+
+    fn indefinite_tide(tide_start: OffsetDateTime) -> Tide {
+        create_test_tide(
+            &TideTemplate::new(
+                MetricsType::Creating,
+                TideFrequency::Indefinite,
+                600.0,
+                tide_start,
+                None,
+            ),
+            tide_start,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_checkpoint_resumes_incremental_progress_after_restart() -> Result<()> {
+        let db_manager = create_test_db_manager().await;
+        let tide = indefinite_tide(datetime!(2025-01-01 00:00 UTC));
+        let long_ago = datetime!(2025-01-01 00:00 UTC);
+
+        insert_creating_activity(&db_manager, 1, datetime!(2025-01-06 09:00 UTC), datetime!(2025-01-06 10:00 UTC), long_ago).await;
+
+        let tide_progress = TideProgress::new_with_db_manager(db_manager.clone());
+        let progress = tide_progress
+            .get_tide_progress_cached(&tide, datetime!(2025-01-06 12:00 UTC), false)
+            .await?;
+        assert!((progress - 60.0).abs() < 0.01);
+
+        // A row that was already recorded when the checkpoint was written but falls before it
+        // would be picked up by a full rescan, so seeing it ignored shows the checkpoint was used
+        insert_creating_activity(&db_manager, 2, datetime!(2025-01-05 09:00 UTC), datetime!(2025-01-05 09:30 UTC), long_ago).await;
+        insert_creating_activity(&db_manager, 3, datetime!(2025-01-06 13:00 UTC), datetime!(2025-01-06 13:15 UTC), long_ago).await;
+
+        let restarted = TideProgress::new_with_db_manager(db_manager.clone());
+        let progress = restarted
+            .get_tide_progress_cached(&tide, datetime!(2025-01-06 14:00 UTC), false)
+            .await?;
+        assert!((progress - 75.0).abs() < 0.01, "Expected ~75 minutes, got {}", progress);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_checkpoint_invalidated_by_activity_edits_before_it() -> Result<()> {
+        let db_manager = create_test_db_manager().await;
+        let tide = indefinite_tide(datetime!(2025-01-01 00:00 UTC));
+
+        insert_creating_activity(&db_manager, 1, datetime!(2025-01-06 09:00 UTC), datetime!(2025-01-06 10:00 UTC), datetime!(2025-01-01 00:00 UTC)).await;
+
+        let tide_progress = TideProgress::new_with_db_manager(db_manager.clone());
+        tide_progress
+            .get_tide_progress_cached(&tide, datetime!(2025-01-06 12:00 UTC), false)
+            .await?;
+
+        // Activity before the checkpoint recorded after it was written
+        let edited_at = OffsetDateTime::now_utc() + time::Duration::seconds(1);
+        insert_creating_activity(&db_manager, 2, datetime!(2025-01-05 09:00 UTC), datetime!(2025-01-05 09:30 UTC), edited_at).await;
+
+        let restarted = TideProgress::new_with_db_manager(db_manager.clone());
+        let progress = restarted
+            .get_tide_progress_cached(&tide, datetime!(2025-01-06 14:00 UTC), false)
+            .await?;
+        assert!((progress - 90.0).abs() < 0.01, "Expected full recalculation of ~90 minutes, got {}", progress);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_clear_tide_cache_removes_checkpoint() -> Result<()> {
+        let db_manager = create_test_db_manager().await;
+        let tide = indefinite_tide(datetime!(2025-01-01 00:00 UTC));
+        let checkpoint_repo = TideProgressCheckpointRepo::new(db_manager.pool.clone());
+
+        let tide_progress = TideProgress::new_with_db_manager(db_manager.clone());
+        tide_progress
+            .get_tide_progress_cached(&tide, datetime!(2025-01-06 12:00 UTC), false)
+            .await?;
+        assert!(checkpoint_repo.get_checkpoint(&tide.id).await?.is_some());
+
+        tide_progress.clear_tide_cache(&tide.id).await;
+        assert!(checkpoint_repo.get_checkpoint(&tide.id).await?.is_none());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_cached_progress_matches_full_calculation_as_activity_is_recorded() -> Result<()> {
+        let tide_start = datetime!(2025-01-06 00:00 UTC);
+
+        for seed in 1..=24u64 {
+            let db_manager = create_test_db_manager().await;
+            let tide_progress = TideProgress::new_with_db_manager(db_manager.clone());
+            let tide = indefinite_tide(tide_start);
+            let mut rng = TestRng::new(seed);
+
+            // Back-to-back states like the monitor writes, each recorded once it ends
+            let mut states = Vec::new();
+            let mut cursor = tide_start + time::Duration::minutes(rng.next_below(60) as i64);
+            for _ in 0..1 + rng.next_below(20) {
+                let end = cursor + time::Duration::seconds(30 + rng.next_below(45 * 60) as i64);
+                states.push((cursor, end));
+                cursor = end + time::Duration::seconds(rng.next_below(20 * 60) as i64);
+            }
+
+            let mut recorded = 0;
+            let mut evaluation_time = tide_start;
+            while evaluation_time < cursor {
+                evaluation_time += time::Duration::seconds(15 + rng.next_below(30 * 60) as i64);
+                while recorded < states.len() && states[recorded].1 <= evaluation_time {
+                    let (start, end) = states[recorded];
+                    insert_creating_activity(&db_manager, recorded as i64 + 1, start, end, end).await;
+                    recorded += 1;
+                }
+                tide_progress
+                    .get_tide_progress_cached(&tide, evaluation_time, false)
+                    .await?;
+            }
+
+            let cached = tide_progress
+                .get_tide_progress_cached(&tide, evaluation_time, false)
+                .await?;
+            let full = tide_progress.calculate_tide_progress(&tide, evaluation_time).await?;
+            let expected: f64 = states
+                .iter()
+                .map(|(start, end)| (*end - *start).as_seconds_f64() / 60.0)
+                .sum();
+
+            assert!((cached - full).abs() < 1e-6, "seed {}: cached {} != full {}", seed, cached, full);
+            assert!((full - expected).abs() < 1e-6, "seed {}: full {} != recorded {}", seed, full, expected);
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_tag_set_tide_counts_category_tags_by_id() -> Result<()> {
+        let db_manager = create_test_db_manager().await;
+        let tide_progress = TideProgress::new_with_db_manager(db_manager.clone());
+        let long_ago = datetime!(2025-01-01 00:00 UTC);
+
+        // Two hours of creating time, the second hour also categorized as coding
+        insert_creating_activity(&db_manager, 1, datetime!(2025-01-06 09:00 UTC), datetime!(2025-01-06 10:00 UTC), long_ago).await;
+        insert_creating_activity(&db_manager, 2, datetime!(2025-01-06 10:00 UTC), datetime!(2025-01-06 11:00 UTC), long_ago).await;
+        sqlx::query("INSERT INTO tag (id, name, tag_type) VALUES ('coding-tag', 'coding', 'category')")
+            .execute(&db_manager.pool)
+            .await
+            .unwrap();
+        sqlx::query(
+            "INSERT INTO activity_state_tag (activity_state_id, tag_id, created_at, updated_at)
+             VALUES ('2', 'coding-tag', ?1, ?1)"
+        )
+        .bind(long_ago)
+        .execute(&db_manager.pool)
+        .await
+        .unwrap();
+
+        let tide_start = datetime!(2025-01-06 00:00 UTC);
+        let tide = create_test_tide(
+            &TideTemplate::new_for_tags(vec!["coding-tag".to_string()], TideFrequency::Weekly, 600.0, tide_start, None),
+            tide_start,
+        );
+
+        let progress = tide_progress
+            .get_tide_progress_cached(&tide, datetime!(2025-01-06 10:30 UTC), false)
+            .await?;
+        assert_eq!(progress, 0.0);
+        let progress = tide_progress
+            .get_tide_progress_cached(&tide, datetime!(2025-01-06 12:00 UTC), false)
+            .await?;
+        assert!((progress - 60.0).abs() < 0.01, "Expected ~60 minutes, got {}", progress);
+
+        // Renaming the tag doesn't change what the tide measures
+        sqlx::query("UPDATE tag SET name = 'programming' WHERE id = 'coding-tag'")
+            .execute(&db_manager.pool)
+            .await
+            .unwrap();
+        let progress = tide_progress
+            .calculate_tide_progress(&tide, datetime!(2025-01-06 12:00 UTC))
+            .await?;
+        assert!((progress - 60.0).abs() < 0.01, "Expected ~60 minutes, got {}", progress);
+
+        Ok(())
+    }
+
+    async fn insert_flow_session(db_manager: &DbManager, id: &str, start: &str, end: &str) {
+        sqlx::query("INSERT INTO flow_session (id, objective, start, end) VALUES (?1, 'focus', ?2, ?3)")
+            .bind(id)
+            .bind(start)
+            .bind(end)
+            .execute(&db_manager.pool)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_flow_session_tides_measure_finished_sessions() -> Result<()> {
+        let db_manager = create_test_db_manager().await;
+        let tide_progress = TideProgress::new_with_db_manager(db_manager.clone());
+        let tide_start = datetime!(2025-01-06 00:00 UTC);
+        let count_tide = create_test_tide(
+            &TideTemplate::new(MetricsType::FlowSessionCount, TideFrequency::Daily, 3.0, tide_start, None),
+            tide_start,
+        );
+        let minutes_tide = create_test_tide(
+            &TideTemplate::new(MetricsType::FlowSessionMinutes, TideFrequency::Daily, 120.0, tide_start, None),
+            tide_start,
+        );
+
+        insert_flow_session(&db_manager, "session-1", "2025-01-06T09:00:00.000Z", "2025-01-06T09:50:00.000Z").await;
+        assert_eq!(
+            tide_progress.get_tide_progress_cached(&count_tide, datetime!(2025-01-06 10:00 UTC), false).await?,
+            1.0
+        );
+        assert_eq!(
+            tide_progress.get_tide_progress_cached(&minutes_tide, datetime!(2025-01-06 10:00 UTC), false).await?,
+            50.0
+        );
+
+        // Incremental checks pick up sessions finished since the last one
+        insert_flow_session(&db_manager, "session-2", "2025-01-06T10:30:00.000Z", "2025-01-06T11:15:00.000Z").await;
+        assert_eq!(
+            tide_progress.get_tide_progress_cached(&count_tide, datetime!(2025-01-06 12:00 UTC), false).await?,
+            2.0
+        );
+        assert_eq!(
+            tide_progress.get_tide_progress_cached(&minutes_tide, datetime!(2025-01-06 12:00 UTC), false).await?,
+            95.0
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_app_switch_rate_tide_is_recalculated_over_whole_tide() -> Result<()> {
+        let db_manager = create_test_db_manager().await;
+        let tide_progress = TideProgress::new_with_db_manager(db_manager.clone());
+        let tide_start = datetime!(2025-01-06 00:00 UTC);
+        let tide = create_test_tide(
+            &TideTemplate::new(MetricsType::AppSwitchRate, TideFrequency::Daily, 30.0, tide_start, None),
+            tide_start,
+        );
+
+        for (id, app_switches, start_time, end_time) in [
+            (1, 40, datetime!(2025-01-06 09:00 UTC), datetime!(2025-01-06 10:00 UTC)),
+            (2, 0, datetime!(2025-01-06 10:00 UTC), datetime!(2025-01-06 11:00 UTC)),
+        ] {
+            sqlx::query(
+                "INSERT INTO activity_state (id, state, app_switches, start_time, end_time)
+                 VALUES (?1, 'ACTIVE', ?2, ?3, ?4)"
+            )
+            .bind(id)
+            .bind(app_switches)
+            .bind(start_time)
+            .bind(end_time)
+            .execute(&db_manager.pool)
+            .await
+            .unwrap();
+        }
+
+        let rate = tide_progress
+            .get_tide_progress_cached(&tide, datetime!(2025-01-06 10:30 UTC), false)
+            .await?;
+        assert!((rate - 40.0).abs() < 1e-9, "Expected 40 switches per hour, got {}", rate);
+
+        // A quiet hour lowers the rate instead of adding to the previous one
+        let rate = tide_progress
+            .get_tide_progress_cached(&tide, datetime!(2025-01-06 12:00 UTC), false)
+            .await?;
+        assert!((rate - 20.0).abs() < 1e-9, "Expected 20 switches per hour, got {}", rate);
+
+        Ok(())
+    }
 }
@@ -1,14 +1,33 @@
 use ebb_db::{
     db::{
-        models::{tide::Tide, tide_template::TideTemplate},
+        models::{
+            tide::{Tide, TideStatus},
+            tide_pause::TidePause,
+            tide_template::{TideTemplate, DEFAULT_PROGRESS_MILESTONES},
+            tide_template_version::TideTemplateVersion,
+            tide_types::TideFrequency,
+        },
+        tide_milestone_repo::TideMilestoneRepo,
+        tide_pause_repo::TidePauseRepo,
         tide_repo::TideRepo,
         tide_template_repo::TideTemplateRepo,
+        tide_template_validation::{validate_template, TideTemplateError},
+        tide_template_version_repo::TideTemplateVersionRepo,
     },
     db_manager::{self, DbManager},
+    services::device_service::DeviceService,
 };
 use std::sync::Arc;
 use thiserror::Error;
-use time::OffsetDateTime;
+use time::{Date, OffsetDateTime, Weekday};
+use time_tz::{OffsetDateTimeExt, Tz};
+
+use crate::{
+    tide_history::{self, HistoryEntry, HistoryRollup, HistoryStatus, RollupPeriod},
+    tide_metrics::TideMetrics,
+    tide_suggestion::{self, GoalSuggestion, SuggestionOptions},
+    time_helpers,
+};
 
 #[derive(Error, Debug)]
 pub enum TideServiceError {
@@ -20,6 +39,16 @@ pub enum TideServiceError {
     TideNotFound { tide_id: String },
     #[error("Invalid operation: {message}")]
     InvalidOperation { message: String },
+    #[error(transparent)]
+    InvalidTemplate(#[from] TideTemplateError),
+    #[error("Invalid pause: {start_date} to {end_date} (end date must not be before start date)")]
+    InvalidPause { start_date: Date, end_date: Date },
+    #[error("Pause not found: {pause_id}")]
+    PauseNotFound { pause_id: String },
+    #[error("Template is archived: {template_id}")]
+    TemplateArchived { template_id: String },
+    #[error("Invalid date range: {start_date} to {end_date} (end date must not be before start date)")]
+    InvalidDateRange { start_date: Date, end_date: Date },
 }
 
 pub type Result<T> = std::result::Result<T, TideServiceError>;
@@ -29,6 +58,10 @@ pub type Result<T> = std::result::Result<T, TideServiceError>;
 pub struct TideService {
     tide_repo: TideRepo,
     tide_template_repo: TideTemplateRepo,
+    tide_milestone_repo: TideMilestoneRepo,
+    tide_pause_repo: TidePauseRepo,
+    tide_template_version_repo: TideTemplateVersionRepo,
+    device_service: DeviceService,
     _db_manager: Arc<DbManager>, // Keep reference to ensure connection pool stays alive
 }
 
@@ -41,6 +74,10 @@ impl TideService {
         Ok(Self {
             tide_repo: TideRepo::new(db_manager.pool.clone()),
             tide_template_repo: TideTemplateRepo::new(db_manager.pool.clone()),
+            tide_milestone_repo: TideMilestoneRepo::new(db_manager.pool.clone()),
+            tide_pause_repo: TidePauseRepo::new(db_manager.pool.clone()),
+            tide_template_version_repo: TideTemplateVersionRepo::new(db_manager.pool.clone()),
+            device_service: DeviceService::new_with_pool(db_manager.pool.clone()),
             _db_manager: db_manager,
         })
     }
@@ -49,6 +86,10 @@ impl TideService {
         Self {
             tide_repo: TideRepo::new(db_manager.pool.clone()),
             tide_template_repo: TideTemplateRepo::new(db_manager.pool.clone()),
+            tide_milestone_repo: TideMilestoneRepo::new(db_manager.pool.clone()),
+            tide_pause_repo: TidePauseRepo::new(db_manager.pool.clone()),
+            tide_template_version_repo: TideTemplateVersionRepo::new(db_manager.pool.clone()),
+            device_service: DeviceService::new_with_pool(db_manager.pool.clone()),
             _db_manager: db_manager,
         }
     }
@@ -66,14 +107,76 @@ impl TideService {
                 template_id: template_id.to_string(),
             })?;
 
+        let tz = self.get_timezone().await?;
+        let week_start = self.get_week_start_day().await?;
         let start = start_time.unwrap_or_else(OffsetDateTime::now_utc);
-        let tide = Tide::from_template(&template, start);
+        let end = self.calculate_tide_end_time(&template, start, tz, week_start);
+        let mut tide = Tide::from_template_with_end(&template, start, end);
+
+        // Tides starting during a pause are excused up front so they never count against streaks
+        if self.is_paused_on(start.to_timezone(tz).date()).await? {
+            tide.status = Some(TideStatus::Excused);
+        }
 
         self.tide_repo.create_tide(&tide).await?;
 
         Ok(tide)
     }
 
+    /// Get the timezone used for tide boundaries from the device's "timezone" preference
+    /// Falls back to the system timezone, then UTC, when unset or unknown
+    pub async fn get_timezone(&self) -> Result<&'static Tz> {
+        let timezone = self.device_service.get_timezone().await?;
+        Ok(time_helpers::resolve_timezone(timezone.as_deref()))
+    }
+
+    /// Get the day weekly tides start on from the device's "week_start_day" preference
+    pub async fn get_week_start_day(&self) -> Result<Weekday> {
+        let day = self.device_service.get_week_start_day().await?;
+        time_helpers::weekday_from_number(day).ok_or_else(|| TideServiceError::InvalidOperation {
+            message: format!("Invalid week start day: {}", day),
+        })
+    }
+
+    /// Set the day weekly tides start on (0=Sunday, 6=Saturday)
+    /// Open weekly tides keep their start and are re-ended at the next boundary under the
+    /// new week start, so the following tide picks up from there without gaps or overlap
+    pub async fn set_week_start_day(
+        &self,
+        week_start_day: u8,
+        evaluation_time: OffsetDateTime,
+    ) -> Result<Vec<Tide>> {
+        let week_start = time_helpers::weekday_from_number(week_start_day).ok_or_else(|| {
+            TideServiceError::InvalidOperation {
+                message: format!("Invalid week start day: {}", week_start_day),
+            }
+        })?;
+
+        self.device_service
+            .set_week_start_day(week_start_day)
+            .await?;
+
+        let tz = self.get_timezone().await?;
+        let mut migrated_tides = Vec::new();
+
+        for mut tide in self.tide_repo.get_active_tides_at(evaluation_time).await? {
+            if tide.tide_frequency != TideFrequency::Weekly || tide.completed_at.is_some() {
+                continue;
+            }
+
+            let new_end = time_helpers::get_next_week_start(tide.start, tz, week_start);
+            if tide.end == Some(new_end) {
+                continue;
+            }
+
+            self.tide_repo.end_tide(&tide.id, new_end).await?;
+            tide.end = Some(new_end);
+            migrated_tides.push(tide);
+        }
+
+        Ok(migrated_tides)
+    }
+
     pub async fn get_tide(&self, tide_id: &str) -> Result<Option<Tide>> {
         let tide = self.tide_repo.get_tide(tide_id).await?;
         Ok(tide)
@@ -84,11 +187,22 @@ impl TideService {
         Ok(templates)
     }
 
-    pub async fn create_template(&self, template: &TideTemplate) -> Result<()> {
+    /// Validate and store a new template, returning the normalized template that was written
+    /// The template's first goal is recorded as version 1
+    pub async fn create_template(&self, template: &TideTemplate) -> Result<TideTemplate> {
+        let now = OffsetDateTime::now_utc();
+        let template = TideTemplate {
+            version: 1,
+            archived_at: None,
+            ..validate_template(template, now)?
+        };
         self.tide_template_repo
-            .create_tide_template(template)
+            .create_tide_template(&template)
             .await?;
-        Ok(())
+        self.tide_template_version_repo
+            .create_version(&TideTemplateVersion::from_template(&template, template.created_at))
+            .await?;
+        Ok(template)
     }
 
     /// Get a specific template by ID
@@ -100,18 +214,98 @@ impl TideService {
         Ok(template)
     }
 
-    pub async fn update_template(&self, template: &TideTemplate) -> Result<()> {
+    /// Validate and store changes to a template, returning the normalized template that was written
+    /// Goal changes are recorded as a new template version and apply from the current period: the
+    /// running, unfinalized tide picks up the new goal amount and direction, while tides that have
+    /// already ended keep the goal they were scored against. Archived templates can't be edited.
+    pub async fn update_template(&self, template: &TideTemplate) -> Result<TideTemplate> {
+        let now = OffsetDateTime::now_utc();
+        let existing = self.get_template(&template.id).await?.ok_or_else(|| {
+            TideServiceError::TemplateNotFound {
+                template_id: template.id.clone(),
+            }
+        })?;
+        if existing.is_archived() {
+            return Err(TideServiceError::TemplateArchived {
+                template_id: template.id.clone(),
+            });
+        }
+
+        let mut template = validate_template(template, now)?;
+        let goal_changed = template.goal_differs_from(&existing);
+        template.version = if goal_changed { existing.version + 1 } else { existing.version };
+        template.archived_at = None;
+
         self.tide_template_repo
-            .update_tide_template(template)
+            .update_tide_template(&template)
             .await?;
-        Ok(())
+
+        if goal_changed {
+            self.tide_template_version_repo
+                .create_version(&TideTemplateVersion::from_template(&template, now))
+                .await?;
+
+            for tide in self.tide_repo.get_active_tides_at(now).await? {
+                if tide.tide_template_id != template.id || tide.is_finalized() {
+                    continue;
+                }
+                self.tide_repo
+                    .update_tide_goal(&tide.id, template.goal_amount, template.goal_direction)
+                    .await?;
+            }
+        }
+
+        Ok(template)
+    }
+
+    /// Get the recorded goal versions of a template, oldest first
+    pub async fn get_template_versions(&self, template_id: &str) -> Result<Vec<TideTemplateVersion>> {
+        let versions = self
+            .tide_template_version_repo
+            .get_versions(template_id)
+            .await?;
+        Ok(versions)
+    }
+
+    /// Get templates that were archived, most recent first
+    pub async fn get_archived_templates(&self) -> Result<Vec<TideTemplate>> {
+        let templates = self.tide_template_repo.get_archived_tide_templates().await?;
+        Ok(templates)
     }
 
-    pub async fn delete_template(&self, template_id: &str) -> Result<()> {
+    /// Archive (soft-delete) a template so it creates no more tides
+    /// Its tides and versions are kept with a valid parent. A tide still running at
+    /// `evaluation_time` is ended there, so it is finalized on the next check like any other.
+    /// Returns the tides that were ended.
+    pub async fn archive_template(
+        &self,
+        template_id: &str,
+        evaluation_time: OffsetDateTime,
+    ) -> Result<Vec<Tide>> {
+        let template = self.get_template(template_id).await?.ok_or_else(|| {
+            TideServiceError::TemplateNotFound {
+                template_id: template_id.to_string(),
+            }
+        })?;
+        if template.is_archived() {
+            return Ok(Vec::new());
+        }
+
         self.tide_template_repo
-            .delete_tide_template(template_id)
+            .archive_tide_template(template_id, evaluation_time)
             .await?;
-        Ok(())
+
+        let mut ended_tides = Vec::new();
+        for mut tide in self.tide_repo.get_active_tides_at(evaluation_time).await? {
+            if tide.tide_template_id != template_id || tide.is_finalized() {
+                continue;
+            }
+            self.tide_repo.end_tide(&tide.id, evaluation_time).await?;
+            tide.end = Some(evaluation_time);
+            ended_tides.push(tide);
+        }
+
+        Ok(ended_tides)
     }
 
     pub async fn update_tide_progress(&self, tide_id: &str, actual_amount: f64) -> Result<()> {
@@ -126,6 +320,76 @@ impl TideService {
         Ok(())
     }
 
+    /// Record the template's progress milestones the tide has crossed at this amount
+    /// Returns only milestones that weren't already recorded, so each fires once per tide
+    pub async fn record_reached_milestones(
+        &self,
+        tide: &Tide,
+        actual_amount: f64,
+        reached_at: OffsetDateTime,
+    ) -> Result<Vec<u8>> {
+        if tide.goal_amount <= 0.0 {
+            return Ok(Vec::new());
+        }
+
+        let milestones = match self.get_template(&tide.tide_template_id).await? {
+            Some(template) => template.get_progress_milestones(),
+            None => DEFAULT_PROGRESS_MILESTONES.to_vec(),
+        };
+        let percentage = actual_amount / tide.goal_amount * 100.0;
+
+        let mut reached = Vec::new();
+        for milestone in milestones {
+            if percentage >= f64::from(milestone)
+                && self
+                    .tide_milestone_repo
+                    .record_milestone(&tide.id, milestone, reached_at)
+                    .await?
+            {
+                reached.push(milestone);
+            }
+        }
+
+        Ok(reached)
+    }
+
+    /// Get tides whose period has ended but haven't been closed out with a terminal status
+    pub async fn get_expired_tides(&self, evaluation_time: OffsetDateTime) -> Result<Vec<Tide>> {
+        let tides = self
+            .tide_repo
+            .get_expired_unfinalized_tides(evaluation_time)
+            .await?;
+        Ok(tides)
+    }
+
+    /// Close out an expired tide with its final amount and terminal status
+    /// Tides that reach their goal without an existing `completed_at` are stamped as completed at their end
+    pub async fn finalize_tide(&self, tide: &Tide, actual_amount: f64) -> Result<TideStatus> {
+        let status = if tide.is_completed() {
+            TideStatus::Completed
+        } else {
+            TideStatus::for_direction(tide.goal_direction, actual_amount, tide.goal_amount)
+        };
+        let completed_at = match status {
+            TideStatus::Completed => tide.completed_at.or(tide.end),
+            _ => None,
+        };
+
+        self.tide_repo
+            .finalize_tide(&tide.id, actual_amount, status, completed_at)
+            .await?;
+
+        Ok(status)
+    }
+
+    /// Close out a limit tide as missed as soon as it goes over its limit, before its period ends
+    pub async fn fail_tide(&self, tide: &Tide, actual_amount: f64) -> Result<()> {
+        self.tide_repo
+            .finalize_tide(&tide.id, actual_amount, TideStatus::Missed, None)
+            .await?;
+        Ok(())
+    }
+
     pub async fn get_tides_by_template(&self, template_id: &str) -> Result<Vec<Tide>> {
         let tides = self.tide_repo.get_tides_by_template(template_id).await?;
         Ok(tides)
@@ -145,72 +409,425 @@ impl TideService {
         Ok(tides)
     }
 
+    /// Create and score tides for every period that ended while the app was not running
+    /// Starts from the template's last tide end, or its `first_tide` when it has none, and stops
+    /// before the period containing `evaluation_time` (that one is handled by
+    /// `get_or_create_active_tides_for_period`). Periods that already have a tide are skipped,
+    /// so running this repeatedly is safe.
+    pub async fn backfill_missed_tides(
+        &self,
+        metrics: &TideMetrics,
+        evaluation_time: OffsetDateTime,
+    ) -> Result<Vec<Tide>> {
+        let tz = self.get_timezone().await?;
+        let week_start = self.get_week_start_day().await?;
+        let templates = self.get_all_templates().await?;
+        let mut backfilled_tides = Vec::new();
+
+        for template in &templates {
+            // Indefinite tides have no periods to backfill
+            if template.tide_frequency == TideFrequency::Indefinite {
+                continue;
+            }
+
+            let mut period_start = match self
+                .tide_repo
+                .get_latest_tide_end_for_template(&template.id)
+                .await?
+            {
+                Some(latest_end) => {
+                    let start = time_helpers::get_period_start(
+                        template.tide_frequency,
+                        latest_end,
+                        tz,
+                        week_start,
+                    );
+                    if start == latest_end {
+                        start
+                    } else {
+                        // Latest tide ended mid-period, resume from the following period
+                        match self.calculate_tide_end_time(template, start, tz, week_start) {
+                            Some(end) => end,
+                            None => continue,
+                        }
+                    }
+                }
+                None => time_helpers::get_period_start(
+                    template.tide_frequency,
+                    template.first_tide,
+                    tz,
+                    week_start,
+                ),
+            };
+
+            while let Some(period_end) =
+                self.calculate_tide_end_time(template, period_start, tz, week_start)
+            {
+                if period_end > evaluation_time {
+                    break;
+                }
+
+                if self.should_create_tide_now(template, period_start, tz)
+                    && !self
+                        .tide_repo
+                        .has_tide_for_date_range(&template.id, period_start, period_end)
+                        .await?
+                {
+                    let mut tide =
+                        Tide::from_template_with_end(template, period_start, Some(period_end));
+                    tide.actual_amount = metrics.measure(&tide, period_start, period_end).await?;
+                    let status = if self.is_paused_on(period_start.to_timezone(tz).date()).await? {
+                        TideStatus::Excused
+                    } else {
+                        TideStatus::for_direction(tide.goal_direction, tide.actual_amount, tide.goal_amount)
+                    };
+                    if status == TideStatus::Completed {
+                        tide.completed_at = Some(period_end);
+                    }
+                    tide.status = Some(status);
+
+                    self.tide_repo.create_tide(&tide).await?;
+                    backfilled_tides.push(tide);
+                }
+
+                period_start = period_end;
+            }
+        }
+
+        Ok(backfilled_tides)
+    }
+
+    /// Pause tides for a range of local dates (inclusive), e.g. for a vacation
+    /// Tides starting during the pause are created as excused. Unfinalized tides already running
+    /// at `evaluation_time` that started within the pause are excused too, so pausing "from today" works.
+    pub async fn create_pause(
+        &self,
+        start_date: Date,
+        end_date: Date,
+        reason: Option<String>,
+        evaluation_time: OffsetDateTime,
+    ) -> Result<(TidePause, Vec<Tide>)> {
+        if end_date < start_date {
+            return Err(TideServiceError::InvalidPause { start_date, end_date });
+        }
+
+        let pause = TidePause::new(start_date, end_date, reason);
+        self.tide_pause_repo.create_pause(&pause).await?;
+
+        let tz = self.get_timezone().await?;
+        let mut excused_tides = Vec::new();
+        for mut tide in self.tide_repo.get_active_tides_at(evaluation_time).await? {
+            if tide.is_finalized() || !pause.contains(tide.start.to_timezone(tz).date()) {
+                continue;
+            }
+            self.tide_repo
+                .finalize_tide(&tide.id, tide.actual_amount, TideStatus::Excused, None)
+                .await?;
+            tide.status = Some(TideStatus::Excused);
+            excused_tides.push(tide);
+        }
+
+        Ok((pause, excused_tides))
+    }
+
+    pub async fn get_pauses(&self) -> Result<Vec<TidePause>> {
+        let pauses = self.tide_pause_repo.get_all_pauses().await?;
+        Ok(pauses)
+    }
+
+    /// Remove a pause so tides created from now on count again
+    /// Tides already excused by the pause stay excused
+    pub async fn delete_pause(&self, pause_id: &str) -> Result<()> {
+        if self.tide_pause_repo.get_pause(pause_id).await?.is_none() {
+            return Err(TideServiceError::PauseNotFound {
+                pause_id: pause_id.to_string(),
+            });
+        }
+        self.tide_pause_repo.delete_pause(pause_id).await?;
+        Ok(())
+    }
+
+    /// Check if a local date falls within any pause
+    pub async fn is_paused_on(&self, date: Date) -> Result<bool> {
+        let pauses = self.tide_pause_repo.get_pauses_on(date).await?;
+        Ok(!pauses.is_empty())
+    }
+
+    /// Add or remove a one-off skip date for a template
+    /// The period starting on a skipped local date gets no tide at all
+    pub async fn set_template_skip_date(
+        &self,
+        template_id: &str,
+        date: Date,
+        skipped: bool,
+    ) -> Result<TideTemplate> {
+        let mut template = self.get_template(template_id).await?.ok_or_else(|| {
+            TideServiceError::TemplateNotFound {
+                template_id: template_id.to_string(),
+            }
+        })?;
+
+        let mut skip_dates = template.get_skip_dates();
+        skip_dates.retain(|skip_date| *skip_date != date);
+        if skipped {
+            skip_dates.push(date);
+        }
+        template.skip_dates = TideTemplate::set_skip_dates(skip_dates);
+        template.updated_at = OffsetDateTime::now_utc();
+
+        self.update_template(&template).await
+    }
+
+    /// Suggest a goal for a template from the amounts of its most recent matching periods
+    /// Walks back from the period containing `evaluation_time` (which is excluded), sampling periods
+    /// a tide would be created for - so `day_of_week` and skip dates are respected - and leaving out
+    /// paused ones. The suggestion is only returned, the template is not changed.
+    pub async fn suggest_goal(
+        &self,
+        template_id: &str,
+        metrics: &TideMetrics,
+        evaluation_time: OffsetDateTime,
+        options: &SuggestionOptions,
+    ) -> Result<Option<GoalSuggestion>> {
+        let template = self.get_template(template_id).await?.ok_or_else(|| {
+            TideServiceError::TemplateNotFound {
+                template_id: template_id.to_string(),
+            }
+        })?;
+
+        // Indefinite tides have no periods to learn from
+        if template.tide_frequency == TideFrequency::Indefinite {
+            return Ok(None);
+        }
+
+        let tz = self.get_timezone().await?;
+        let week_start = self.get_week_start_day().await?;
+        let mut samples = Vec::new();
+        let mut period_start =
+            time_helpers::get_period_start(template.tide_frequency, evaluation_time, tz, week_start);
+
+        // Bounded so templates with few matching days or long pauses can't scan forever
+        for _ in 0..options.periods.saturating_mul(7) {
+            if samples.len() >= options.periods {
+                break;
+            }
+
+            let period_end = period_start;
+            period_start = time_helpers::get_period_start(
+                template.tide_frequency,
+                period_end - time::Duration::seconds(1),
+                tz,
+                week_start,
+            );
+
+            if !self.should_create_tide_now(&template, period_start, tz)
+                || self.is_paused_on(period_start.to_timezone(tz).date()).await?
+            {
+                continue;
+            }
+
+            let tide = Tide::from_template_with_end(&template, period_start, Some(period_end));
+            samples.push(metrics.measure(&tide, period_start, period_end).await?);
+        }
+
+        Ok(tide_suggestion::suggest_from_samples(&template, &samples, options))
+    }
+
+    /// Get a template's history between two local dates (inclusive) as one entry per expected period
+    /// Periods are those a tide would be created for, from the period containing `start_date` up to
+    /// the one containing `evaluation_time`, and not before the template's `first_tide` or after it
+    /// was archived. Periods without a tide are filled in against the goal the template had then.
+    /// Indefinite templates have no periods, so their tides in the range are returned as-is.
+    pub async fn get_template_history(
+        &self,
+        template_id: &str,
+        start_date: Date,
+        end_date: Date,
+        evaluation_time: OffsetDateTime,
+    ) -> Result<Vec<HistoryEntry>> {
+        if end_date < start_date {
+            return Err(TideServiceError::InvalidDateRange { start_date, end_date });
+        }
+
+        let template = self.get_template(template_id).await?.ok_or_else(|| {
+            TideServiceError::TemplateNotFound {
+                template_id: template_id.to_string(),
+            }
+        })?;
+
+        let tz = self.get_timezone().await?;
+        let week_start = self.get_week_start_day().await?;
+        let range_start = time_helpers::local_midnight(start_date, tz);
+        let range_end = time_helpers::local_midnight(end_date + time::Duration::days(1), tz);
+
+        let mut tides = self.tide_repo.get_tides_by_template(template_id).await?;
+        tides.reverse();
+
+        if template.tide_frequency == TideFrequency::Indefinite {
+            return Ok(tides
+                .iter()
+                .filter(|tide| tide.start < range_end && tide.end.is_none_or(|end| end > range_start))
+                .map(HistoryEntry::from_tide)
+                .collect());
+        }
+
+        let versions = self.tide_template_version_repo.get_versions(template_id).await?;
+        let history_end = template.archived_at.map_or(range_end, |archived_at| archived_at.min(range_end));
+        let mut entries = Vec::new();
+        let mut period_start = time_helpers::get_period_start(template.tide_frequency, range_start, tz, week_start)
+            .max(time_helpers::get_period_start(template.tide_frequency, template.first_tide, tz, week_start));
+
+        while period_start < history_end && period_start <= evaluation_time {
+            let Some(period_end) = self.calculate_tide_end_time(&template, period_start, tz, week_start) else {
+                break;
+            };
+
+            if let Some(tide) = tides
+                .iter()
+                .find(|tide| tide.start >= period_start && tide.start < period_end)
+            {
+                entries.push(HistoryEntry::from_tide(tide));
+            } else {
+                let template_then = tide_history::template_as_of(&template, &versions, period_start);
+                if self.should_create_tide_now(&template_then, period_start, tz) {
+                    let status = if self.is_paused_on(period_start.to_timezone(tz).date()).await? {
+                        HistoryStatus::Excused
+                    } else if period_end > evaluation_time {
+                        HistoryStatus::InProgress
+                    } else {
+                        HistoryStatus::NoTide
+                    };
+                    entries.push(HistoryEntry::without_tide(
+                        &template_then,
+                        period_start,
+                        Some(period_end),
+                        status,
+                    ));
+                }
+            }
+
+            period_start = period_end;
+        }
+
+        Ok(entries)
+    }
+
+    /// Roll a template's history between two local dates up into weeks or months
+    pub async fn get_template_rollups(
+        &self,
+        template_id: &str,
+        start_date: Date,
+        end_date: Date,
+        period: RollupPeriod,
+        evaluation_time: OffsetDateTime,
+    ) -> Result<Vec<HistoryRollup>> {
+        let entries = self
+            .get_template_history(template_id, start_date, end_date, evaluation_time)
+            .await?;
+        let tz = self.get_timezone().await?;
+        let week_start = self.get_week_start_day().await?;
+
+        Ok(tide_history::rollup(&entries, period, tz, week_start))
+    }
+
     /// Get or create active tides for the current period based on templates
     /// This method ensures that all templates have appropriate active tides for the evaluation time
-    /// Currently only creates tides for the current evaluation time (no backfill)
+    /// Missed past periods are filled in separately by `backfill_missed_tides`
     pub async fn get_or_create_active_tides_for_period(
         &self,
         evaluation_time: OffsetDateTime,
     ) -> Result<Vec<Tide>> {
-        // Get all templates and active tides (2 efficient queries)
+        let mut active_tides = self.get_active_tides(evaluation_time).await?;
+        let created_tides = self
+            .create_missing_tides_for_period(&active_tides, evaluation_time)
+            .await?;
+        active_tides.extend(created_tides);
+
+        Ok(active_tides)
+    }
+
+    /// Get the tides whose period covers the evaluation time
+    pub async fn get_active_tides(&self, evaluation_time: OffsetDateTime) -> Result<Vec<Tide>> {
+        let tides = self.tide_repo.get_active_tides_at(evaluation_time).await?;
+        Ok(tides)
+    }
+
+    /// Create tides for templates that have no active tide at the evaluation time
+    /// Returns only the newly created tides
+    pub async fn create_missing_tides_for_period(
+        &self,
+        active_tides: &[Tide],
+        evaluation_time: OffsetDateTime,
+    ) -> Result<Vec<Tide>> {
+        let tz = self.get_timezone().await?;
+        let week_start = self.get_week_start_day().await?;
+
         let templates = self.get_all_templates().await?;
-        let mut active_tides = self.tide_repo.get_active_tides_at(evaluation_time).await?;
 
-        println!("Active tides: {:?}", active_tides);
         // Create a set of template IDs that already have active tides
-        let active_template_ids: std::collections::HashSet<String> = active_tides
+        let active_template_ids: std::collections::HashSet<&str> = active_tides
             .iter()
-            .map(|tide| tide.tide_template_id.clone())
+            .map(|tide| tide.tide_template_id.as_str())
             .collect();
 
-        println!("Active template IDs: {:?}", active_template_ids);
-
         // Find templates that don't have active tides
         let templates_needing_evaluation: Vec<&TideTemplate> = templates
             .iter()
-            .filter(|template| !active_template_ids.contains(&template.id))
+            .filter(|template| !active_template_ids.contains(template.id.as_str()))
             .collect();
 
-        println!(
-            "Templates needing evaluation: {:?}",
-            templates_needing_evaluation
-        );
-
         // For each template without an active tide, check if we should create one
+        let mut created_tides = Vec::new();
         for template in templates_needing_evaluation {
-            if self.should_create_tide_now(template, evaluation_time) {
-                // Calculate the appropriate start time based on tide frequency
-                let tide_start_time = self.calculate_tide_start_time(template, evaluation_time);
-                println!("Creating tide for template: {:?}", template);
-                println!("Tide start time: {:?}", tide_start_time);
+            // Calculate the appropriate start time based on tide frequency
+            let tide_start_time = self.calculate_tide_start_time(template, evaluation_time, tz, week_start);
+            if self.should_create_tide_now(template, tide_start_time, tz) {
                 let new_tide = self
                     .create_tide_from_template(&template.id, Some(tide_start_time))
                     .await?;
-                println!("New tide: {:?}", new_tide);
-                active_tides.push(new_tide);
+                created_tides.push(new_tide);
             }
         }
 
-        Ok(active_tides)
+        Ok(created_tides)
     }
 
-    /// Determine if we should create a new tide for a template at the given time
+    /// Determine if we should create a new tide for a template for the period starting at the given time
+    /// Periods starting on one of the template's skip dates get no tide, and templates with a recurrence
+    /// rule only get tides for periods the rule occurs in
     fn should_create_tide_now(
         &self,
         template: &TideTemplate,
         evaluation_time: OffsetDateTime,
+        tz: &Tz,
     ) -> bool {
-        match template.tide_frequency.as_str() {
-            "indefinite" => true, // Always create if no active tide exists
-            "daily" => {
-                // Only create if evaluation day matches the template's day_of_week pattern
-                let current_weekday = evaluation_time.weekday().number_days_from_sunday() as u8;
+        if template.is_skipped_on(evaluation_time.to_timezone(tz).date()) {
+            return false;
+        }
+
+        if let Some(rule) = &template.recurrence_rule {
+            return time_helpers::rule_occurs_in_period(
+                rule,
+                template.first_tide,
+                template.tide_frequency,
+                evaluation_time,
+                tz,
+            );
+        }
+
+        match template.tide_frequency {
+            TideFrequency::Indefinite => true, // Always create if no active tide exists
+            TideFrequency::Daily => {
+                // Only create if the local evaluation day matches the template's day_of_week pattern
+                let current_weekday = evaluation_time
+                    .to_timezone(tz)
+                    .weekday()
+                    .number_days_from_sunday();
                 let allowed_days = template.get_days_of_week();
                 allowed_days.contains(&current_weekday)
             }
-            "weekly" => true,  // Always create if no active tide exists
-            "monthly" => true, // Always create if no active tide exists
-            _ => false,        // Unknown frequency
+            TideFrequency::Weekly => true,  // Always create if no active tide exists
+            TideFrequency::Monthly => true, // Always create if no active tide exists
         }
     }
 
@@ -219,24 +836,31 @@ impl TideService {
         &self,
         template: &TideTemplate,
         evaluation_time: OffsetDateTime,
+        tz: &Tz,
+        week_start: Weekday,
     ) -> OffsetDateTime {
-        use crate::time_helpers::{get_day_start, get_month_start, get_week_start};
-
-        match template.tide_frequency.as_str() {
-            "daily" => get_day_start(evaluation_time), // Start of the current day
-            "weekly" => get_week_start(evaluation_time), // Start of the current week
-            "monthly" => get_month_start(evaluation_time), // Start of the current month
-            "indefinite" => get_day_start(evaluation_time), // Default to start of day for indefinite
-            _ => evaluation_time, // Fallback to current time for unknown frequencies
-        }
+        time_helpers::get_period_start(template.tide_frequency, evaluation_time, tz, week_start)
+    }
+
+    /// Calculate the end time for a tide starting at `start` - the next local calendar boundary
+    fn calculate_tide_end_time(
+        &self,
+        template: &TideTemplate,
+        start: OffsetDateTime,
+        tz: &Tz,
+        week_start: Weekday,
+    ) -> Option<OffsetDateTime> {
+        time_helpers::get_period_end(template.tide_frequency, start, tz, week_start)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use ebb_db::db::models::tide_types::MetricsType;
     use sqlx::sqlite::SqlitePoolOptions;
     use time::macros::datetime;
+    use time_tz::{timezones, OffsetDateTimeExt, TimeZone};
 
     // NOTE: The database migrations create 2 default templates that affect test expectations:
     // 1. 'default-daily-template' - daily, weekdays only (Mon-Fri), creating, 180.0 goal
@@ -285,8 +909,8 @@ mod tests {
         // First create a template
         let first_tide = datetime!(2025-01-01 0:00 UTC);
         let template = TideTemplate::new(
-            "creating".to_string(),
-            "daily".to_string(),
+            MetricsType::Creating,
+            TideFrequency::Daily,
             100.0,
             first_tide,
             None,
@@ -300,8 +924,8 @@ mod tests {
 
         // Verify the tide was created correctly
         assert_eq!(tide.tide_template_id, template.id);
-        assert_eq!(tide.metrics_type, "creating");
-        assert_eq!(tide.tide_frequency, "daily");
+        assert_eq!(tide.metrics_type, MetricsType::Creating);
+        assert_eq!(tide.tide_frequency, TideFrequency::Daily);
         assert_eq!(tide.goal_amount, 100.0);
         assert_eq!(tide.actual_amount, 0.0);
         assert!(tide.end.is_some()); // Should have system-generated end time
@@ -318,8 +942,8 @@ mod tests {
         // Create a template and tide
         let first_tide = datetime!(2025-01-01 0:00 UTC);
         let template = TideTemplate::new(
-            "creating".to_string(),
-            "daily".to_string(),
+            MetricsType::Creating,
+            TideFrequency::Daily,
             100.0,
             first_tide,
             None,
@@ -349,15 +973,15 @@ mod tests {
 
         let first_tide = datetime!(2025-01-01 0:00 UTC);
         let indefinite_template = TideTemplate::new(
-            "project".to_string(),
-            "indefinite".to_string(),
+            MetricsType::Neutral,
+            TideFrequency::Indefinite,
             1000.0,
             first_tide,
             None,
         );
 
         let test_time = datetime!(2025-01-06 10:00 UTC); // Monday
-        assert!(tide_service.should_create_tide_now(&indefinite_template, test_time));
+        assert!(tide_service.should_create_tide_now(&indefinite_template, test_time, timezones::db::UTC));
 
         Ok(())
     }
@@ -369,15 +993,15 @@ mod tests {
 
         let first_tide = datetime!(2025-01-01 0:00 UTC);
         let weekly_template = TideTemplate::new(
-            "learning".to_string(),
-            "weekly".to_string(),
+            MetricsType::Consuming,
+            TideFrequency::Weekly,
             500.0,
             first_tide,
             None,
         );
 
         let test_time = datetime!(2025-01-06 10:00 UTC); // Monday
-        assert!(tide_service.should_create_tide_now(&weekly_template, test_time));
+        assert!(tide_service.should_create_tide_now(&weekly_template, test_time, timezones::db::UTC));
 
         Ok(())
     }
@@ -389,15 +1013,15 @@ mod tests {
 
         let first_tide = datetime!(2025-01-01 0:00 UTC);
         let monthly_template = TideTemplate::new(
-            "fitness".to_string(),
-            "monthly".to_string(),
+            MetricsType::Idle,
+            TideFrequency::Monthly,
             2000.0,
             first_tide,
             None,
         );
 
         let test_time = datetime!(2025-01-06 10:00 UTC); // Monday
-        assert!(tide_service.should_create_tide_now(&monthly_template, test_time));
+        assert!(tide_service.should_create_tide_now(&monthly_template, test_time, timezones::db::UTC));
 
         Ok(())
     }
@@ -409,8 +1033,8 @@ mod tests {
 
         let first_tide = datetime!(2025-01-01 0:00 UTC);
         let weekday_template = TideTemplate::new(
-            "creating".to_string(),
-            "daily".to_string(),
+            MetricsType::Creating,
+            TideFrequency::Daily,
             100.0,
             first_tide,
             Some("1,2,3,4,5".to_string()), // Monday through Friday
@@ -418,23 +1042,23 @@ mod tests {
 
         // Test Monday (1) - should be true
         let monday = datetime!(2025-01-06 10:00 UTC); // This is a Monday
-        assert!(tide_service.should_create_tide_now(&weekday_template, monday));
+        assert!(tide_service.should_create_tide_now(&weekday_template, monday, timezones::db::UTC));
 
         // Test Tuesday (2) - should be true
         let tuesday = datetime!(2025-01-07 10:00 UTC); // This is a Tuesday
-        assert!(tide_service.should_create_tide_now(&weekday_template, tuesday));
+        assert!(tide_service.should_create_tide_now(&weekday_template, tuesday, timezones::db::UTC));
 
         // Test Friday (5) - should be true
         let friday = datetime!(2025-01-03 10:00 UTC); // This is a Friday
-        assert!(tide_service.should_create_tide_now(&weekday_template, friday));
+        assert!(tide_service.should_create_tide_now(&weekday_template, friday, timezones::db::UTC));
 
         // Test Sunday (0) - should be false
         let sunday = datetime!(2025-01-05 10:00 UTC); // This is a Sunday
-        assert!(!tide_service.should_create_tide_now(&weekday_template, sunday));
+        assert!(!tide_service.should_create_tide_now(&weekday_template, sunday, timezones::db::UTC));
 
         // Test Saturday (6) - should be false
         let saturday = datetime!(2025-01-04 10:00 UTC); // This is a Saturday
-        assert!(!tide_service.should_create_tide_now(&weekday_template, saturday));
+        assert!(!tide_service.should_create_tide_now(&weekday_template, saturday, timezones::db::UTC));
 
         Ok(())
     }
@@ -446,8 +1070,8 @@ mod tests {
 
         let first_tide = datetime!(2025-01-01 0:00 UTC);
         let all_days_template = TideTemplate::new(
-            "creating".to_string(),
-            "daily".to_string(),
+            MetricsType::Creating,
+            TideFrequency::Daily,
             100.0,
             first_tide,
             None, // All days (default)
@@ -455,13 +1079,13 @@ mod tests {
 
         // Test various days - all should be true
         let monday = datetime!(2025-01-06 10:00 UTC); // Monday
-        assert!(tide_service.should_create_tide_now(&all_days_template, monday));
+        assert!(tide_service.should_create_tide_now(&all_days_template, monday, timezones::db::UTC));
 
         let sunday = datetime!(2025-01-05 10:00 UTC); // Sunday
-        assert!(tide_service.should_create_tide_now(&all_days_template, sunday));
+        assert!(tide_service.should_create_tide_now(&all_days_template, sunday, timezones::db::UTC));
 
         let saturday = datetime!(2025-01-04 10:00 UTC); // Saturday
-        assert!(tide_service.should_create_tide_now(&all_days_template, saturday));
+        assert!(tide_service.should_create_tide_now(&all_days_template, saturday, timezones::db::UTC));
 
         Ok(())
     }
@@ -473,8 +1097,8 @@ mod tests {
 
         let first_tide = datetime!(2025-01-01 0:00 UTC);
         let specific_days_template = TideTemplate::new(
-            "creating".to_string(),
-            "daily".to_string(),
+            MetricsType::Creating,
+            TideFrequency::Daily,
             100.0,
             first_tide,
             Some("1,3,5".to_string()), // Monday, Wednesday, Friday
@@ -482,43 +1106,72 @@ mod tests {
 
         // Test Monday (1) - should be true
         let monday = datetime!(2025-01-06 10:00 UTC);
-        assert!(tide_service.should_create_tide_now(&specific_days_template, monday));
+        assert!(tide_service.should_create_tide_now(&specific_days_template, monday, timezones::db::UTC));
 
         // Test Wednesday (3) - should be true
         let wednesday = datetime!(2025-01-08 10:00 UTC);
-        assert!(tide_service.should_create_tide_now(&specific_days_template, wednesday));
+        assert!(tide_service.should_create_tide_now(&specific_days_template, wednesday, timezones::db::UTC));
 
         // Test Friday (5) - should be true
         let friday = datetime!(2025-01-03 10:00 UTC);
-        assert!(tide_service.should_create_tide_now(&specific_days_template, friday));
+        assert!(tide_service.should_create_tide_now(&specific_days_template, friday, timezones::db::UTC));
 
         // Test Tuesday (2) - should be false
         let tuesday = datetime!(2025-01-07 10:00 UTC);
-        assert!(!tide_service.should_create_tide_now(&specific_days_template, tuesday));
+        assert!(!tide_service.should_create_tide_now(&specific_days_template, tuesday, timezones::db::UTC));
 
         // Test Sunday (0) - should be false
         let sunday = datetime!(2025-01-05 10:00 UTC);
-        assert!(!tide_service.should_create_tide_now(&specific_days_template, sunday));
+        assert!(!tide_service.should_create_tide_now(&specific_days_template, sunday, timezones::db::UTC));
 
         Ok(())
     }
 
     #[tokio::test]
-    async fn test_should_create_tide_now_unknown_frequency() -> Result<()> {
+    async fn test_should_create_tide_now_with_recurrence_rule() -> Result<()> {
         let db_manager = create_test_db_manager().await;
         let tide_service = TideService::new_with_manager(db_manager);
 
-        let first_tide = datetime!(2025-01-01 0:00 UTC);
-        let unknown_template = TideTemplate::new(
-            "unknown".to_string(),
-            "yearly".to_string(), // Unknown frequency
-            1000.0,
-            first_tide,
+        // Every other week, anchored at the week of the first tide
+        let mut biweekly_template = TideTemplate::new(
+            MetricsType::Creating,
+            TideFrequency::Weekly,
+            600.0,
+            datetime!(2025-01-06 0:00 UTC),
             None,
         );
-
-        let test_time = datetime!(2025-01-06 10:00 UTC);
-        assert!(!tide_service.should_create_tide_now(&unknown_template, test_time));
+        biweekly_template.recurrence_rule = Some("RRULE:FREQ=WEEKLY;INTERVAL=2".parse().unwrap());
+        let utc = timezones::db::UTC;
+        assert!(tide_service.should_create_tide_now(&biweekly_template, datetime!(2025-01-06 0:00 UTC), utc));
+        assert!(!tide_service.should_create_tide_now(&biweekly_template, datetime!(2025-01-13 0:00 UTC), utc));
+        assert!(tide_service.should_create_tide_now(&biweekly_template, datetime!(2025-01-20 0:00 UTC), utc));
+
+        // Daily tides on the first Monday of each month only, overriding day_of_week
+        let mut first_monday_template = TideTemplate::new(
+            MetricsType::Creating,
+            TideFrequency::Daily,
+            100.0,
+            datetime!(2025-01-01 0:00 UTC),
+            Some("1,2,3,4,5".to_string()),
+        );
+        first_monday_template.recurrence_rule = Some("RRULE:FREQ=MONTHLY;BYDAY=1MO".parse().unwrap());
+        assert!(tide_service.should_create_tide_now(&first_monday_template, datetime!(2025-01-06 10:00 UTC), utc));
+        assert!(!tide_service.should_create_tide_now(&first_monday_template, datetime!(2025-01-13 10:00 UTC), utc));
+        assert!(!tide_service.should_create_tide_now(&first_monday_template, datetime!(2025-01-07 10:00 UTC), utc));
+        assert!(tide_service.should_create_tide_now(&first_monday_template, datetime!(2025-02-03 10:00 UTC), utc));
+
+        // Monthly tides in months the rule occurs in
+        let mut quarterly_template = TideTemplate::new(
+            MetricsType::Creating,
+            TideFrequency::Monthly,
+            2000.0,
+            datetime!(2025-01-01 0:00 UTC),
+            None,
+        );
+        quarterly_template.recurrence_rule = Some("RRULE:FREQ=MONTHLY;INTERVAL=3;BYMONTHDAY=15".parse().unwrap());
+        assert!(tide_service.should_create_tide_now(&quarterly_template, datetime!(2025-01-01 0:00 UTC), utc));
+        assert!(!tide_service.should_create_tide_now(&quarterly_template, datetime!(2025-02-01 0:00 UTC), utc));
+        assert!(tide_service.should_create_tide_now(&quarterly_template, datetime!(2025-04-01 0:00 UTC), utc));
 
         Ok(())
     }
@@ -548,8 +1201,8 @@ mod tests {
         // Create an indefinite template
         let first_tide = datetime!(2025-01-01 0:00 UTC);
         let template = TideTemplate::new(
-            "project".to_string(),
-            "indefinite".to_string(),
+            MetricsType::Neutral,
+            TideFrequency::Indefinite,
             1000.0,
             first_tide,
             None,
@@ -565,7 +1218,7 @@ mod tests {
             .await?;
         assert_eq!(active_tides.len(), 3);
         assert_eq!(active_tides[0].tide_template_id, template.id);
-        assert_eq!(active_tides[0].tide_frequency, "indefinite");
+        assert_eq!(active_tides[0].tide_frequency, TideFrequency::Indefinite);
 
         // Call again - should not create another tide (should return existing active tide)
         let active_tides_2 = tide_service
@@ -585,8 +1238,8 @@ mod tests {
         // Create a daily template for weekdays only (Monday-Friday: 1,2,3,4,5)
         let first_tide = datetime!(2025-01-01 0:00 UTC);
         let template = TideTemplate::new(
-            "creating".to_string(),
-            "daily".to_string(),
+            MetricsType::Creating,
+            TideFrequency::Daily,
             100.0,
             first_tide,
             Some("1,2,3,4,5".to_string()), // Monday through Friday
@@ -613,8 +1266,8 @@ mod tests {
         // Create a daily template for weekdays only (Monday-Friday: 1,2,3,4,5)
         let first_tide = datetime!(2025-01-01 0:00 UTC);
         let template = TideTemplate::new(
-            "creating".to_string(),
-            "daily".to_string(),
+            MetricsType::Creating,
+            TideFrequency::Daily,
             100.0,
             first_tide,
             Some("1,2,3,4,5".to_string()), // Monday through Friday
@@ -640,8 +1293,8 @@ mod tests {
         // Create a weekly template
         let first_tide = datetime!(2025-01-01 0:00 UTC);
         let template = TideTemplate::new(
-            "learning".to_string(),
-            "weekly".to_string(),
+            MetricsType::Consuming,
+            TideFrequency::Weekly,
             500.0,
             first_tide,
             None,
@@ -657,7 +1310,7 @@ mod tests {
             .await?;
         assert_eq!(active_tides.len(), 3);
         assert_eq!(active_tides[0].tide_template_id, template.id);
-        assert_eq!(active_tides[0].tide_frequency, "weekly");
+        assert_eq!(active_tides[0].tide_frequency, TideFrequency::Weekly);
 
         Ok(())
     }
@@ -672,22 +1325,22 @@ mod tests {
 
         // Create multiple templates
         let indefinite_template = TideTemplate::new(
-            "project".to_string(),
-            "indefinite".to_string(),
+            MetricsType::Neutral,
+            TideFrequency::Indefinite,
             1000.0,
             first_tide,
             None,
         );
         let weekly_template = TideTemplate::new(
-            "learning".to_string(),
-            "weekly".to_string(),
+            MetricsType::Consuming,
+            TideFrequency::Weekly,
             500.0,
             first_tide,
             None,
         );
         let daily_template = TideTemplate::new(
-            "creating".to_string(),
-            "daily".to_string(),
+            MetricsType::Creating,
+            TideFrequency::Daily,
             100.0,
             first_tide,
             None, // All days
@@ -724,8 +1377,8 @@ mod tests {
         // Create template and manually create a tide
         let first_tide = datetime!(2025-01-01 0:00 UTC);
         let template = TideTemplate::new(
-            "creating".to_string(),
-            "daily".to_string(),
+            MetricsType::Creating,
+            TideFrequency::Daily,
             100.0,
             first_tide,
             None,
@@ -759,22 +1412,22 @@ mod tests {
 
         // Create templates with different scenarios
         let template_with_active = TideTemplate::new(
-            "existing".to_string(),
-            "daily".to_string(),
+            MetricsType::Creating,
+            TideFrequency::Daily,
             100.0,
             first_tide,
             None,
         );
         let template_should_create = TideTemplate::new(
-            "should_create".to_string(),
-            "weekly".to_string(),
+            MetricsType::Consuming,
+            TideFrequency::Weekly,
             200.0,
             first_tide,
             None,
         );
         let template_weekdays_only = TideTemplate::new(
-            "weekdays".to_string(),
-            "daily".to_string(),
+            MetricsType::Neutral,
+            TideFrequency::Daily,
             300.0,
             first_tide,
             Some("1,2,3,4,5".to_string()), // Weekdays only
@@ -821,11 +1474,12 @@ mod tests {
     async fn test_tide_starts_at_beginning_of_day() -> Result<()> {
         let db_manager = create_test_db_manager().await;
         let tide_service = TideService::new_with_manager(db_manager.clone());
+        tide_service.device_service.set_timezone("America/Chicago").await?;
 
         // Create a daily template
         let template = TideTemplate::new(
-            "creating".to_string(),
-            "daily".to_string(),
+            MetricsType::Creating,
+            TideFrequency::Daily,
             180.0,
             datetime!(2025-01-06 00:00 UTC),
             Some("1,2,3,4,5".to_string()),
@@ -851,25 +1505,905 @@ mod tests {
         // The tide should be stored in UTC but represent midnight in local timezone
         assert_eq!(our_tide.start.offset(), time::UtcOffset::UTC);
 
-        // When converted to local time, should be at midnight
-        let local_offset = time::UtcOffset::current_local_offset().unwrap_or(time::UtcOffset::UTC);
-        let start_local = our_tide.start.to_offset(local_offset);
+        // When converted to the configured timezone, should be at midnight
+        let chicago = timezones::db::america::CHICAGO;
+        let start_local = our_tide.start.to_timezone(chicago);
         assert_eq!(start_local.hour(), 0);
         assert_eq!(start_local.minute(), 0);
         assert_eq!(start_local.second(), 0);
 
         // Verify the date in local timezone matches expected date
-        let evaluation_local = evaluation_time.to_offset(local_offset);
+        let evaluation_local = evaluation_time.to_timezone(chicago);
         assert_eq!(start_local.date(), evaluation_local.date());
 
         // The tide should end at the end of the day (for daily tides)
         let end_time = our_tide.end.expect("Daily tide should have an end time");
         assert_eq!(end_time.offset(), time::UtcOffset::UTC);
-        let end_local = end_time.to_offset(local_offset);
+        let end_local = end_time.to_timezone(chicago);
         assert_eq!(end_local.hour(), 0);
         assert_eq!(end_local.minute(), 0);
         assert_eq!(end_local.second(), 0);
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_monthly_tide_ends_at_next_month_start() -> Result<()> {
+        let db_manager = create_test_db_manager().await;
+        let tide_service = TideService::new_with_manager(db_manager);
+        tide_service.device_service.set_timezone("America/Chicago").await?;
+
+        let template = TideTemplate::new(
+            MetricsType::Creating,
+            TideFrequency::Monthly,
+            2000.0,
+            datetime!(2025-01-01 00:00 UTC),
+            None,
+        );
+        tide_service.create_template(&template).await?;
+
+        let evaluation_time = datetime!(2025-02-14 12:00 UTC);
+        let active_tides = tide_service
+            .get_or_create_active_tides_for_period(evaluation_time)
+            .await?;
+        let our_tide = active_tides
+            .iter()
+            .find(|t| t.tide_template_id == template.id)
+            .expect("Should find our tide");
+
+        let chicago = timezones::db::america::CHICAGO;
+        let start_local = our_tide.start.to_timezone(chicago);
+        let end_local = our_tide
+            .end
+            .expect("Monthly tide should have an end time")
+            .to_timezone(chicago);
+
+        assert_eq!(start_local.day(), 1);
+        assert_eq!(end_local.day(), 1);
+        assert_eq!(end_local.month(), start_local.month().next());
+        assert_eq!(end_local.hour(), 0);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_daily_tide_uses_configured_timezone() -> Result<()> {
+        let db_manager = create_test_db_manager().await;
+        let tide_service = TideService::new_with_manager(db_manager);
+        tide_service.device_service.set_timezone("America/Chicago").await?;
+
+        let template = TideTemplate::new(
+            MetricsType::Creating,
+            TideFrequency::Daily,
+            100.0,
+            datetime!(2025-01-01 00:00 UTC),
+            None,
+        );
+        tide_service.create_template(&template).await?;
+
+        // 03:00 UTC on Tuesday is still Monday evening in Chicago
+        let evaluation_time = datetime!(2025-01-07 03:00 UTC);
+        let active_tides = tide_service
+            .get_or_create_active_tides_for_period(evaluation_time)
+            .await?;
+        let our_tide = active_tides
+            .iter()
+            .find(|t| t.tide_template_id == template.id)
+            .expect("Should find our tide");
+
+        assert_eq!(our_tide.start, datetime!(2025-01-06 06:00 UTC));
+        assert_eq!(our_tide.end, Some(datetime!(2025-01-07 06:00 UTC)));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_get_timezone_defaults_when_unset() -> Result<()> {
+        let db_manager = create_test_db_manager().await;
+        let tide_service = TideService::new_with_manager(db_manager);
+
+        let tz = tide_service.get_timezone().await?;
+        assert_eq!(tz.name(), time_helpers::resolve_timezone(None).name());
+
+        tide_service.device_service.set_timezone("Europe/Berlin").await?;
+        let tz = tide_service.get_timezone().await?;
+        assert_eq!(tz.name(), "Europe/Berlin");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_weekly_tide_respects_week_start_day() -> Result<()> {
+        let db_manager = create_test_db_manager().await;
+        let tide_service = TideService::new_with_manager(db_manager);
+        tide_service.device_service.set_timezone("UTC").await?;
+        tide_service.set_week_start_day(0, datetime!(2025-01-08 10:00 UTC)).await?;
+
+        let template = TideTemplate::new(
+            MetricsType::Creating,
+            TideFrequency::Weekly,
+            600.0,
+            datetime!(2025-01-01 00:00 UTC),
+            None,
+        );
+        tide_service.create_template(&template).await?;
+
+        // Wednesday 2025-01-08 -> week runs Sunday 2025-01-05 to Sunday 2025-01-12
+        let active_tides = tide_service
+            .get_or_create_active_tides_for_period(datetime!(2025-01-08 10:00 UTC))
+            .await?;
+        let our_tide = active_tides
+            .iter()
+            .find(|t| t.tide_template_id == template.id)
+            .expect("Should find our tide");
+
+        assert_eq!(our_tide.start, datetime!(2025-01-05 00:00 UTC));
+        assert_eq!(our_tide.end, Some(datetime!(2025-01-12 00:00 UTC)));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_set_week_start_day_migrates_open_weekly_tides() -> Result<()> {
+        let db_manager = create_test_db_manager().await;
+        let tide_service = TideService::new_with_manager(db_manager);
+        tide_service.device_service.set_timezone("UTC").await?;
+
+        let template = TideTemplate::new(
+            MetricsType::Creating,
+            TideFrequency::Weekly,
+            600.0,
+            datetime!(2025-01-01 00:00 UTC),
+            None,
+        );
+        tide_service.create_template(&template).await?;
+
+        // Monday-start week: Monday 2025-01-06 to Monday 2025-01-13
+        let evaluation_time = datetime!(2025-01-08 10:00 UTC);
+        let active_tides = tide_service
+            .get_or_create_active_tides_for_period(evaluation_time)
+            .await?;
+        let weekly_tide = active_tides
+            .iter()
+            .find(|t| t.tide_template_id == template.id)
+            .expect("Should find our tide");
+        assert_eq!(weekly_tide.start, datetime!(2025-01-06 00:00 UTC));
+        assert_eq!(weekly_tide.end, Some(datetime!(2025-01-13 00:00 UTC)));
+
+        // Switch to Sunday-start weeks mid-week
+        let migrated = tide_service.set_week_start_day(0, evaluation_time).await?;
+        assert!(migrated.iter().any(|t| t.id == weekly_tide.id));
+
+        // The open tide keeps its start and now ends at the next Sunday
+        let updated = tide_service.get_tide(&weekly_tide.id).await?.unwrap();
+        assert_eq!(updated.start, datetime!(2025-01-06 00:00 UTC));
+        assert_eq!(updated.end, Some(datetime!(2025-01-12 00:00 UTC)));
+
+        // The next tide picks up exactly where the migrated one ended
+        let active_tides = tide_service
+            .get_or_create_active_tides_for_period(datetime!(2025-01-12 10:00 UTC))
+            .await?;
+        let next_tide = active_tides
+            .iter()
+            .find(|t| t.tide_template_id == template.id)
+            .expect("Should find the next tide");
+        assert_ne!(next_tide.id, weekly_tide.id);
+        assert_eq!(next_tide.start, datetime!(2025-01-12 00:00 UTC));
+        assert_eq!(next_tide.end, Some(datetime!(2025-01-19 00:00 UTC)));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_set_week_start_day_rejects_invalid_day() -> Result<()> {
+        let db_manager = create_test_db_manager().await;
+        let tide_service = TideService::new_with_manager(db_manager);
+
+        let result = tide_service
+            .set_week_start_day(7, datetime!(2025-01-08 10:00 UTC))
+            .await;
+        assert!(matches!(result, Err(TideServiceError::InvalidOperation { .. })));
+        assert_eq!(tide_service.get_week_start_day().await?, Weekday::Monday);
+
+        Ok(())
+    }
+
+    async fn insert_tagged_activity(
+        pool: &sqlx::Pool<sqlx::Sqlite>,
+        id: i64,
+        tag_id: &str,
+        start: OffsetDateTime,
+        end: OffsetDateTime,
+    ) {
+        sqlx::query(
+            "INSERT INTO activity_state (id, state, app_switches, start_time, end_time, created_at)
+             VALUES (?1, 'ACTIVE', 0, ?2, ?3, ?3)",
+        )
+        .bind(id)
+        .bind(start)
+        .bind(end)
+        .execute(pool)
+        .await
+        .unwrap();
+
+        sqlx::query("INSERT INTO activity_state_tag (activity_state_id, tag_id) VALUES (?1, ?2)")
+            .bind(id.to_string())
+            .bind(tag_id)
+            .execute(pool)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_backfill_missed_tides_creates_and_scores_past_periods() -> Result<()> {
+        let db_manager = crate::test_helpers::create_test_db_manager().await;
+        let tide_service = TideService::new_with_manager(db_manager.clone());
+        let metrics = TideMetrics::new(&db_manager, &db_manager);
+        tide_service.device_service.set_timezone("UTC").await?;
+
+        sqlx::query("INSERT INTO tag (id, name, tag_type) VALUES ('creating-tag', 'creating', 'activity')")
+            .execute(&db_manager.pool)
+            .await
+            .unwrap();
+
+        // Weekdays only, starting Friday 2025-01-03
+        let template = TideTemplate::new(
+            MetricsType::Creating,
+            TideFrequency::Daily,
+            60.0,
+            datetime!(2025-01-03 10:00 UTC),
+            Some("1,2,3,4,5".to_string()),
+        );
+        tide_service.create_template(&template).await?;
+
+        // 90 minutes of creating on Tuesday 2025-01-07
+        insert_tagged_activity(
+            &db_manager.pool,
+            1,
+            "creating-tag",
+            datetime!(2025-01-07 09:00 UTC),
+            datetime!(2025-01-07 10:30 UTC),
+        )
+        .await;
+
+        // App comes back on Thursday 2025-01-09
+        let evaluation_time = datetime!(2025-01-09 12:00 UTC);
+        let backfilled = tide_service
+            .backfill_missed_tides(&metrics, evaluation_time)
+            .await?;
+
+        let mut starts: Vec<OffsetDateTime> = backfilled
+            .iter()
+            .filter(|t| t.tide_template_id == template.id)
+            .map(|t| t.start)
+            .collect();
+        starts.sort();
+        // Fri, Mon, Tue, Wed - weekend skipped, current day left for the active tide pass
+        assert_eq!(
+            starts,
+            vec![
+                datetime!(2025-01-03 00:00 UTC),
+                datetime!(2025-01-06 00:00 UTC),
+                datetime!(2025-01-07 00:00 UTC),
+                datetime!(2025-01-08 00:00 UTC),
+            ]
+        );
+
+        let tuesday = backfilled
+            .iter()
+            .find(|t| t.start == datetime!(2025-01-07 00:00 UTC))
+            .unwrap();
+        assert!((tuesday.actual_amount - 90.0).abs() < 0.01);
+        assert_eq!(tuesday.completed_at, Some(datetime!(2025-01-08 00:00 UTC)));
+
+        let monday = tide_service
+            .get_tides_by_template(&template.id)
+            .await?
+            .into_iter()
+            .find(|t| t.start == datetime!(2025-01-06 00:00 UTC))
+            .unwrap();
+        assert_eq!(monday.actual_amount, 0.0);
+        assert!(monday.completed_at.is_none());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_backfill_missed_tides_is_idempotent() -> Result<()> {
+        let db_manager = crate::test_helpers::create_test_db_manager().await;
+        let tide_service = TideService::new_with_manager(db_manager.clone());
+        let metrics = TideMetrics::new(&db_manager, &db_manager);
+        tide_service.device_service.set_timezone("UTC").await?;
+
+        let template = TideTemplate::new(
+            MetricsType::Creating,
+            TideFrequency::Daily,
+            60.0,
+            datetime!(2025-01-06 10:00 UTC),
+            None,
+        );
+        tide_service.create_template(&template).await?;
+
+        let evaluation_time = datetime!(2025-01-09 12:00 UTC);
+        let first = tide_service
+            .backfill_missed_tides(&metrics, evaluation_time)
+            .await?;
+        assert_eq!(
+            first.iter().filter(|t| t.tide_template_id == template.id).count(),
+            3
+        );
+
+        let second = tide_service
+            .backfill_missed_tides(&metrics, evaluation_time)
+            .await?;
+        assert!(second.iter().all(|t| t.tide_template_id != template.id));
+        assert_eq!(tide_service.get_tides_by_template(&template.id).await?.len(), 3);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_backfill_missed_tides_resumes_from_latest_tide() -> Result<()> {
+        let db_manager = crate::test_helpers::create_test_db_manager().await;
+        let tide_service = TideService::new_with_manager(db_manager.clone());
+        let metrics = TideMetrics::new(&db_manager, &db_manager);
+        tide_service.device_service.set_timezone("UTC").await?;
+
+        let template = TideTemplate::new(
+            MetricsType::Creating,
+            TideFrequency::Weekly,
+            600.0,
+            datetime!(2024-12-01 00:00 UTC),
+            None,
+        );
+        tide_service.create_template(&template).await?;
+
+        // Last tide the app created was the week of Monday 2024-12-30
+        tide_service
+            .create_tide_from_template(&template.id, Some(datetime!(2024-12-30 00:00 UTC)))
+            .await?;
+
+        let evaluation_time = datetime!(2025-01-22 12:00 UTC);
+        let backfilled = tide_service
+            .backfill_missed_tides(&metrics, evaluation_time)
+            .await?;
+
+        let mut starts: Vec<OffsetDateTime> = backfilled
+            .iter()
+            .filter(|t| t.tide_template_id == template.id)
+            .map(|t| t.start)
+            .collect();
+        starts.sort();
+        assert_eq!(
+            starts,
+            vec![datetime!(2025-01-06 00:00 UTC), datetime!(2025-01-13 00:00 UTC)]
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_create_template_rejects_invalid_template() -> Result<()> {
+        let db_manager = create_test_db_manager().await;
+        let tide_service = TideService::new_with_manager(db_manager);
+
+        let template = TideTemplate::new(
+            MetricsType::Creating,
+            TideFrequency::Daily,
+            120.0,
+            datetime!(2025-01-01 00:00 UTC),
+            Some("".to_string()),
+        );
+        let result = tide_service.create_template(&template).await;
+        assert!(matches!(result, Err(TideServiceError::InvalidTemplate(TideTemplateError::InvalidDaysOfWeek { .. }))));
+        assert!(tide_service.get_template(&template.id).await?.is_none());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_update_template_normalizes_and_validates() -> Result<()> {
+        let db_manager = create_test_db_manager().await;
+        let tide_service = TideService::new_with_manager(db_manager);
+
+        let mut template = tide_service
+            .get_template("default-daily-template")
+            .await?
+            .unwrap();
+
+        template.day_of_week = Some("5,1,3".to_string());
+        let updated = tide_service.update_template(&template).await?;
+        assert_eq!(updated.day_of_week, Some("1,3,5".to_string()));
+
+        template.goal_amount = -10.0;
+        let result = tide_service.update_template(&template).await;
+        assert!(matches!(result, Err(TideServiceError::InvalidTemplate(TideTemplateError::InvalidGoalAmount { .. }))));
+
+        let stored = tide_service
+            .get_template("default-daily-template")
+            .await?
+            .unwrap();
+        assert_eq!(stored.goal_amount, 180.0);
+        assert_eq!(stored.day_of_week, Some("1,3,5".to_string()));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_record_reached_milestones_fires_once_per_tide() -> Result<()> {
+        let db_manager = create_test_db_manager().await;
+        let tide_service = TideService::new_with_manager(db_manager);
+
+        let mut template = TideTemplate::new(
+            MetricsType::Creating,
+            TideFrequency::Daily,
+            100.0,
+            datetime!(2025-01-01 00:00 UTC),
+            None,
+        );
+        template.progress_milestones = Some("25,50,100".to_string());
+        tide_service.create_template(&template).await?;
+        let tide = tide_service
+            .create_tide_from_template(&template.id, Some(datetime!(2025-01-06 00:00 UTC)))
+            .await?;
+
+        let reached_at = datetime!(2025-01-06 12:00 UTC);
+        assert_eq!(tide_service.record_reached_milestones(&tide, 10.0, reached_at).await?, Vec::<u8>::new());
+        assert_eq!(tide_service.record_reached_milestones(&tide, 60.0, reached_at).await?, vec![25, 50]);
+        assert_eq!(tide_service.record_reached_milestones(&tide, 60.0, reached_at).await?, Vec::<u8>::new());
+        assert_eq!(tide_service.record_reached_milestones(&tide, 120.0, reached_at).await?, vec![100]);
+
+        // A fresh service over the same database still sees the recorded milestones
+        let tide_service = TideService::new_with_manager(tide_service._db_manager.clone());
+        assert_eq!(tide_service.record_reached_milestones(&tide, 120.0, reached_at).await?, Vec::<u8>::new());
+
+        Ok(())
+    }
+
+    fn daily_template_for_pauses() -> TideTemplate {
+        TideTemplate::new(
+            MetricsType::Creating,
+            TideFrequency::Daily,
+            60.0,
+            datetime!(2025-01-01 00:00 UTC),
+            None,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_tides_created_during_pause_are_excused() -> Result<()> {
+        let db_manager = create_test_db_manager().await;
+        let tide_service = TideService::new_with_manager(db_manager);
+        tide_service.device_service.set_timezone("UTC").await?;
+        let template = daily_template_for_pauses();
+        tide_service.create_template(&template).await?;
+
+        let (_, excused) = tide_service
+            .create_pause(
+                time::macros::date!(2025-01-06),
+                time::macros::date!(2025-01-08),
+                Some("Vacation".to_string()),
+                datetime!(2025-01-05 12:00 UTC),
+            )
+            .await?;
+        assert!(excused.is_empty());
+        assert!(tide_service.is_paused_on(time::macros::date!(2025-01-08)).await?);
+        assert!(!tide_service.is_paused_on(time::macros::date!(2025-01-09)).await?);
+
+        let during = tide_service
+            .create_missing_tides_for_period(&[], datetime!(2025-01-08 12:00 UTC))
+            .await?;
+        let tide = during.iter().find(|t| t.tide_template_id == template.id).unwrap();
+        assert!(tide.is_excused());
+        // Excused tides are already finalized, so they are never expired as missed
+        assert!(tide_service
+            .get_expired_tides(datetime!(2025-01-10 00:00 UTC))
+            .await?
+            .iter()
+            .all(|t| t.id != tide.id));
+
+        let after = tide_service
+            .create_missing_tides_for_period(&[], datetime!(2025-01-09 12:00 UTC))
+            .await?;
+        let tide = after.iter().find(|t| t.tide_template_id == template.id).unwrap();
+        assert!(tide.status.is_none());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_create_pause_excuses_running_tides_and_validates_range() -> Result<()> {
+        let db_manager = create_test_db_manager().await;
+        let tide_service = TideService::new_with_manager(db_manager);
+        tide_service.device_service.set_timezone("UTC").await?;
+        let template = daily_template_for_pauses();
+        tide_service.create_template(&template).await?;
+        let tide = tide_service
+            .create_tide_from_template(&template.id, Some(datetime!(2025-01-06 00:00 UTC)))
+            .await?;
+        assert!(tide.status.is_none());
+
+        // "I'm off from today" excuses the tide that's already running
+        let (pause, excused) = tide_service
+            .create_pause(
+                time::macros::date!(2025-01-06),
+                time::macros::date!(2025-01-06),
+                None,
+                datetime!(2025-01-06 12:00 UTC),
+            )
+            .await?;
+        assert!(excused.iter().any(|t| t.id == tide.id));
+        assert!(tide_service.get_tide(&tide.id).await?.unwrap().is_excused());
+
+        // Removing the pause leaves the excused tide alone
+        tide_service.delete_pause(&pause.id).await?;
+        assert!(tide_service.get_pauses().await?.is_empty());
+        assert!(tide_service.get_tide(&tide.id).await?.unwrap().is_excused());
+        assert!(matches!(
+            tide_service.delete_pause(&pause.id).await,
+            Err(TideServiceError::PauseNotFound { .. })
+        ));
+
+        assert!(matches!(
+            tide_service
+                .create_pause(
+                    time::macros::date!(2025-01-08),
+                    time::macros::date!(2025-01-07),
+                    None,
+                    datetime!(2025-01-06 12:00 UTC),
+                )
+                .await,
+            Err(TideServiceError::InvalidPause { .. })
+        ));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_template_skip_dates_prevent_tide_creation() -> Result<()> {
+        let db_manager = create_test_db_manager().await;
+        let tide_service = TideService::new_with_manager(db_manager);
+        tide_service.device_service.set_timezone("UTC").await?;
+        let template = daily_template_for_pauses();
+        tide_service.create_template(&template).await?;
+
+        let updated = tide_service
+            .set_template_skip_date(&template.id, time::macros::date!(2025-01-07), true)
+            .await?;
+        assert_eq!(updated.skip_dates, Some("2025-01-07".to_string()));
+
+        let skipped = tide_service
+            .create_missing_tides_for_period(&[], datetime!(2025-01-07 12:00 UTC))
+            .await?;
+        assert!(skipped.iter().all(|t| t.tide_template_id != template.id));
+
+        let next_day = tide_service
+            .create_missing_tides_for_period(&[], datetime!(2025-01-08 12:00 UTC))
+            .await?;
+        assert!(next_day.iter().any(|t| t.tide_template_id == template.id));
+
+        let updated = tide_service
+            .set_template_skip_date(&template.id, time::macros::date!(2025-01-07), false)
+            .await?;
+        assert_eq!(updated.skip_dates, None);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_backfill_honors_pauses_and_skip_dates() -> Result<()> {
+        let db_manager = crate::test_helpers::create_test_db_manager().await;
+        let tide_service = TideService::new_with_manager(db_manager.clone());
+        let metrics = TideMetrics::new(&db_manager, &db_manager);
+        tide_service.device_service.set_timezone("UTC").await?;
+
+        let mut template = TideTemplate::new(
+            MetricsType::Creating,
+            TideFrequency::Daily,
+            60.0,
+            datetime!(2025-01-06 10:00 UTC),
+            None,
+        );
+        template.skip_dates = Some("2025-01-08".to_string());
+        tide_service.create_template(&template).await?;
+        tide_service
+            .create_pause(
+                time::macros::date!(2025-01-06),
+                time::macros::date!(2025-01-07),
+                None,
+                datetime!(2025-01-06 10:00 UTC),
+            )
+            .await?;
+
+        let backfilled = tide_service
+            .backfill_missed_tides(&metrics, datetime!(2025-01-10 12:00 UTC))
+            .await?;
+        let mut statuses: Vec<(OffsetDateTime, Option<TideStatus>)> = backfilled
+            .into_iter()
+            .filter(|t| t.tide_template_id == template.id)
+            .map(|t| (t.start, t.status))
+            .collect();
+        statuses.sort_by_key(|(start, _)| *start);
+        assert_eq!(
+            statuses,
+            vec![
+                (datetime!(2025-01-06 00:00 UTC), Some(TideStatus::Excused)),
+                (datetime!(2025-01-07 00:00 UTC), Some(TideStatus::Excused)),
+                (datetime!(2025-01-09 00:00 UTC), Some(TideStatus::Missed)),
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_suggest_goal_samples_matching_periods() -> Result<()> {
+        let db_manager = crate::test_helpers::create_test_db_manager().await;
+        let tide_service = TideService::new_with_manager(db_manager.clone());
+        let metrics = TideMetrics::new(&db_manager, &db_manager);
+        tide_service.device_service.set_timezone("UTC").await?;
+
+        sqlx::query("INSERT INTO tag (id, name, tag_type) VALUES ('creating-tag', 'creating', 'activity')")
+            .execute(&db_manager.pool)
+            .await
+            .unwrap();
+
+        // Weekdays only, 180 minute goal
+        let template = TideTemplate::new(
+            MetricsType::Creating,
+            TideFrequency::Daily,
+            180.0,
+            datetime!(2025-01-01 00:00 UTC),
+            Some("1,2,3,4,5".to_string()),
+        );
+        tide_service.create_template(&template).await?;
+
+        // Minutes of creating on Mon 2025-01-06 .. Fri 2025-01-10, plus a long weekend session
+        let days = [
+            (datetime!(2025-01-06 09:00 UTC), 60),
+            (datetime!(2025-01-07 09:00 UTC), 90),
+            (datetime!(2025-01-08 09:00 UTC), 120),
+            (datetime!(2025-01-09 09:00 UTC), 100),
+            (datetime!(2025-01-10 09:00 UTC), 80),
+            (datetime!(2025-01-11 09:00 UTC), 400),
+        ];
+        for (id, (start, minutes)) in days.into_iter().enumerate() {
+            insert_tagged_activity(
+                &db_manager.pool,
+                id as i64 + 1,
+                "creating-tag",
+                start,
+                start + time::Duration::minutes(minutes),
+            )
+            .await;
+        }
+
+        let options = SuggestionOptions {
+            periods: 5,
+            ..SuggestionOptions::default()
+        };
+        // Monday 2025-01-13, the running day is excluded and the weekend is skipped
+        let suggestion = tide_service
+            .suggest_goal(&template.id, &metrics, datetime!(2025-01-13 12:00 UTC), &options)
+            .await?
+            .unwrap();
+        assert_eq!(suggestion.sample_count, 5);
+        assert_eq!(suggestion.median, 90.0);
+        assert_eq!(suggestion.percentile_amount, 100.0);
+        assert_eq!(suggestion.suggested_goal, 90.0);
+        assert!(!suggestion.ramped);
+
+        // Suggestions are never applied
+        let stored = tide_service.get_template(&template.id).await?.unwrap();
+        assert_eq!(stored.goal_amount, 180.0);
+
+        // Paused days are left out of the sample
+        tide_service
+            .create_pause(
+                time::macros::date!(2025-01-08),
+                time::macros::date!(2025-01-08),
+                None,
+                datetime!(2025-01-13 12:00 UTC),
+            )
+            .await?;
+        let suggestion = tide_service
+            .suggest_goal(&template.id, &metrics, datetime!(2025-01-13 12:00 UTC), &options)
+            .await?
+            .unwrap();
+        assert_eq!(suggestion.sample_count, 5);
+        assert_eq!(suggestion.percentile_amount, 90.0);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_goal_edits_are_versioned_and_only_reach_the_running_tide() -> Result<()> {
+        let db_manager = create_test_db_manager().await;
+        let tide_service = TideService::new_with_manager(db_manager);
+        let now = OffsetDateTime::now_utc();
+
+        let template = tide_service
+            .create_template(&TideTemplate::new(
+                MetricsType::Creating,
+                TideFrequency::Daily,
+                60.0,
+                datetime!(2025-01-01 00:00 UTC),
+                None,
+            ))
+            .await?;
+        let past_tide = tide_service
+            .create_tide_from_template(&template.id, Some(now - time::Duration::days(3)))
+            .await?;
+        tide_service.finalize_tide(&past_tide, 30.0).await?;
+        let running_tide = tide_service
+            .create_tide_from_template(&template.id, Some(now - time::Duration::hours(1)))
+            .await?;
+
+        // Milestones aren't part of the goal, so no new version
+        let mut edited = template.clone();
+        edited.progress_milestones = Some("50,100".to_string());
+        let edited = tide_service.update_template(&edited).await?;
+        assert_eq!(edited.version, 1);
+
+        let mut edited = edited.clone();
+        edited.goal_amount = 90.0;
+        let edited = tide_service.update_template(&edited).await?;
+        assert_eq!(edited.version, 2);
+
+        let versions = tide_service.get_template_versions(&template.id).await?;
+        assert_eq!(
+            versions.iter().map(|v| (v.version, v.goal_amount)).collect::<Vec<_>>(),
+            vec![(1, 60.0), (2, 90.0)]
+        );
+
+        // The running tide picks up the new goal, the finalized one keeps the goal it was scored against
+        assert_eq!(tide_service.get_tide(&running_tide.id).await?.unwrap().goal_amount, 90.0);
+        assert_eq!(tide_service.get_tide(&past_tide.id).await?.unwrap().goal_amount, 60.0);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_archive_template_keeps_tides_and_stops_new_ones() -> Result<()> {
+        let db_manager = create_test_db_manager().await;
+        let tide_service = TideService::new_with_manager(db_manager);
+        tide_service.device_service.set_timezone("UTC").await?;
+
+        let template = tide_service
+            .create_template(&TideTemplate::new(
+                MetricsType::Creating,
+                TideFrequency::Daily,
+                60.0,
+                datetime!(2025-01-01 00:00 UTC),
+                None,
+            ))
+            .await?;
+        let running_tide = tide_service
+            .create_tide_from_template(&template.id, Some(datetime!(2025-01-06 00:00 UTC)))
+            .await?;
+
+        let ended = tide_service
+            .archive_template(&template.id, datetime!(2025-01-06 12:00 UTC))
+            .await?;
+        assert_eq!(ended.len(), 1);
+        assert_eq!(ended[0].id, running_tide.id);
+
+        // The tide keeps its parent and is finalized like any other expired tide
+        let tide = tide_service.get_tide(&running_tide.id).await?.unwrap();
+        assert_eq!(tide.end, Some(datetime!(2025-01-06 12:00 UTC)));
+        assert!(tide_service.get_template(&tide.tide_template_id).await?.unwrap().is_archived());
+        assert!(tide_service
+            .get_expired_tides(datetime!(2025-01-06 12:01 UTC))
+            .await?
+            .iter()
+            .any(|t| t.id == running_tide.id));
+
+        assert!(tide_service.get_all_templates().await?.iter().all(|t| t.id != template.id));
+        assert_eq!(tide_service.get_archived_templates().await?.len(), 1);
+        let created = tide_service
+            .create_missing_tides_for_period(&[], datetime!(2025-01-07 12:00 UTC))
+            .await?;
+        assert!(created.iter().all(|t| t.tide_template_id != template.id));
+
+        assert!(matches!(
+            tide_service.update_template(&template).await,
+            Err(TideServiceError::TemplateArchived { .. })
+        ));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_template_history_fills_in_every_expected_period() -> Result<()> {
+        let db_manager = create_test_db_manager().await;
+        let tide_service = TideService::new_with_manager(db_manager);
+        tide_service.device_service.set_timezone("UTC").await?;
+
+        let mut template = TideTemplate::new(
+            MetricsType::Creating,
+            TideFrequency::Daily,
+            60.0,
+            datetime!(2025-01-06 09:00 UTC),
+            None,
+        );
+        template.skip_dates = Some("2025-01-09".to_string());
+        let template = tide_service.create_template(&template).await?;
+
+        // The goal was raised from the 7th, before any of these periods were looked at
+        let mut raised = TideTemplateVersion::from_template(&template, datetime!(2025-01-07 00:00 UTC));
+        raised.version = 2;
+        raised.goal_amount = 90.0;
+        tide_service.tide_template_version_repo.create_version(&raised).await?;
+
+        let completed = tide_service
+            .create_tide_from_template(&template.id, Some(datetime!(2025-01-06 00:00 UTC)))
+            .await?;
+        tide_service.finalize_tide(&completed, 60.0).await?;
+        tide_service
+            .create_pause(
+                time::macros::date!(2025-01-08),
+                time::macros::date!(2025-01-08),
+                None,
+                datetime!(2025-01-08 00:00 UTC),
+            )
+            .await?;
+        let running = tide_service
+            .create_tide_from_template(&template.id, Some(datetime!(2025-01-10 00:00 UTC)))
+            .await?;
+
+        // Clipped to the first tide and the evaluation time; the skipped 9th isn't expected
+        let history = tide_service
+            .get_template_history(
+                &template.id,
+                time::macros::date!(2025-01-01),
+                time::macros::date!(2025-01-31),
+                datetime!(2025-01-10 12:00 UTC),
+            )
+            .await?;
+        let summary: Vec<(OffsetDateTime, HistoryStatus, f64, f64)> = history
+            .iter()
+            .map(|entry| (entry.period_start, entry.status, entry.goal_amount, entry.percent))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (datetime!(2025-01-06 00:00 UTC), HistoryStatus::Completed, 60.0, 100.0),
+                (datetime!(2025-01-07 00:00 UTC), HistoryStatus::NoTide, 90.0, 0.0),
+                (datetime!(2025-01-08 00:00 UTC), HistoryStatus::Excused, 90.0, 0.0),
+                (datetime!(2025-01-10 00:00 UTC), HistoryStatus::InProgress, 60.0, 0.0),
+            ]
+        );
+        assert_eq!(history[0].tide_id.as_deref(), Some(completed.id.as_str()));
+        assert_eq!(history[3].tide_id.as_deref(), Some(running.id.as_str()));
+        assert_eq!(history[1].period_end, Some(datetime!(2025-01-08 00:00 UTC)));
+
+        let rollups = tide_service
+            .get_template_rollups(
+                &template.id,
+                time::macros::date!(2025-01-01),
+                time::macros::date!(2025-01-31),
+                RollupPeriod::Month,
+                datetime!(2025-01-10 12:00 UTC),
+            )
+            .await?;
+        assert_eq!(rollups.len(), 1);
+        assert_eq!(rollups[0].periods, 4);
+        assert_eq!(rollups[0].scored, 2);
+        assert_eq!(rollups[0].hit_rate, Some(0.5));
+        assert_eq!(rollups[0].average_actual, Some(30.0));
+
+        assert!(matches!(
+            tide_service
+                .get_template_history(
+                    &template.id,
+                    time::macros::date!(2025-01-31),
+                    time::macros::date!(2025-01-01),
+                    datetime!(2025-01-10 12:00 UTC),
+                )
+                .await,
+            Err(TideServiceError::InvalidDateRange { .. })
+        ));
+
+        Ok(())
+    }
 }
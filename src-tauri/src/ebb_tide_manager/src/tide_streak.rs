@@ -0,0 +1,508 @@
+use ebb_db::{
+    db::{
+        models::{
+            tide::{Tide, TideStatus},
+            tide_streak_snapshot::TideStreakSnapshot,
+            tide_template::TideTemplate,
+            tide_types::TideFrequency,
+        },
+        tide_repo::TideRepo,
+        tide_streak_snapshot_repo::TideStreakSnapshotRepo,
+        tide_template_repo::TideTemplateRepo,
+    },
+    db_manager::{self, DbManager},
+    services::device_service::DeviceService,
+};
+use serde::Serialize;
+use std::sync::Arc;
+use thiserror::Error;
+use time::OffsetDateTime;
+use time_tz::{OffsetDateTimeExt, Tz};
+
+use crate::time_helpers;
+
+#[derive(Error, Debug)]
+pub enum TideStreakError {
+    #[error("Database error: {0}")]
+    Database(#[from] Box<dyn std::error::Error + Send + Sync>),
+    #[error("Template not found: {template_id}")]
+    TemplateNotFound { template_id: String },
+}
+
+pub type Result<T> = std::result::Result<T, TideStreakError>;
+
+/// Outcome of a single tide period for streak purposes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StreakPeriodStatus {
+    /// The goal was reached
+    Hit,
+    /// The period ended without reaching the goal
+    Missed,
+    /// The tide hasn't been finalized yet, so it neither extends nor breaks the streak
+    Pending,
+    /// The period fell in a pause, so it neither extends nor breaks the streak
+    Excused,
+}
+
+/// A single period in a template's streak history
+#[derive(Debug, Clone, Serialize)]
+pub struct StreakPeriod {
+    pub tide_id: String,
+    #[serde(with = "time::serde::rfc3339")]
+    pub start: OffsetDateTime,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub end: Option<OffsetDateTime>,
+    pub goal_amount: f64,
+    pub actual_amount: f64,
+    pub status: StreakPeriodStatus,
+}
+
+/// Current and longest streak for a template
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct StreakSummary {
+    pub tide_template_id: String,
+    pub current_streak: u32,
+    pub longest_streak: u32,
+}
+
+/// TideStreak tracks consecutive hit periods per tide template
+/// Streaks are folded incrementally into a persisted snapshot so only new tides are replayed
+pub struct TideStreak {
+    tide_repo: TideRepo,
+    tide_template_repo: TideTemplateRepo,
+    snapshot_repo: TideStreakSnapshotRepo,
+    device_service: DeviceService,
+    _db_manager: Arc<DbManager>, // Keep reference to ensure connection pool stays alive
+}
+
+impl TideStreak {
+    pub async fn new() -> Result<Self> {
+        let db_manager = db_manager::DbManager::get_shared_ebb()
+            .await
+            .map_err(|e| TideStreakError::Database(Box::new(e)))?;
+
+        Ok(Self::new_with_manager(db_manager))
+    }
+
+    pub fn new_with_manager(db_manager: Arc<DbManager>) -> Self {
+        Self {
+            tide_repo: TideRepo::new(db_manager.pool.clone()),
+            tide_template_repo: TideTemplateRepo::new(db_manager.pool.clone()),
+            snapshot_repo: TideStreakSnapshotRepo::new(db_manager.pool.clone()),
+            device_service: DeviceService::new_with_pool(db_manager.pool.clone()),
+            _db_manager: db_manager,
+        }
+    }
+
+    /// Get the current and longest streak for a template
+    /// Only finalized tides are scored, so a period that hasn't been closed out yet doesn't break the
+    /// current streak
+    pub async fn get_streak(&self, template_id: &str) -> Result<StreakSummary> {
+        let template = self.get_template(template_id).await?;
+        let tides = self.get_counted_tides(&template).await?;
+
+        // Rebuild from scratch when the template's schedule changed since the snapshot was taken
+        let stored = self.snapshot_repo.get_snapshot(template_id).await?;
+        let mut snapshot = match stored {
+            Some(snapshot)
+                if snapshot.day_of_week == template.day_of_week
+                    && snapshot.recurrence_rule == template.recurrence_rule =>
+            {
+                snapshot
+            }
+            _ => TideStreakSnapshot {
+                recurrence_rule: template.recurrence_rule.clone(),
+                ..TideStreakSnapshot::new(template.id.clone(), template.day_of_week.clone())
+            },
+        };
+
+        let folded_through = snapshot.last_tide_start;
+        let mut changed = false;
+        for tide in tides
+            .iter()
+            .filter(|tide| folded_through.is_none_or(|last| tide.start > last))
+        {
+            match period_status(tide) {
+                // Stop at the unfinalized period, it gets folded in once it's been closed out
+                StreakPeriodStatus::Pending => break,
+                StreakPeriodStatus::Hit => {
+                    snapshot.current_streak += 1;
+                    snapshot.longest_streak = snapshot.longest_streak.max(snapshot.current_streak);
+                }
+                StreakPeriodStatus::Missed => snapshot.current_streak = 0,
+                StreakPeriodStatus::Excused => {}
+            }
+            snapshot.last_tide_start = Some(tide.start);
+            changed = true;
+        }
+
+        if changed {
+            self.snapshot_repo.upsert_snapshot(&snapshot).await?;
+        }
+
+        Ok(StreakSummary {
+            tide_template_id: template.id,
+            current_streak: snapshot.current_streak as u32,
+            longest_streak: snapshot.longest_streak as u32,
+        })
+    }
+
+    /// Get the hit/miss history for every counted period of a template, oldest first
+    pub async fn get_streak_history(&self, template_id: &str) -> Result<Vec<StreakPeriod>> {
+        let template = self.get_template(template_id).await?;
+        let tides = self.get_counted_tides(&template).await?;
+
+        let history = tides
+            .into_iter()
+            .map(|tide| StreakPeriod {
+                status: period_status(&tide),
+                tide_id: tide.id,
+                start: tide.start,
+                end: tide.end,
+                goal_amount: tide.goal_amount,
+                actual_amount: tide.actual_amount,
+            })
+            .collect();
+
+        Ok(history)
+    }
+
+    /// Drop the persisted snapshot so the next `get_streak` replays every tide
+    /// Call this after editing or deleting past tides for the template
+    pub async fn rebuild_streak(&self, template_id: &str) -> Result<()> {
+        self.snapshot_repo.delete_snapshot(template_id).await?;
+        Ok(())
+    }
+
+    async fn get_template(&self, template_id: &str) -> Result<TideTemplate> {
+        self.tide_template_repo
+            .get_tide_template(template_id)
+            .await?
+            .ok_or_else(|| TideStreakError::TemplateNotFound {
+                template_id: template_id.to_string(),
+            })
+    }
+
+    /// Get the template's tides that count toward its streak, oldest first
+    /// Tides for periods outside the template's recurrence rule, or for daily tides without one, on days
+    /// outside its days of week are ignored
+    async fn get_counted_tides(&self, template: &TideTemplate) -> Result<Vec<Tide>> {
+        let timezone = self.device_service.get_timezone().await?;
+        let tz = time_helpers::resolve_timezone(timezone.as_deref());
+        let allowed_days = template.get_days_of_week();
+
+        let mut tides: Vec<Tide> = self
+            .tide_repo
+            .get_tides_by_template(&template.id)
+            .await?
+            .into_iter()
+            .filter(|tide| match &template.recurrence_rule {
+                Some(rule) => time_helpers::rule_occurs_in_period(
+                    rule,
+                    template.first_tide,
+                    tide.tide_frequency,
+                    tide.start,
+                    tz,
+                ),
+                None => tide.tide_frequency != TideFrequency::Daily || is_allowed_day(tide, tz, &allowed_days),
+            })
+            .collect();
+        tides.sort_by_key(|tide| tide.start);
+
+        Ok(tides)
+    }
+}
+
+fn is_allowed_day(tide: &Tide, tz: &Tz, allowed_days: &[u8]) -> bool {
+    let weekday = tide.start.to_timezone(tz).weekday().number_days_from_sunday();
+    allowed_days.contains(&weekday)
+}
+
+/// Determine whether a tide hit its goal, missed it, is still pending, or was excused by a pause
+/// Read from the terminal status only: amounts of unfinalized tides can be stale, and limit tides
+/// that go over are finalized as missed before their period ends
+fn period_status(tide: &Tide) -> StreakPeriodStatus {
+    match tide.status {
+        Some(TideStatus::Completed) => StreakPeriodStatus::Hit,
+        Some(TideStatus::Partial | TideStatus::Missed) => StreakPeriodStatus::Missed,
+        Some(TideStatus::Excused) => StreakPeriodStatus::Excused,
+        None => StreakPeriodStatus::Pending,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ebb_db::db::models::tide_types::{GoalDirection, MetricsType};
+    use time::macros::datetime;
+
+    use crate::test_helpers::{create_test_db_manager, create_test_tide};
+
+    async fn create_daily_template(
+        db_manager: &Arc<DbManager>,
+        day_of_week: Option<String>,
+    ) -> TideTemplate {
+        let template = TideTemplate::new(
+            MetricsType::Creating,
+            TideFrequency::Daily,
+            60.0,
+            datetime!(2025-01-01 00:00 UTC),
+            day_of_week,
+        );
+        TideTemplateRepo::new(db_manager.pool.clone())
+            .create_tide_template(&template)
+            .await
+            .unwrap();
+        template
+    }
+
+    /// Insert a daily tide starting at `start`, finalized with the given progress
+    async fn insert_daily_tide(
+        db_manager: &Arc<DbManager>,
+        template: &TideTemplate,
+        start: OffsetDateTime,
+        actual_amount: f64,
+    ) -> Tide {
+        let mut tide = create_test_tide(template, start);
+        tide.actual_amount = actual_amount;
+        let status = TideStatus::from_amounts(actual_amount, tide.goal_amount);
+        if status == TideStatus::Completed {
+            tide.completed_at = tide.end;
+        }
+        tide.status = Some(status);
+        TideRepo::new(db_manager.pool.clone())
+            .create_tide(&tide)
+            .await
+            .unwrap();
+        tide
+    }
+
+    async fn create_streak(db_manager: &Arc<DbManager>) -> TideStreak {
+        let streak = TideStreak::new_with_manager(db_manager.clone());
+        streak.device_service.set_timezone("UTC").await.unwrap();
+        streak
+    }
+
+    #[tokio::test]
+    async fn test_streak_counts_consecutive_hits() -> Result<()> {
+        let db_manager = create_test_db_manager().await;
+        let streak = create_streak(&db_manager).await;
+        let template = create_daily_template(&db_manager, None).await;
+
+        // Hit, miss, hit, hit, hit
+        insert_daily_tide(&db_manager, &template, datetime!(2025-01-01 00:00 UTC), 90.0).await;
+        insert_daily_tide(&db_manager, &template, datetime!(2025-01-02 00:00 UTC), 10.0).await;
+        insert_daily_tide(&db_manager, &template, datetime!(2025-01-03 00:00 UTC), 60.0).await;
+        insert_daily_tide(&db_manager, &template, datetime!(2025-01-04 00:00 UTC), 75.0).await;
+        insert_daily_tide(&db_manager, &template, datetime!(2025-01-05 00:00 UTC), 61.0).await;
+
+        let summary = streak.get_streak(&template.id).await?;
+        assert_eq!(summary.current_streak, 3);
+        assert_eq!(summary.longest_streak, 3);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_excused_periods_do_not_break_streak() -> Result<()> {
+        let db_manager = create_test_db_manager().await;
+        let streak = create_streak(&db_manager).await;
+        let template = create_daily_template(&db_manager, None).await;
+
+        // Hit, two excused vacation days with no progress, hit
+        insert_daily_tide(&db_manager, &template, datetime!(2025-01-01 00:00 UTC), 60.0).await;
+        for start in [datetime!(2025-01-02 00:00 UTC), datetime!(2025-01-03 00:00 UTC)] {
+            let mut tide = create_test_tide(&template, start);
+            tide.status = Some(TideStatus::Excused);
+            TideRepo::new(db_manager.pool.clone()).create_tide(&tide).await.unwrap();
+        }
+        insert_daily_tide(&db_manager, &template, datetime!(2025-01-04 00:00 UTC), 60.0).await;
+
+        let summary = streak.get_streak(&template.id).await?;
+        assert_eq!(summary.current_streak, 2);
+        assert_eq!(summary.longest_streak, 2);
+
+        let history = streak.get_streak_history(&template.id).await?;
+        assert_eq!(history[1].status, StreakPeriodStatus::Excused);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_streak_skips_days_outside_days_of_week() -> Result<()> {
+        let db_manager = create_test_db_manager().await;
+        let streak = create_streak(&db_manager).await;
+        let template = create_daily_template(&db_manager, Some("1,2,3,4,5".to_string())).await;
+
+        // Thu, Fri hit; no weekend tides; Mon, Tue hit
+        insert_daily_tide(&db_manager, &template, datetime!(2025-01-02 00:00 UTC), 60.0).await;
+        insert_daily_tide(&db_manager, &template, datetime!(2025-01-03 00:00 UTC), 60.0).await;
+        insert_daily_tide(&db_manager, &template, datetime!(2025-01-06 00:00 UTC), 60.0).await;
+        insert_daily_tide(&db_manager, &template, datetime!(2025-01-07 00:00 UTC), 60.0).await;
+        // A missed Saturday tide (e.g. left over from an older schedule) is ignored
+        insert_daily_tide(&db_manager, &template, datetime!(2025-01-04 00:00 UTC), 0.0).await;
+
+        let summary = streak.get_streak(&template.id).await?;
+        assert_eq!(summary.current_streak, 4);
+        assert_eq!(summary.longest_streak, 4);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_pending_period_does_not_break_streak() -> Result<()> {
+        let db_manager = create_test_db_manager().await;
+        let streak = create_streak(&db_manager).await;
+        let template = create_daily_template(&db_manager, None).await;
+
+        insert_daily_tide(&db_manager, &template, datetime!(2025-01-01 00:00 UTC), 60.0).await;
+        insert_daily_tide(&db_manager, &template, datetime!(2025-01-02 00:00 UTC), 60.0).await;
+        // The last period has partial progress but hasn't been finalized, even if its end has passed
+        let mut pending = create_test_tide(&template, datetime!(2025-01-03 00:00 UTC));
+        pending.actual_amount = 30.0;
+        let tide_repo = TideRepo::new(db_manager.pool.clone());
+        tide_repo.create_tide(&pending).await?;
+
+        let summary = streak.get_streak(&template.id).await?;
+        assert_eq!(summary.current_streak, 2);
+
+        let history = streak.get_streak_history(&template.id).await?;
+        let statuses: Vec<StreakPeriodStatus> = history.iter().map(|p| p.status).collect();
+        assert_eq!(
+            statuses,
+            vec![
+                StreakPeriodStatus::Hit,
+                StreakPeriodStatus::Hit,
+                StreakPeriodStatus::Pending,
+            ]
+        );
+
+        // Once it's finalized short of the goal the streak resets
+        tide_repo
+            .finalize_tide(&pending.id, 30.0, TideStatus::Partial, None)
+            .await?;
+        let summary = streak.get_streak(&template.id).await?;
+        assert_eq!(summary.current_streak, 0);
+        assert_eq!(summary.longest_streak, 2);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_streak_snapshot_is_persisted_and_extended() -> Result<()> {
+        let db_manager = create_test_db_manager().await;
+        let streak = create_streak(&db_manager).await;
+        let template = create_daily_template(&db_manager, None).await;
+
+        insert_daily_tide(&db_manager, &template, datetime!(2025-01-01 00:00 UTC), 60.0).await;
+        insert_daily_tide(&db_manager, &template, datetime!(2025-01-02 00:00 UTC), 60.0).await;
+        streak.get_streak(&template.id).await?;
+
+        let snapshot = streak.snapshot_repo.get_snapshot(&template.id).await?.unwrap();
+        assert_eq!(snapshot.current_streak, 2);
+        assert_eq!(snapshot.last_tide_start, Some(datetime!(2025-01-02 00:00 UTC)));
+
+        // New tides are folded onto the snapshot
+        insert_daily_tide(&db_manager, &template, datetime!(2025-01-03 00:00 UTC), 60.0).await;
+        let summary = streak.get_streak(&template.id).await?;
+        assert_eq!(summary.current_streak, 3);
+
+        let snapshot = streak.snapshot_repo.get_snapshot(&template.id).await?.unwrap();
+        assert_eq!(snapshot.last_tide_start, Some(datetime!(2025-01-03 00:00 UTC)));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_streak_rebuilds_when_days_of_week_change() -> Result<()> {
+        let db_manager = create_test_db_manager().await;
+        let streak = create_streak(&db_manager).await;
+        let mut template = create_daily_template(&db_manager, None).await;
+
+        // Fri hit, Sat missed, Mon hit
+        insert_daily_tide(&db_manager, &template, datetime!(2025-01-03 00:00 UTC), 60.0).await;
+        insert_daily_tide(&db_manager, &template, datetime!(2025-01-04 00:00 UTC), 0.0).await;
+        insert_daily_tide(&db_manager, &template, datetime!(2025-01-06 00:00 UTC), 60.0).await;
+
+        let summary = streak.get_streak(&template.id).await?;
+        assert_eq!(summary.current_streak, 1);
+
+        // Switching to weekdays only drops the Saturday miss
+        template.day_of_week = Some("1,2,3,4,5".to_string());
+        TideTemplateRepo::new(db_manager.pool.clone())
+            .update_tide_template(&template)
+            .await?;
+
+        let summary = streak.get_streak(&template.id).await?;
+        assert_eq!(summary.current_streak, 2);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_streak_follows_recurrence_rule() -> Result<()> {
+        let db_manager = create_test_db_manager().await;
+        let streak = create_streak(&db_manager).await;
+        let mut template = create_daily_template(&db_manager, None).await;
+
+        // Wed hit, Thu missed, Fri hit
+        insert_daily_tide(&db_manager, &template, datetime!(2025-01-01 00:00 UTC), 60.0).await;
+        insert_daily_tide(&db_manager, &template, datetime!(2025-01-02 00:00 UTC), 0.0).await;
+        insert_daily_tide(&db_manager, &template, datetime!(2025-01-03 00:00 UTC), 60.0).await;
+
+        let summary = streak.get_streak(&template.id).await?;
+        assert_eq!(summary.current_streak, 1);
+
+        // Every other day from the first tide drops the Thursday miss
+        template.recurrence_rule = Some("RRULE:FREQ=DAILY;INTERVAL=2".parse().unwrap());
+        TideTemplateRepo::new(db_manager.pool.clone())
+            .update_tide_template(&template)
+            .await?;
+
+        let summary = streak.get_streak(&template.id).await?;
+        assert_eq!(summary.current_streak, 2);
+        let snapshot = streak.snapshot_repo.get_snapshot(&template.id).await?.unwrap();
+        assert_eq!(snapshot.recurrence_rule, template.recurrence_rule);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_get_streak_unknown_template() -> Result<()> {
+        let db_manager = create_test_db_manager().await;
+        let streak = create_streak(&db_manager).await;
+
+        let result = streak.get_streak("missing-template").await;
+        assert!(matches!(result, Err(TideStreakError::TemplateNotFound { .. })));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_period_status_reads_terminal_status() {
+        let mut template = TideTemplate::new(
+            MetricsType::Consuming,
+            TideFrequency::Daily,
+            60.0,
+            datetime!(2025-01-01 00:00 UTC),
+            None,
+        );
+        template.goal_direction = GoalDirection::Limit;
+        let mut tide = create_test_tide(&template, datetime!(2025-01-06 00:00 UTC));
+
+        // Amounts of unfinalized tides may be stale, so they're never scored
+        tide.actual_amount = 61.0;
+        assert_eq!(period_status(&tide), StreakPeriodStatus::Pending);
+
+        tide.status = Some(TideStatus::Missed);
+        assert_eq!(period_status(&tide), StreakPeriodStatus::Missed);
+
+        tide.actual_amount = 30.0;
+        tide.status = Some(TideStatus::Completed);
+        assert_eq!(period_status(&tide), StreakPeriodStatus::Hit);
+
+        tide.status = Some(TideStatus::Partial);
+        assert_eq!(period_status(&tide), StreakPeriodStatus::Missed);
+    }
+}
@@ -0,0 +1,214 @@
+use ebb_db::db::models::{
+    tide::TideStatus,
+    tide_template::TideTemplate,
+    tide_types::{GoalDirection, MetricsType},
+};
+use serde::Serialize;
+
+/// How goal suggestions are computed from a template's history
+#[derive(Debug, Clone, PartialEq)]
+pub struct SuggestionOptions {
+    /// Number of past matching periods to sample
+    pub periods: usize,
+    /// Fewer sampled periods than this gives no suggestion
+    pub min_periods: usize,
+    /// Percentile (0.0-1.0) reported alongside the median as a stretch goal
+    pub percentile: f64,
+    /// Share of sampled periods (0.0-1.0) that must hit the current goal before it is ramped
+    pub ramp_hit_rate: f64,
+    /// Percent to raise (or for limits, lower) a consistently hit goal by; None disables ramping
+    pub ramp_percent: Option<f64>,
+}
+
+impl Default for SuggestionOptions {
+    fn default() -> Self {
+        Self {
+            periods: 8,
+            min_periods: 3,
+            percentile: 0.75,
+            ramp_hit_rate: 0.8,
+            ramp_percent: Some(5.0),
+        }
+    }
+}
+
+/// A suggested goal for a template, returned to the caller rather than applied
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GoalSuggestion {
+    pub tide_template_id: String,
+    pub current_goal: f64,
+    pub suggested_goal: f64,
+    /// Median amount over the sampled periods
+    pub median: f64,
+    /// Amount at `percentile` over the sampled periods
+    pub percentile_amount: f64,
+    pub percentile: f64,
+    pub sample_count: usize,
+    /// Share of sampled periods that hit the current goal
+    pub hit_rate: f64,
+    /// Whether the suggestion ramps the current goal instead of following the median
+    pub ramped: bool,
+}
+
+/// Value at percentile `p` (0.0-1.0) of sorted samples, interpolating between closest ranks
+pub fn percentile(sorted: &[f64], p: f64) -> Option<f64> {
+    if sorted.is_empty() {
+        return None;
+    }
+    let rank = p.clamp(0.0, 1.0) * (sorted.len() - 1) as f64;
+    let lower = rank.floor() as usize;
+    let upper = rank.ceil() as usize;
+    Some(sorted[lower] + (sorted[upper] - sorted[lower]) * (rank - lower as f64))
+}
+
+/// Goals are suggested in steps the UI can show: 5 minutes for time metrics, whole units otherwise
+fn goal_step(metrics_type: MetricsType) -> f64 {
+    match metrics_type {
+        MetricsType::AppSwitchRate | MetricsType::FlowSessionCount => 1.0,
+        _ => 5.0,
+    }
+}
+
+fn round_to(amount: f64, step: f64) -> f64 {
+    ((amount / step).round() * step).max(step)
+}
+
+/// Suggest a goal for a template from the amounts of its past periods
+/// Follows the median, so the goal is hit about half the time. When the current goal is already
+/// hit in at least `ramp_hit_rate` of the periods it is ramped by `ramp_percent` instead
+/// (up for minimums, down for limits). Returns None with fewer than `min_periods` samples.
+pub fn suggest_from_samples(
+    template: &TideTemplate,
+    samples: &[f64],
+    options: &SuggestionOptions,
+) -> Option<GoalSuggestion> {
+    if samples.is_empty() || samples.len() < options.min_periods {
+        return None;
+    }
+
+    let mut sorted = samples.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    let median = percentile(&sorted, 0.5)?;
+    let percentile_amount = percentile(&sorted, options.percentile)?;
+
+    let hits = samples
+        .iter()
+        .filter(|&&amount| {
+            TideStatus::for_direction(template.goal_direction, amount, template.goal_amount)
+                == TideStatus::Completed
+        })
+        .count();
+    let hit_rate = hits as f64 / samples.len() as f64;
+
+    let ramp = options
+        .ramp_percent
+        .filter(|_| hit_rate >= options.ramp_hit_rate);
+    let (suggested_goal, ramped) = match ramp {
+        Some(ramp_percent) => {
+            let factor = match template.goal_direction {
+                GoalDirection::Minimum => 1.0 + ramp_percent / 100.0,
+                GoalDirection::Limit => 1.0 - ramp_percent / 100.0,
+            };
+            (round_to(template.goal_amount * factor, 1.0), true)
+        }
+        None => (round_to(median, goal_step(template.metrics_type)), false),
+    };
+
+    Some(GoalSuggestion {
+        tide_template_id: template.id.clone(),
+        current_goal: template.goal_amount,
+        suggested_goal,
+        median,
+        percentile_amount,
+        percentile: options.percentile,
+        sample_count: samples.len(),
+        hit_rate,
+        ramped,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ebb_db::db::models::tide_types::TideFrequency;
+    use time::macros::datetime;
+
+    fn template(goal_amount: f64, goal_direction: GoalDirection) -> TideTemplate {
+        let mut template = TideTemplate::new(
+            MetricsType::Creating,
+            TideFrequency::Daily,
+            goal_amount,
+            datetime!(2025-01-01 00:00 UTC),
+            None,
+        );
+        template.goal_direction = goal_direction;
+        template
+    }
+
+    #[test]
+    fn test_percentile_interpolates() {
+        let sorted = [10.0, 20.0, 30.0, 40.0];
+        assert_eq!(percentile(&sorted, 0.0), Some(10.0));
+        assert_eq!(percentile(&sorted, 0.5), Some(25.0));
+        assert_eq!(percentile(&sorted, 1.0), Some(40.0));
+        assert_eq!(percentile(&[7.0], 0.75), Some(7.0));
+        assert_eq!(percentile(&[], 0.5), None);
+    }
+
+    #[test]
+    fn test_suggestion_follows_median_when_goal_is_missed() {
+        let template = template(180.0, GoalDirection::Minimum);
+        let samples = [95.0, 120.0, 0.0, 143.0, 110.0];
+
+        let suggestion = suggest_from_samples(&template, &samples, &SuggestionOptions::default()).unwrap();
+        assert_eq!(suggestion.median, 110.0);
+        assert_eq!(suggestion.percentile_amount, 120.0);
+        assert_eq!(suggestion.suggested_goal, 110.0);
+        assert_eq!(suggestion.hit_rate, 0.0);
+        assert!(!suggestion.ramped);
+        assert_eq!(suggestion.sample_count, 5);
+    }
+
+    #[test]
+    fn test_suggestion_ramps_consistently_hit_goals() {
+        let samples = [130.0, 125.0, 140.0, 90.0, 150.0];
+
+        let minimum = suggest_from_samples(
+            &template(120.0, GoalDirection::Minimum),
+            &samples,
+            &SuggestionOptions::default(),
+        )
+        .unwrap();
+        assert!(minimum.ramped);
+        assert_eq!(minimum.hit_rate, 0.8);
+        assert_eq!(minimum.suggested_goal, 126.0);
+
+        let limit = suggest_from_samples(
+            &template(160.0, GoalDirection::Limit),
+            &samples,
+            &SuggestionOptions::default(),
+        )
+        .unwrap();
+        assert!(limit.ramped);
+        assert_eq!(limit.suggested_goal, 152.0);
+
+        let no_ramp = SuggestionOptions {
+            ramp_percent: None,
+            ..SuggestionOptions::default()
+        };
+        let suggestion = suggest_from_samples(&template(120.0, GoalDirection::Minimum), &samples, &no_ramp).unwrap();
+        assert!(!suggestion.ramped);
+        assert_eq!(suggestion.suggested_goal, 130.0);
+    }
+
+    #[test]
+    fn test_suggestion_needs_enough_history() {
+        let template = template(60.0, GoalDirection::Minimum);
+        assert!(suggest_from_samples(&template, &[30.0, 40.0], &SuggestionOptions::default()).is_none());
+        assert!(suggest_from_samples(&template, &[], &SuggestionOptions { min_periods: 0, ..SuggestionOptions::default() }).is_none());
+
+        // An idle history still suggests the smallest step rather than a zero goal
+        let suggestion = suggest_from_samples(&template, &[0.0, 0.0, 0.0], &SuggestionOptions::default()).unwrap();
+        assert_eq!(suggestion.suggested_goal, 5.0);
+    }
+}
@@ -1,83 +1,169 @@
-use time::OffsetDateTime;
+use ebb_db::db::models::{recurrence_rule::RecurrenceRule, tide_types::TideFrequency};
+use time::{Date, Duration, Month, OffsetDateTime, PrimitiveDateTime, Time, UtcOffset, Weekday};
+use time_tz::{timezones, Offset, OffsetDateTimeExt, OffsetResult, PrimitiveDateTimeExt, TimeZone, Tz};
+
+/// Resolve the timezone used for tide boundaries from an IANA name (e.g. "America/Chicago")
+/// Falls back to the system timezone, then UTC, if the name is missing or unknown
+pub fn resolve_timezone(name: Option<&str>) -> &'static Tz {
+    if let Some(name) = name {
+        match timezones::get_by_name(name) {
+            Some(tz) => return tz,
+            None => log::warn!("Unknown timezone '{}', falling back to system timezone", name),
+        }
+    }
+
+    time_tz::system::get_timezone().unwrap_or_else(|e| {
+        log::warn!("Could not determine system timezone ({}), falling back to UTC", e);
+        timezones::db::UTC
+    })
+}
+
+/// Get the UTC offset of a timezone at a specific instant, so DST is applied for that date
+fn offset_at(tz: &Tz, time: OffsetDateTime) -> UtcOffset {
+    tz.get_offset_utc(&time).to_utc()
+}
+
+/// Get the local calendar date for a given time
+fn local_date(time: OffsetDateTime, tz: &Tz) -> Date {
+    time.to_timezone(tz).date()
+}
+
+/// Convert local midnight on the given date to UTC
+/// The offset is resolved for that date, so days around DST transitions are 23 or 25 hours long
+pub fn local_midnight(date: Date, tz: &Tz) -> OffsetDateTime {
+    let midnight = PrimitiveDateTime::new(date, Time::MIDNIGHT);
 
-/// Get the start of the week (Monday at 00:00:00) for a given time in the system's local timezone
+    // Guess with the offset at UTC midnight, then re-resolve at the guessed instant
+    let guess = midnight.assume_offset(offset_at(tz, midnight.assume_utc()));
+    midnight
+        .assume_offset(offset_at(tz, guess))
+        .to_offset(UtcOffset::UTC)
+}
+
+/// Convert a local wall clock time on the given date to UTC
+/// Times skipped by a DST jump are moved forward by the jump (02:30 becomes 03:30),
+/// and times repeated when clocks go back resolve to their first occurrence
+pub fn local_datetime(date: Date, time: Time, tz: &Tz) -> OffsetDateTime {
+    let local = PrimitiveDateTime::new(date, time);
+    let resolved = match local.assume_timezone(tz) {
+        OffsetResult::Some(resolved) => resolved,
+        OffsetResult::Ambiguous(a, b) => a.min(b),
+        // In the gap, so read the wall clock with the offset from before the jump
+        OffsetResult::None => local.assume_offset(offset_at(tz, local.assume_utc() - Duration::days(1))),
+    };
+    resolved.to_offset(UtcOffset::UTC)
+}
+
+/// Get the week start day on or before the given date
+fn week_start_date(date: Date, week_start: Weekday) -> Date {
+    let days_since_week_start = (date.weekday().number_days_from_sunday() + 7
+        - week_start.number_days_from_sunday())
+        % 7;
+    date - Duration::days(days_since_week_start as i64)
+}
+
+/// Convert a day number (0=Sunday, 6=Saturday, matching `TideTemplate::day_of_week`) to a Weekday
+pub fn weekday_from_number(day: u8) -> Option<Weekday> {
+    (day <= 6).then(|| Weekday::Sunday.nth_next(day))
+}
+
+/// Get the first day of the month following the given date
+fn next_month_start_date(date: Date) -> Date {
+    let (year, month) = match date.month() {
+        Month::December => (date.year() + 1, Month::January),
+        month => (date.year(), month.next()),
+    };
+    Date::from_calendar_date(year, month, 1).expect("first day of month is always valid")
+}
+
+/// Get the start of the week (`week_start` day at 00:00:00) for a given time in the given timezone
 /// Returns the result as UTC time (representing local midnight converted to UTC)
 /// This is used for weekly tide calculations
-pub fn get_week_start(time: OffsetDateTime) -> OffsetDateTime {
-    // Get system's local offset
-    let local_offset = time::UtcOffset::current_local_offset()
-        .unwrap_or(time::UtcOffset::UTC);
-
-    // Convert to local timezone
-    let local_time = time.to_offset(local_offset);
-
-    let weekday = local_time.weekday().number_days_from_sunday() as i64;
-    let days_since_monday = if weekday == 0 { 6 } else { weekday - 1 };
-
-    let start_of_week_local = local_time.replace_hour(0)
-        .unwrap()
-        .replace_minute(0)
-        .unwrap()
-        .replace_second(0)
-        .unwrap()
-        .replace_nanosecond(0)
-        .unwrap()
-        - time::Duration::days(days_since_monday);
-
-    // Convert back to UTC for storage
-    start_of_week_local.to_offset(time::UtcOffset::UTC)
+pub fn get_week_start(time: OffsetDateTime, tz: &Tz, week_start: Weekday) -> OffsetDateTime {
+    local_midnight(week_start_date(local_date(time, tz), week_start), tz)
 }
 
-/// Get the start of the month (1st day at 00:00:00) for a given time in the system's local timezone
+/// Get the start of the month (1st day at 00:00:00) for a given time in the given timezone
 /// Returns the result as UTC time (representing local midnight converted to UTC)
 /// This is used for monthly tide calculations
-pub fn get_month_start(time: OffsetDateTime) -> OffsetDateTime {
-    // Get system's local offset
-    let local_offset = time::UtcOffset::current_local_offset()
-        .unwrap_or(time::UtcOffset::UTC);
-
-    // Convert to local timezone
-    let local_time = time.to_offset(local_offset);
-
-    let start_of_month_local = local_time.replace_day(1)
-        .unwrap()
-        .replace_hour(0)
-        .unwrap()
-        .replace_minute(0)
-        .unwrap()
-        .replace_second(0)
-        .unwrap()
-        .replace_nanosecond(0)
-        .unwrap();
-
-    // Convert back to UTC for storage
-    start_of_month_local.to_offset(time::UtcOffset::UTC)
+pub fn get_month_start(time: OffsetDateTime, tz: &Tz) -> OffsetDateTime {
+    let first = local_date(time, tz)
+        .replace_day(1)
+        .expect("first day of month is always valid");
+    local_midnight(first, tz)
 }
 
-/// Get the start of the day (00:00:00) for a given time in the system's local timezone
+/// Get the start of the day (00:00:00) for a given time in the given timezone
 /// Returns the result as UTC time (representing local midnight converted to UTC)
 /// This is used for daily tide calculations
-pub fn get_day_start(time: OffsetDateTime) -> OffsetDateTime {
-    // Get system's local offset
-    let local_offset = time::UtcOffset::current_local_offset()
-        .unwrap_or(time::UtcOffset::UTC); // Fallback to UTC if local offset unavailable
-
-    // Convert to local timezone
-    let local_time = time.to_offset(local_offset);
-
-    // Get start of day in local timezone
-    let start_of_day_local = local_time
-        .replace_hour(0)
-        .unwrap()
-        .replace_minute(0)
-        .unwrap()
-        .replace_second(0)
-        .unwrap()
-        .replace_nanosecond(0)
-        .unwrap();
-
-    // Convert back to UTC for storage
-    start_of_day_local.to_offset(time::UtcOffset::UTC)
+pub fn get_day_start(time: OffsetDateTime, tz: &Tz) -> OffsetDateTime {
+    local_midnight(local_date(time, tz), tz)
+}
+
+/// Get the start of the next day (local midnight after the given time), returned as UTC
+pub fn get_next_day_start(time: OffsetDateTime, tz: &Tz) -> OffsetDateTime {
+    local_midnight(local_date(time, tz) + Duration::days(1), tz)
+}
+
+/// Get the start of the next week (the following local `week_start` day at 00:00:00), returned as UTC
+pub fn get_next_week_start(time: OffsetDateTime, tz: &Tz, week_start: Weekday) -> OffsetDateTime {
+    let next_week = week_start_date(local_date(time, tz), week_start) + Duration::days(7);
+    local_midnight(next_week, tz)
+}
+
+/// Get the start of the next month (the 1st of the following month at local 00:00:00), returned as UTC
+pub fn get_next_month_start(time: OffsetDateTime, tz: &Tz) -> OffsetDateTime {
+    local_midnight(next_month_start_date(local_date(time, tz)), tz)
+}
+
+/// Get the start of the tide period containing the given time for a tide frequency
+/// Indefinite tides start at the beginning of the current day
+pub fn get_period_start(
+    tide_frequency: TideFrequency,
+    time: OffsetDateTime,
+    tz: &Tz,
+    week_start: Weekday,
+) -> OffsetDateTime {
+    match tide_frequency {
+        TideFrequency::Daily => get_day_start(time, tz),
+        TideFrequency::Weekly => get_week_start(time, tz, week_start),
+        TideFrequency::Monthly => get_month_start(time, tz),
+        TideFrequency::Indefinite => get_day_start(time, tz),
+    }
+}
+
+/// Check whether a recurrence rule first occurring on the local date of `first_tide` occurs within
+/// the tide period starting at `period_start`; indefinite tides only start on days the rule occurs
+pub fn rule_occurs_in_period(
+    rule: &RecurrenceRule,
+    first_tide: OffsetDateTime,
+    tide_frequency: TideFrequency,
+    period_start: OffsetDateTime,
+    tz: &Tz,
+) -> bool {
+    let start = local_date(period_start, tz);
+    let end = match tide_frequency {
+        TideFrequency::Daily | TideFrequency::Indefinite => start + Duration::days(1),
+        TideFrequency::Weekly => start + Duration::days(7),
+        TideFrequency::Monthly => next_month_start_date(start),
+    };
+    rule.occurs_between(local_date(first_tide, tz), start, end)
+}
+
+/// Get the end of the tide period that starts at `start` - the next calendar boundary
+/// Returns None for indefinite tides
+pub fn get_period_end(
+    tide_frequency: TideFrequency,
+    start: OffsetDateTime,
+    tz: &Tz,
+    week_start: Weekday,
+) -> Option<OffsetDateTime> {
+    match tide_frequency {
+        TideFrequency::Daily => Some(get_next_day_start(start, tz)),
+        TideFrequency::Weekly => Some(get_next_week_start(start, tz, week_start)),
+        TideFrequency::Monthly => Some(get_next_month_start(start, tz)),
+        TideFrequency::Indefinite => None,
+    }
 }
 
 #[cfg(test)]
@@ -85,15 +171,22 @@ mod tests {
     use super::*;
     use time::macros::datetime;
 
+    fn chicago() -> &'static Tz {
+        timezones::db::america::CHICAGO
+    }
+
+    fn utc() -> &'static Tz {
+        timezones::db::UTC
+    }
+
     #[test]
     fn test_get_week_start_monday() {
         // Monday should return the same day at 00:00:00 in local time, returned as UTC
         let monday = datetime!(2025-01-06 15:30:45 UTC); // Monday afternoon
-        let week_start = get_week_start(monday);
+        let week_start = get_week_start(monday, chicago(), Weekday::Monday);
 
         assert_eq!(week_start.offset(), time::UtcOffset::UTC);
-        let local_offset = time::UtcOffset::current_local_offset().unwrap_or(time::UtcOffset::UTC);
-        let week_start_local = week_start.to_offset(local_offset);
+        let week_start_local = week_start.to_timezone(chicago());
 
         assert_eq!(week_start_local.hour(), 0);
         assert_eq!(week_start_local.minute(), 0);
@@ -106,11 +199,10 @@ mod tests {
     fn test_get_week_start_tuesday() {
         // Tuesday should return previous Monday at 00:00:00 in local time, returned as UTC
         let tuesday = datetime!(2025-01-07 10:15:30 UTC); // Tuesday morning
-        let week_start = get_week_start(tuesday);
+        let week_start = get_week_start(tuesday, chicago(), Weekday::Monday);
 
         assert_eq!(week_start.offset(), time::UtcOffset::UTC);
-        let local_offset = time::UtcOffset::current_local_offset().unwrap_or(time::UtcOffset::UTC);
-        let week_start_local = week_start.to_offset(local_offset);
+        let week_start_local = week_start.to_timezone(chicago());
 
         assert_eq!(week_start_local.hour(), 0);
         assert_eq!(week_start_local.minute(), 0);
@@ -123,11 +215,10 @@ mod tests {
     fn test_get_week_start_friday() {
         // Friday should return Monday of the same week at 00:00:00 in local time, returned as UTC
         let friday = datetime!(2025-01-03 18:45:12 UTC); // Friday evening
-        let week_start = get_week_start(friday);
+        let week_start = get_week_start(friday, chicago(), Weekday::Monday);
 
         assert_eq!(week_start.offset(), time::UtcOffset::UTC);
-        let local_offset = time::UtcOffset::current_local_offset().unwrap_or(time::UtcOffset::UTC);
-        let week_start_local = week_start.to_offset(local_offset);
+        let week_start_local = week_start.to_timezone(chicago());
 
         assert_eq!(week_start_local.hour(), 0);
         assert_eq!(week_start_local.minute(), 0);
@@ -140,11 +231,10 @@ mod tests {
     fn test_get_week_start_sunday() {
         // Sunday should return previous Monday at 00:00:00 in local time, returned as UTC
         let sunday = datetime!(2025-01-05 12:00:00 UTC); // Sunday noon
-        let week_start = get_week_start(sunday);
+        let week_start = get_week_start(sunday, chicago(), Weekday::Monday);
 
         assert_eq!(week_start.offset(), time::UtcOffset::UTC);
-        let local_offset = time::UtcOffset::current_local_offset().unwrap_or(time::UtcOffset::UTC);
-        let week_start_local = week_start.to_offset(local_offset);
+        let week_start_local = week_start.to_timezone(chicago());
 
         assert_eq!(week_start_local.hour(), 0);
         assert_eq!(week_start_local.minute(), 0);
@@ -157,11 +247,10 @@ mod tests {
     fn test_get_week_start_saturday() {
         // Saturday should return Monday of the same week at 00:00:00 in local time, returned as UTC
         let saturday = datetime!(2025-01-04 08:20:15 UTC); // Saturday morning
-        let week_start = get_week_start(saturday);
+        let week_start = get_week_start(saturday, chicago(), Weekday::Monday);
 
         assert_eq!(week_start.offset(), time::UtcOffset::UTC);
-        let local_offset = time::UtcOffset::current_local_offset().unwrap_or(time::UtcOffset::UTC);
-        let week_start_local = week_start.to_offset(local_offset);
+        let week_start_local = week_start.to_timezone(chicago());
 
         assert_eq!(week_start_local.hour(), 0);
         assert_eq!(week_start_local.minute(), 0);
@@ -174,11 +263,10 @@ mod tests {
     fn test_get_week_start_already_midnight() {
         // Test with a time already at midnight
         let wednesday_midnight = datetime!(2025-01-08 00:00:00 UTC); // Wednesday at midnight
-        let week_start = get_week_start(wednesday_midnight);
+        let week_start = get_week_start(wednesday_midnight, chicago(), Weekday::Monday);
 
         assert_eq!(week_start.offset(), time::UtcOffset::UTC);
-        let local_offset = time::UtcOffset::current_local_offset().unwrap_or(time::UtcOffset::UTC);
-        let week_start_local = week_start.to_offset(local_offset);
+        let week_start_local = week_start.to_timezone(chicago());
 
         assert_eq!(week_start_local.hour(), 0);
         assert_eq!(week_start_local.minute(), 0);
@@ -191,12 +279,11 @@ mod tests {
     fn test_get_month_start_first_day() {
         // First day of month should return the same day at 00:00:00 in local time, returned as UTC
         let first_day = datetime!(2025-01-01 15:30:45 UTC); // January 1st afternoon
-        let month_start = get_month_start(first_day);
+        let month_start = get_month_start(first_day, chicago());
 
         assert_eq!(month_start.offset(), time::UtcOffset::UTC);
-        let local_offset = time::UtcOffset::current_local_offset().unwrap_or(time::UtcOffset::UTC);
-        let month_start_local = month_start.to_offset(local_offset);
-        let first_day_local = first_day.to_offset(local_offset);
+        let month_start_local = month_start.to_timezone(chicago());
+        let first_day_local = first_day.to_timezone(chicago());
 
         assert_eq!(month_start_local.hour(), 0);
         assert_eq!(month_start_local.minute(), 0);
@@ -211,12 +298,11 @@ mod tests {
     fn test_get_month_start_middle_of_month() {
         // Middle of month should return first day at 00:00:00 in local time, returned as UTC
         let mid_month = datetime!(2025-01-15 10:25:30 UTC); // January 15th morning
-        let month_start = get_month_start(mid_month);
+        let month_start = get_month_start(mid_month, chicago());
 
         assert_eq!(month_start.offset(), time::UtcOffset::UTC);
-        let local_offset = time::UtcOffset::current_local_offset().unwrap_or(time::UtcOffset::UTC);
-        let month_start_local = month_start.to_offset(local_offset);
-        let mid_month_local = mid_month.to_offset(local_offset);
+        let month_start_local = month_start.to_timezone(chicago());
+        let mid_month_local = mid_month.to_timezone(chicago());
 
         assert_eq!(month_start_local.hour(), 0);
         assert_eq!(month_start_local.minute(), 0);
@@ -231,12 +317,11 @@ mod tests {
     fn test_get_month_start_end_of_month() {
         // End of month should return first day at 00:00:00 in local time, returned as UTC
         let end_month = datetime!(2025-01-31 23:59:59 UTC); // January 31st end of day
-        let month_start = get_month_start(end_month);
+        let month_start = get_month_start(end_month, chicago());
 
         assert_eq!(month_start.offset(), time::UtcOffset::UTC);
-        let local_offset = time::UtcOffset::current_local_offset().unwrap_or(time::UtcOffset::UTC);
-        let month_start_local = month_start.to_offset(local_offset);
-        let end_month_local = end_month.to_offset(local_offset);
+        let month_start_local = month_start.to_timezone(chicago());
+        let end_month_local = end_month.to_timezone(chicago());
 
         assert_eq!(month_start_local.hour(), 0);
         assert_eq!(month_start_local.minute(), 0);
@@ -251,12 +336,11 @@ mod tests {
     fn test_get_month_start_february() {
         // Test with February (shorter month)
         let february = datetime!(2025-02-20 14:45:12 UTC); // February 20th
-        let month_start = get_month_start(february);
+        let month_start = get_month_start(february, chicago());
 
         assert_eq!(month_start.offset(), time::UtcOffset::UTC);
-        let local_offset = time::UtcOffset::current_local_offset().unwrap_or(time::UtcOffset::UTC);
-        let month_start_local = month_start.to_offset(local_offset);
-        let february_local = february.to_offset(local_offset);
+        let month_start_local = month_start.to_timezone(chicago());
+        let february_local = february.to_timezone(chicago());
 
         assert_eq!(month_start_local.hour(), 0);
         assert_eq!(month_start_local.minute(), 0);
@@ -271,12 +355,11 @@ mod tests {
     fn test_get_month_start_december() {
         // Test with December (end of year)
         let december = datetime!(2025-12-25 08:15:30 UTC); // December 25th
-        let month_start = get_month_start(december);
+        let month_start = get_month_start(december, chicago());
 
         assert_eq!(month_start.offset(), time::UtcOffset::UTC);
-        let local_offset = time::UtcOffset::current_local_offset().unwrap_or(time::UtcOffset::UTC);
-        let month_start_local = month_start.to_offset(local_offset);
-        let december_local = december.to_offset(local_offset);
+        let month_start_local = month_start.to_timezone(chicago());
+        let december_local = december.to_timezone(chicago());
 
         assert_eq!(month_start_local.hour(), 0);
         assert_eq!(month_start_local.minute(), 0);
@@ -291,12 +374,11 @@ mod tests {
     fn test_get_month_start_already_first_midnight() {
         // Test with a time already at first of month at midnight
         let first_midnight = datetime!(2025-06-01 00:00:00 UTC); // June 1st at midnight
-        let month_start = get_month_start(first_midnight);
+        let month_start = get_month_start(first_midnight, chicago());
 
         assert_eq!(month_start.offset(), time::UtcOffset::UTC);
-        let local_offset = time::UtcOffset::current_local_offset().unwrap_or(time::UtcOffset::UTC);
-        let month_start_local = month_start.to_offset(local_offset);
-        let first_midnight_local = first_midnight.to_offset(local_offset);
+        let month_start_local = month_start.to_timezone(chicago());
+        let first_midnight_local = first_midnight.to_timezone(chicago());
 
         assert_eq!(month_start_local.hour(), 0);
         assert_eq!(month_start_local.minute(), 0);
@@ -311,15 +393,12 @@ mod tests {
     fn test_get_day_start_morning() {
         // Morning time should return same day at 00:00:00 in local time, returned as UTC
         let morning = datetime!(2025-01-15 08:30:45 UTC); // January 15th morning
-        let day_start = get_day_start(morning);
+        let day_start = get_day_start(morning, chicago());
 
         // Result should be in UTC
         assert_eq!(day_start.offset(), time::UtcOffset::UTC);
 
-        // When converted to local time, should be at midnight
-        let local_offset = time::UtcOffset::current_local_offset()
-            .unwrap_or(time::UtcOffset::UTC);
-        let day_start_local = day_start.to_offset(local_offset);
+        let day_start_local = day_start.to_timezone(chicago());
 
         assert_eq!(day_start_local.hour(), 0);
         assert_eq!(day_start_local.minute(), 0);
@@ -327,7 +406,7 @@ mod tests {
         assert_eq!(day_start_local.nanosecond(), 0);
 
         // Verify it's the same day in local timezone
-        let morning_local = morning.to_offset(local_offset);
+        let morning_local = morning.to_timezone(chicago());
         assert_eq!(day_start_local.date(), morning_local.date());
     }
 
@@ -335,15 +414,12 @@ mod tests {
     fn test_get_day_start_afternoon() {
         // Afternoon time should return same day at 00:00:00 in local time, returned as UTC
         let afternoon = datetime!(2025-01-15 14:25:12 UTC); // January 15th afternoon
-        let day_start = get_day_start(afternoon);
+        let day_start = get_day_start(afternoon, chicago());
 
         // Result should be in UTC
         assert_eq!(day_start.offset(), time::UtcOffset::UTC);
 
-        // When converted to local time, should be at midnight
-        let local_offset = time::UtcOffset::current_local_offset()
-            .unwrap_or(time::UtcOffset::UTC);
-        let day_start_local = day_start.to_offset(local_offset);
+        let day_start_local = day_start.to_timezone(chicago());
 
         assert_eq!(day_start_local.hour(), 0);
         assert_eq!(day_start_local.minute(), 0);
@@ -351,7 +427,7 @@ mod tests {
         assert_eq!(day_start_local.nanosecond(), 0);
 
         // Verify it's the same day in local timezone
-        let afternoon_local = afternoon.to_offset(local_offset);
+        let afternoon_local = afternoon.to_timezone(chicago());
         assert_eq!(day_start_local.date(), afternoon_local.date());
     }
 
@@ -359,18 +435,17 @@ mod tests {
     fn test_get_day_start_evening() {
         // Evening time should return same day at 00:00:00 in local time, returned as UTC
         let evening = datetime!(2025-01-15 21:45:30 UTC); // January 15th evening
-        let day_start = get_day_start(evening);
+        let day_start = get_day_start(evening, chicago());
 
         assert_eq!(day_start.offset(), time::UtcOffset::UTC);
-        let local_offset = time::UtcOffset::current_local_offset().unwrap_or(time::UtcOffset::UTC);
-        let day_start_local = day_start.to_offset(local_offset);
+        let day_start_local = day_start.to_timezone(chicago());
 
         assert_eq!(day_start_local.hour(), 0);
         assert_eq!(day_start_local.minute(), 0);
         assert_eq!(day_start_local.second(), 0);
         assert_eq!(day_start_local.nanosecond(), 0);
 
-        let evening_local = evening.to_offset(local_offset);
+        let evening_local = evening.to_timezone(chicago());
         assert_eq!(day_start_local.date(), evening_local.date());
     }
 
@@ -378,18 +453,17 @@ mod tests {
     fn test_get_day_start_end_of_day() {
         // End of day should return same day at 00:00:00 in local time, returned as UTC
         let end_of_day = datetime!(2025-01-15 23:59:59 UTC); // January 15th end of day
-        let day_start = get_day_start(end_of_day);
+        let day_start = get_day_start(end_of_day, chicago());
 
         assert_eq!(day_start.offset(), time::UtcOffset::UTC);
-        let local_offset = time::UtcOffset::current_local_offset().unwrap_or(time::UtcOffset::UTC);
-        let day_start_local = day_start.to_offset(local_offset);
+        let day_start_local = day_start.to_timezone(chicago());
 
         assert_eq!(day_start_local.hour(), 0);
         assert_eq!(day_start_local.minute(), 0);
         assert_eq!(day_start_local.second(), 0);
         assert_eq!(day_start_local.nanosecond(), 0);
 
-        let end_of_day_local = end_of_day.to_offset(local_offset);
+        let end_of_day_local = end_of_day.to_timezone(chicago());
         assert_eq!(day_start_local.date(), end_of_day_local.date());
     }
 
@@ -397,18 +471,17 @@ mod tests {
     fn test_get_day_start_already_midnight() {
         // Time already at midnight should return same time in local time, returned as UTC
         let midnight = datetime!(2025-01-15 00:00:00 UTC); // January 15th at midnight
-        let day_start = get_day_start(midnight);
+        let day_start = get_day_start(midnight, chicago());
 
         assert_eq!(day_start.offset(), time::UtcOffset::UTC);
-        let local_offset = time::UtcOffset::current_local_offset().unwrap_or(time::UtcOffset::UTC);
-        let day_start_local = day_start.to_offset(local_offset);
+        let day_start_local = day_start.to_timezone(chicago());
 
         assert_eq!(day_start_local.hour(), 0);
         assert_eq!(day_start_local.minute(), 0);
         assert_eq!(day_start_local.second(), 0);
         assert_eq!(day_start_local.nanosecond(), 0);
 
-        let midnight_local = midnight.to_offset(local_offset);
+        let midnight_local = midnight.to_timezone(chicago());
         assert_eq!(day_start_local.date(), midnight_local.date());
     }
 
@@ -416,18 +489,17 @@ mod tests {
     fn test_get_day_start_with_microseconds() {
         // Time with microseconds should be normalized to 00:00:00 in local time, returned as UTC
         let precise_time = datetime!(2025-01-15 12:34:56.789123 UTC); // January 15th with microseconds
-        let day_start = get_day_start(precise_time);
+        let day_start = get_day_start(precise_time, chicago());
 
         assert_eq!(day_start.offset(), time::UtcOffset::UTC);
-        let local_offset = time::UtcOffset::current_local_offset().unwrap_or(time::UtcOffset::UTC);
-        let day_start_local = day_start.to_offset(local_offset);
+        let day_start_local = day_start.to_timezone(chicago());
 
         assert_eq!(day_start_local.hour(), 0);
         assert_eq!(day_start_local.minute(), 0);
         assert_eq!(day_start_local.second(), 0);
         assert_eq!(day_start_local.nanosecond(), 0);
 
-        let precise_time_local = precise_time.to_offset(local_offset);
+        let precise_time_local = precise_time.to_timezone(chicago());
         assert_eq!(day_start_local.date(), precise_time_local.date());
     }
 
@@ -435,18 +507,229 @@ mod tests {
     fn test_get_day_start_leap_year() {
         // Test with leap year date (February 29th)
         let leap_day = datetime!(2024-02-29 16:20:10 UTC); // February 29th (leap year)
-        let day_start = get_day_start(leap_day);
+        let day_start = get_day_start(leap_day, chicago());
 
         assert_eq!(day_start.offset(), time::UtcOffset::UTC);
-        let local_offset = time::UtcOffset::current_local_offset().unwrap_or(time::UtcOffset::UTC);
-        let day_start_local = day_start.to_offset(local_offset);
+        let day_start_local = day_start.to_timezone(chicago());
 
         assert_eq!(day_start_local.hour(), 0);
         assert_eq!(day_start_local.minute(), 0);
         assert_eq!(day_start_local.second(), 0);
         assert_eq!(day_start_local.nanosecond(), 0);
 
-        let leap_day_local = leap_day.to_offset(local_offset);
+        let leap_day_local = leap_day.to_timezone(chicago());
         assert_eq!(day_start_local.date(), leap_day_local.date());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_get_next_day_start_regular_day() {
+        let afternoon = datetime!(2025-01-15 14:25:12 UTC);
+        let next_day = get_next_day_start(afternoon, utc());
+
+        assert_eq!(next_day, datetime!(2025-01-16 00:00 UTC));
+    }
+
+    #[test]
+    fn test_get_next_day_start_spring_forward_is_23_hours() {
+        // Local midnight on 2025-03-09 in Chicago is still CST
+        let day_start = get_day_start(datetime!(2025-03-09 12:00 UTC), chicago());
+        let next_day = get_next_day_start(day_start, chicago());
+
+        assert_eq!(day_start, datetime!(2025-03-09 06:00 UTC));
+        assert_eq!(next_day, datetime!(2025-03-10 05:00 UTC));
+        assert_eq!(next_day - day_start, Duration::hours(23));
+    }
+
+    #[test]
+    fn test_get_next_day_start_fall_back_is_25_hours() {
+        let day_start = get_day_start(datetime!(2025-11-02 12:00 UTC), chicago());
+        let next_day = get_next_day_start(day_start, chicago());
+
+        assert_eq!(day_start, datetime!(2025-11-02 05:00 UTC));
+        assert_eq!(next_day, datetime!(2025-11-03 06:00 UTC));
+        assert_eq!(next_day - day_start, Duration::hours(25));
+    }
+
+    #[test]
+    fn test_get_next_week_start_is_following_monday() {
+        // Wednesday -> following Monday
+        let wednesday = datetime!(2025-01-08 10:00 UTC);
+        let next_week = get_next_week_start(wednesday, utc(), Weekday::Monday);
+        assert_eq!(next_week, datetime!(2025-01-13 00:00 UTC));
+
+        // Monday at midnight -> the Monday after, not the same day
+        let monday = datetime!(2025-01-06 00:00 UTC);
+        let next_week = get_next_week_start(monday, utc(), Weekday::Monday);
+        assert_eq!(next_week, datetime!(2025-01-13 00:00 UTC));
+    }
+
+    #[test]
+    fn test_get_next_week_start_across_dst() {
+        // Week of 2025-03-03 starts in CST and the next week starts in CDT
+        let week_start = get_week_start(datetime!(2025-03-05 18:00 UTC), chicago(), Weekday::Monday);
+        let next_week = get_next_week_start(week_start, chicago(), Weekday::Monday);
+
+        assert_eq!(week_start, datetime!(2025-03-03 06:00 UTC));
+        assert_eq!(next_week, datetime!(2025-03-10 05:00 UTC));
+        assert_eq!(next_week - week_start, Duration::days(7) - Duration::hours(1));
+    }
+
+    #[test]
+    fn test_get_next_month_start_february() {
+        let february = datetime!(2025-02-01 00:00 UTC);
+        let next_month = get_next_month_start(february, utc());
+
+        assert_eq!(next_month, datetime!(2025-03-01 00:00 UTC));
+        assert_eq!(next_month - february, Duration::days(28));
+    }
+
+    #[test]
+    fn test_get_next_month_start_leap_year_february() {
+        let february = datetime!(2024-02-01 00:00 UTC);
+        let next_month = get_next_month_start(february, utc());
+
+        assert_eq!(next_month, datetime!(2024-03-01 00:00 UTC));
+        assert_eq!(next_month - february, Duration::days(29));
+    }
+
+    #[test]
+    fn test_get_next_month_start_december_rolls_year() {
+        let december = datetime!(2025-12-15 08:15:30 UTC);
+        let next_month = get_next_month_start(december, utc());
+
+        assert_eq!(next_month, datetime!(2026-01-01 00:00 UTC));
+    }
+
+    #[test]
+    fn test_get_next_month_start_thirty_one_day_month() {
+        let january = datetime!(2025-01-01 00:00 UTC);
+        let next_month = get_next_month_start(january, utc());
+
+        assert_eq!(next_month - january, Duration::days(31));
+    }
+
+    #[test]
+    fn test_get_next_month_start_across_dst() {
+        let month_start = get_month_start(datetime!(2025-03-15 12:00 UTC), chicago());
+        let next_month = get_next_month_start(month_start, chicago());
+
+        assert_eq!(month_start, datetime!(2025-03-01 06:00 UTC));
+        assert_eq!(next_month, datetime!(2025-04-01 05:00 UTC));
+    }
+
+    #[test]
+    fn test_get_period_end_matches_next_period_start() {
+        let start = get_period_start(TideFrequency::Monthly, datetime!(2025-02-10 12:00 UTC), chicago(), Weekday::Monday);
+        let end = get_period_end(TideFrequency::Monthly, start, chicago(), Weekday::Monday).unwrap();
+        assert_eq!(get_period_start(TideFrequency::Monthly, end, chicago(), Weekday::Monday), end);
+
+        let start = get_period_start(TideFrequency::Weekly, datetime!(2025-02-12 12:00 UTC), chicago(), Weekday::Monday);
+        let end = get_period_end(TideFrequency::Weekly, start, chicago(), Weekday::Monday).unwrap();
+        assert_eq!(get_period_start(TideFrequency::Weekly, end, chicago(), Weekday::Monday), end);
+
+        let start = get_period_start(TideFrequency::Daily, datetime!(2025-02-12 12:00 UTC), chicago(), Weekday::Monday);
+        let end = get_period_end(TideFrequency::Daily, start, chicago(), Weekday::Monday).unwrap();
+        assert_eq!(get_period_start(TideFrequency::Daily, end, chicago(), Weekday::Monday), end);
+    }
+
+    #[test]
+    fn test_get_period_end_indefinite() {
+        let start = datetime!(2025-02-10 00:00 UTC);
+        assert_eq!(get_period_end(TideFrequency::Indefinite, start, chicago(), Weekday::Monday), None);
+    }
+
+    #[test]
+    fn test_get_day_start_uses_offset_for_that_date() {
+        // January is CST (-06:00) and July is CDT (-05:00), regardless of the current date
+        let winter = get_day_start(datetime!(2025-01-15 12:00 UTC), chicago());
+        let summer = get_day_start(datetime!(2025-07-15 12:00 UTC), chicago());
+
+        assert_eq!(winter, datetime!(2025-01-15 06:00 UTC));
+        assert_eq!(summer, datetime!(2025-07-15 05:00 UTC));
+    }
+
+    #[test]
+    fn test_get_day_start_late_evening_belongs_to_local_day() {
+        // 03:00 UTC on Jan 16 is still the evening of Jan 15 in Chicago
+        let day_start = get_day_start(datetime!(2025-01-16 03:00 UTC), chicago());
+        assert_eq!(day_start, datetime!(2025-01-15 06:00 UTC));
+    }
+
+    #[test]
+    fn test_local_datetime_across_dst() {
+        let new_york = timezones::db::america::NEW_YORK;
+        let date = |day| Date::from_calendar_date(2025, Month::March, day).unwrap();
+
+        assert_eq!(local_datetime(date(8), Time::from_hms(9, 0, 0).unwrap(), new_york), datetime!(2025-03-08 14:00 UTC));
+        assert_eq!(local_datetime(date(10), Time::from_hms(9, 0, 0).unwrap(), new_york), datetime!(2025-03-10 13:00 UTC));
+
+        // 02:30 doesn't exist on 2025-03-09, so it moves to 03:30 EDT
+        assert_eq!(local_datetime(date(9), Time::from_hms(2, 30, 0).unwrap(), new_york), datetime!(2025-03-09 07:30 UTC));
+
+        // 01:30 happens twice on 2025-11-02, the EDT one comes first
+        let fall_back = Date::from_calendar_date(2025, Month::November, 2).unwrap();
+        assert_eq!(local_datetime(fall_back, Time::from_hms(1, 30, 0).unwrap(), new_york), datetime!(2025-11-02 05:30 UTC));
+    }
+
+    #[test]
+    fn test_resolve_timezone_by_name() {
+        let tz = resolve_timezone(Some("America/Chicago"));
+        assert_eq!(tz.name(), "America/Chicago");
+
+        let tz = resolve_timezone(Some("Europe/Berlin"));
+        assert_eq!(tz.name(), "Europe/Berlin");
+    }
+
+    #[test]
+    fn test_resolve_timezone_unknown_name_falls_back() {
+        let fallback = resolve_timezone(None);
+        let tz = resolve_timezone(Some("Not/A_Timezone"));
+        assert_eq!(tz.name(), fallback.name());
+    }
+
+    #[test]
+    fn test_get_week_start_sunday_start() {
+        // Wednesday 2025-01-08 -> Sunday 2025-01-05
+        let wednesday = datetime!(2025-01-08 10:00 UTC);
+        let week_start = get_week_start(wednesday, utc(), Weekday::Sunday);
+        assert_eq!(week_start, datetime!(2025-01-05 00:00 UTC));
+
+        // Sunday itself is the start of its own week
+        let sunday = datetime!(2025-01-05 10:00 UTC);
+        let week_start = get_week_start(sunday, utc(), Weekday::Sunday);
+        assert_eq!(week_start, datetime!(2025-01-05 00:00 UTC));
+    }
+
+    #[test]
+    fn test_get_week_start_saturday_start() {
+        // Friday 2025-01-10 -> Saturday 2025-01-04
+        let friday = datetime!(2025-01-10 10:00 UTC);
+        let week_start = get_week_start(friday, utc(), Weekday::Saturday);
+        assert_eq!(week_start, datetime!(2025-01-04 00:00 UTC));
+    }
+
+    #[test]
+    fn test_get_next_week_start_sunday_start() {
+        let wednesday = datetime!(2025-01-08 10:00 UTC);
+        let next_week = get_next_week_start(wednesday, utc(), Weekday::Sunday);
+        assert_eq!(next_week, datetime!(2025-01-12 00:00 UTC));
+    }
+
+    #[test]
+    fn test_get_period_end_weekly_respects_week_start() {
+        let start = get_period_start(TideFrequency::Weekly, datetime!(2025-01-08 10:00 UTC), chicago(), Weekday::Sunday);
+        let end = get_period_end(TideFrequency::Weekly, start, chicago(), Weekday::Sunday).unwrap();
+
+        assert_eq!(start.to_timezone(chicago()).weekday(), Weekday::Sunday);
+        assert_eq!(end.to_timezone(chicago()).weekday(), Weekday::Sunday);
+        assert_eq!(end - start, Duration::days(7));
+    }
+
+    #[test]
+    fn test_weekday_from_number() {
+        assert_eq!(weekday_from_number(0), Some(Weekday::Sunday));
+        assert_eq!(weekday_from_number(1), Some(Weekday::Monday));
+        assert_eq!(weekday_from_number(6), Some(Weekday::Saturday));
+        assert_eq!(weekday_from_number(7), None);
+    }
+}
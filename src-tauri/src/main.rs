@@ -1,8 +1,14 @@
-use ebb_db::{db_manager, migrations, services::device_service::DeviceService, shared_sql_plugin};
-use ebb_tide_manager::TideManager;
+use ebb_db::{
+    db::models::tide_types::GoalDirection, db_manager, migrations,
+    services::device_service::DeviceService, shared_sql_plugin,
+};
+use ebb_tide_manager::{
+    focus_scheduler::{FocusScheduler, ScheduledFlowStart},
+    TideEvent, TideManager,
+};
 use once_cell::sync::OnceCell;
 use std::sync::Arc;
-use tauri::Manager;
+use tauri::{Emitter, Manager};
 use tokio;
 
 mod autostart;
@@ -17,6 +23,9 @@ use autostart::{change_autostart, enable_autostart};
 // Global TideManager instance
 static TIDE_MANAGER: OnceCell<Arc<TideManager>> = OnceCell::new();
 
+// Global FocusScheduler instance
+static FOCUS_SCHEDULER: OnceCell<Arc<FocusScheduler>> = OnceCell::new();
+
 async fn initialize_device_profile() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     log::info!("Starting device profile initialization...");
 
@@ -39,12 +48,17 @@ async fn initialize_device_profile() -> Result<(), Box<dyn std::error::Error + S
     Ok(())
 }
 
-async fn initialize_tide_manager() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+async fn initialize_tide_manager(
+    app_handle: tauri::AppHandle,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     log::info!("Starting TideManager initialization...");
 
     // Create TideManager with default 60-second intervals
     let tide_manager = Arc::new(TideManager::new().await?);
 
+    // Subscribe before starting so no events from the first check are missed
+    tauri::async_runtime::spawn(forward_tide_events(app_handle, tide_manager.subscribe()));
+
     // Store in global static
     TIDE_MANAGER.set(tide_manager.clone()).map_err(|_| {
         Box::new(std::io::Error::new(
@@ -60,6 +74,116 @@ async fn initialize_tide_manager() -> Result<(), Box<dyn std::error::Error + Sen
     Ok(())
 }
 
+async fn initialize_focus_scheduler(
+    app_handle: tauri::AppHandle,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    log::info!("Starting FocusScheduler initialization...");
+
+    let focus_scheduler = Arc::new(FocusScheduler::new().await?);
+
+    // Subscribe before starting so a session due at launch isn't missed
+    tauri::async_runtime::spawn(forward_scheduled_flow_starts(
+        app_handle,
+        focus_scheduler.subscribe(),
+    ));
+
+    FOCUS_SCHEDULER.set(focus_scheduler.clone()).map_err(|_| {
+        Box::new(std::io::Error::new(
+            std::io::ErrorKind::Other,
+            "Failed to set FocusScheduler - already initialized",
+        )) as Box<dyn std::error::Error + Send + Sync>
+    })?;
+
+    focus_scheduler.start().await?;
+
+    log::info!("FocusScheduler started successfully");
+    Ok(())
+}
+
+/// Start scheduled focus sessions with "start-flow", like `notify_start_flow_with_workflow`
+async fn forward_scheduled_flow_starts(
+    app_handle: tauri::AppHandle,
+    mut receiver: tokio::sync::broadcast::Receiver<ScheduledFlowStart>,
+) {
+    loop {
+        match receiver.recv().await {
+            Ok(start) => {
+                if let Err(e) = app_handle.emit("start-flow", &start) {
+                    log::error!("Failed to emit start-flow for schedule {}: {}", start.schedule_id, e);
+                }
+            }
+            Err(tokio::sync::broadcast::error::RecvError::Lagged(skipped)) => {
+                log::warn!("Scheduled flow start forwarder lagged, skipped {} events", skipped);
+            }
+            Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+        }
+    }
+}
+
+/// Show the notification panel when a tide crosses a progress milestone or nears its limit
+fn show_tide_milestone_notification(app_handle: &tauri::AppHandle, event: &TideEvent) {
+    let (goal_direction, metrics_type, milestone, actual_amount, goal_amount) = match event {
+        TideEvent::MilestoneReached {
+            metrics_type,
+            milestone,
+            actual_amount,
+            goal_amount,
+            ..
+        } => (GoalDirection::Minimum, metrics_type, milestone, actual_amount, goal_amount),
+        TideEvent::LimitApproaching {
+            metrics_type,
+            milestone,
+            actual_amount,
+            goal_amount,
+            ..
+        } => (GoalDirection::Limit, metrics_type, milestone, actual_amount, goal_amount),
+        _ => return,
+    };
+
+    let payload = serde_json::json!({
+        "percentage": milestone,
+        "metricsType": metrics_type,
+        "goalDirection": goal_direction,
+        "actualAmount": actual_amount,
+        "goalAmount": goal_amount,
+    })
+    .to_string();
+
+    // Notification windows have to be created on the main thread
+    let handle = app_handle.clone();
+    let result = app_handle.run_on_main_thread(move || {
+        if let Err(e) =
+            notification::create_notification_window(&handle, "tide-milestone", Some(payload))
+        {
+            log::error!("Failed to show tide milestone notification: {}", e);
+        }
+    });
+    if let Err(e) = result {
+        log::error!("Failed to schedule tide milestone notification: {}", e);
+    }
+}
+
+/// Forward tide lifecycle events to the frontend as "tide-event"
+async fn forward_tide_events(
+    app_handle: tauri::AppHandle,
+    mut receiver: tokio::sync::broadcast::Receiver<TideEvent>,
+) {
+    loop {
+        match receiver.recv().await {
+            Ok(event) => {
+                if let Err(e) = app_handle.emit("tide-event", &event) {
+                    log::error!("Failed to emit tide event: {}", e);
+                }
+                show_tide_milestone_notification(&app_handle, &event);
+            }
+            Err(tokio::sync::broadcast::error::RecvError::Lagged(skipped)) => {
+                log::warn!("Tide event forwarder lagged, skipped {} events", skipped);
+            }
+            Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+        }
+    }
+}
+
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let _guard = sentry::init(("https://d23e3cf5027dc14dfe8128f4d35219f7@o4508951187554304.ingest.us.sentry.io/4508951212851200", sentry::ClientOptions {
@@ -79,22 +203,6 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Create SQL plugin with migration notifier
     let (sql_builder, mut migration_rx) = shared_sql_plugin::Builder::new_with_notifier();
 
-    // Spawn task to listen for migration completion and run initialization
-    tauri::async_runtime::spawn(async move {
-        if migration_rx.recv().await.is_ok() {
-            if let Err(e) = initialize_device_profile().await {
-                log::error!("Failed to initialize device profile: {}", e);
-            }
-
-            // Initialize TideManager after device profile is set up
-            if let Err(e) = initialize_tide_manager().await {
-                log::error!("Failed to initialize TideManager: {}", e);
-            }
-        } else {
-            log::warn!("Migration notification channel closed without receiving signal");
-        }
-    });
-
     tauri::Builder::default()
         .plugin(tauri_plugin_single_instance::init(|app, _args, _cwd| {
             // Focus the main window when a second instance is attempted
@@ -130,7 +238,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 ])
                 .build(),
         )
-        .setup(|app| {
+        .setup(move |app| {
             #[cfg(target_os = "macos")]
             app.set_activation_policy(tauri::ActivationPolicy::Regular);
             #[cfg(desktop)]
@@ -138,6 +246,28 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 .plugin(tauri_plugin_updater::Builder::new().build())?;
             enable_autostart(app);
 
+            // Spawn task to listen for migration completion and run initialization
+            // The receiver already exists, so a signal sent before setup runs is still delivered
+            let app_handle = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                if migration_rx.recv().await.is_ok() {
+                    if let Err(e) = initialize_device_profile().await {
+                        log::error!("Failed to initialize device profile: {}", e);
+                    }
+
+                    // Initialize TideManager after device profile is set up
+                    if let Err(e) = initialize_tide_manager(app_handle.clone()).await {
+                        log::error!("Failed to initialize TideManager: {}", e);
+                    }
+
+                    if let Err(e) = initialize_focus_scheduler(app_handle).await {
+                        log::error!("Failed to initialize FocusScheduler: {}", e);
+                    }
+                } else {
+                    log::warn!("Migration notification channel closed without receiving signal");
+                }
+            });
+
             Ok(())
         })
         .plugin(tauri_plugin_global_shortcut::Builder::new().build())
@@ -156,6 +286,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             commands::request_system_permissions,
             commands::start_system_monitoring,
             commands::start_blocking,
+            commands::start_workflow_blocking,
             commands::stop_blocking,
             commands::snooze_blocking,
             commands::is_monitoring_running,
@@ -173,6 +304,21 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             commands::notify_add_time_event,
             commands::notify_snooze_blocking,
             commands::notify_end_session,
+            commands::set_week_start_day,
+            commands::create_tide_template,
+            commands::update_tide_template,
+            commands::set_tide_template_milestones,
+            commands::set_tide_template_recurrence,
+            commands::archive_tide_template,
+            commands::suggest_tide_goal,
+            commands::get_tide_template_history,
+            commands::get_tide_template_rollups,
+            commands::get_tide_streak,
+            commands::get_tide_streak_history,
+            commands::get_focus_schedule_next_start,
+            commands::create_tide_pause,
+            commands::delete_tide_pause,
+            commands::set_tide_template_skip_date,
             change_autostart,
             tray_icon_gen::generate_timer_icon,
         ])
@@ -195,6 +341,11 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                             log::info!("TideManager stopped successfully");
                         }
                     }
+                    if let Some(focus_scheduler) = FOCUS_SCHEDULER.get() {
+                        if let Err(e) = focus_scheduler.stop() {
+                            log::error!("Error stopping FocusScheduler: {}", e);
+                        }
+                    }
                 }
                 _ => {}
             },
@@ -1,6 +1,6 @@
 import { SearchOption } from '@/components/AppSelector'
 import { BlockingPreferenceRepo, BlockingPreferenceDb } from '@/db/ebb/blockingPreferenceRepo'
-import { AppRepo, App } from '@/db/monitor/appRepo'
+import { AppRepo } from '@/db/monitor/appRepo'
 import { TagRepo } from '@/db/monitor/tagRepo'
 import { AppCategory } from '@/lib/app-directory/apps-types'
 import type { Tag, TagWithAppCount } from '@/db/monitor/tagRepo'
@@ -78,46 +78,6 @@ const convertToSearchOptions = async (
   return searchOptions
 }
 
-interface BlockingAppConfig {
-  external_id: string | null
-  is_browser: boolean
-}
-
-const getWorkflowBlockedApps = async (workflowId: string): Promise<BlockingAppConfig[]> => {
-  const selectedApps = await getWorkflowBlockingPreferencesAsSearchOptions(workflowId)
-
-  const directAppSelections: App[] = selectedApps
-    .filter((option): option is Extract<SearchOption, { type: 'app' }> => option.type === 'app' && !!option.app)
-    .map(option => option.app)
-
-  const categorySelections: Tag[] = selectedApps
-    .filter((option): option is Extract<SearchOption, { type: 'category' }> => option.type === 'category' && !!option.tag)
-    .map(option => option.tag)
-
-  const categoryTagIds = categorySelections.map(tag => tag.id).filter(id => !!id) as string[]
-
-  const appsFromCategories = categoryTagIds.length > 0
-    ? await AppRepo.getAppsByCategoryTags(categoryTagIds)
-    : []
-
-  const allAppsToConsider = [...directAppSelections, ...appsFromCategories]
-
-  const uniqueAppsMap = new Map<string, App>()
-  allAppsToConsider.forEach(app => {
-    if (app && app.id) {
-      uniqueAppsMap.set(app.id, app)
-    }
-  })
-  const uniqueApps = Array.from(uniqueAppsMap.values())
-
-  const blockingAppConfig = uniqueApps.map((app: App) => ({
-    external_id: app.app_external_id,
-    is_browser: app.is_browser === 1
-  }))
-
-  return blockingAppConfig
-}
-
 const getDefaultSearchOptions = async (): Promise<SearchOption[]> => {
   const defaultCategoryNames = ['social media', 'entertainment']
   const allCategoryTags = await TagRepo.getTagsByType('category')
@@ -142,7 +102,6 @@ const getDefaultSearchOptions = async (): Promise<SearchOption[]> => {
 export const BlockingPreferenceApi = {
   getWorkflowBlockingPreferencesAsSearchOptions,
   saveWorkflowBlockingPreferences,
-  getWorkflowBlockedApps,
   getDefaultSearchOptions,
 }
 
@@ -14,7 +14,7 @@ const startFlowSession = async (
   objective: string, 
   type: 'smart' | 'manual',
   workflow?: Workflow | null,
-  
+  focusScheduleId?: string,
 ): Promise<string> => {
   const inProgressFlowSession = await FlowSessionRepo.getInProgressFlowSession()
   
@@ -34,7 +34,8 @@ const startFlowSession = async (
     objective: objective || workflowToUse.name,
     self_score: 0,
     duration: workflowToUse.settings.defaultDuration ? workflowToUse.settings.defaultDuration * 60 : undefined,
-    type
+    type,
+    focus_schedule_id: focusScheduleId,
   }
   
   await FlowSessionRepo.createFlowSession(flowSession)
@@ -1,4 +1,5 @@
 import { QueryResult } from '@tauri-apps/plugin-sql'
+import { invoke } from '@tauri-apps/api/core'
 import { 
   FocusSchedule, 
   FocusScheduleRepo, 
@@ -11,6 +12,13 @@ export type FocusScheduleWithWorkflow = FocusSchedule & {
   workflow_name?: string
 }
 
+export interface ScheduledFlowStart {
+  schedule_id: string
+  workflow_id: string
+  label?: string
+  scheduled_time: string // RFC 3339
+}
+
 const createFocusSchedule = async (
   workflowId: string,
   scheduledTime: Date,
@@ -68,6 +76,11 @@ const deleteFocusSchedule = async (id: string): Promise<QueryResult> => {
   return FocusScheduleRepo.deleteFocusSchedule(id)
 }
 
+// Next start of a schedule, worked out in rust so custom rules are evaluated the same way they're started
+const getNextStart = async (id: string): Promise<ScheduledFlowStart | null> => {
+  return invoke<ScheduledFlowStart | null>('get_focus_schedule_next_start', { id })
+}
+
 const formatScheduleDisplay = (schedule: FocusSchedule): string => {
   if (!schedule.recurrence || schedule.recurrence.type === 'none') {
     const date = new Date(schedule.scheduled_time)
@@ -100,6 +113,11 @@ const formatScheduleDisplay = (schedule: FocusSchedule): string => {
     }
   }
   
+  if (schedule.recurrence.type === 'custom') {
+    const time = new Date(schedule.scheduled_time).toLocaleTimeString([], { hour: '2-digit', minute: '2-digit' })
+    return `Custom schedule at ${time}`
+  }
+  
   return 'Invalid schedule'
 }
 
@@ -110,6 +128,7 @@ export const FocusScheduleApi = {
   getFocusSchedulesWithWorkflow,
   getFocusScheduleById,
   deleteFocusSchedule,
+  getNextStart,
   formatScheduleDisplay,
 }
 
@@ -5,7 +5,6 @@ import { DateTime } from 'luxon'
 export type ScheduledSessionAction = 
   | { type: 'none' }
   | { type: 'reminder', schedule: ScheduledSessionInfo }
-  | { type: 'start', schedule: ScheduledSessionInfo }
 
 export interface ScheduledSessionInfo {
   id: string
@@ -18,7 +17,6 @@ export interface ScheduledSessionInfo {
 
 // Track which sessions we've already reminded about to prevent spam
 const remindersSent = new Set<string>()
-const startNotificationsSent = new Set<string>()
 
 // Helper function to calculate next occurrence for weekly recurring sessions
 const getNextOccurrenceForWeeklySchedule = (schedule: FocusSchedule, now: DateTime): DateTime | null => {
@@ -95,7 +93,7 @@ const getNextOccurrenceForDailySchedule = (schedule: FocusSchedule, now: DateTim
 }
 
 // Helper function to get next occurrence for any schedule
-const getNextOccurrence = (schedule: FocusSchedule, now: DateTime): DateTime | null => {
+const getNextOccurrence = async (schedule: FocusSchedule, now: DateTime): Promise<DateTime | null> => {
   if (!schedule.recurrence || schedule.recurrence.type === 'none') {
     // One-time schedule - check if it's in the future
     const scheduledTime = DateTime.fromISO(schedule.scheduled_time)
@@ -110,15 +108,27 @@ const getNextOccurrence = (schedule: FocusSchedule, now: DateTime): DateTime | n
     return getNextOccurrenceForWeeklySchedule(schedule, now)
   }
 
+  // Custom rules are evaluated in rust, which also starts these sessions
+  if (schedule.recurrence.type === 'custom') {
+    try {
+      const nextStart = await FocusScheduleApi.getNextStart(schedule.id)
+      return nextStart ? DateTime.fromISO(nextStart.scheduled_time) : null
+    } catch (error) {
+      console.error(`Failed to get next start for schedule ${schedule.id}:`, error)
+      return null
+    }
+  }
+
   return null
 }
 
-// Generate a unique key for tracking reminders and notifications
+// Generate a unique key for tracking reminders
 const generateSessionKey = (scheduleId: string, scheduledTime: DateTime): string => {
   return `${scheduleId}-${scheduledTime.toISO()}`
 }
 
-// Check if we should show a reminder or start a session
+// Check if we should show a reminder for an upcoming session
+// Due sessions are started by the focus scheduler in rust
 export const checkScheduledSessionStatus = async (): Promise<ScheduledSessionAction> => {
   try {
     const schedules = await FocusScheduleApi.getFocusSchedulesWithWorkflow()
@@ -126,34 +136,15 @@ export const checkScheduledSessionStatus = async (): Promise<ScheduledSessionAct
     
     // Check each active schedule
     for (const schedule of schedules) {
-      const nextOccurrence = getNextOccurrence(schedule, now)
+      const nextOccurrence = await getNextOccurrence(schedule, now)
       
       if (!nextOccurrence) continue
       
       const minutesUntilSession = nextOccurrence.diff(now, 'minutes').minutes
       const sessionKey = generateSessionKey(schedule.id, nextOccurrence)
       
-      // If the session should start now (within 2 minutes of scheduled time)
-      if (minutesUntilSession <= 2 && minutesUntilSession >= -2) {
-        if (!startNotificationsSent.has(sessionKey)) {
-          startNotificationsSent.add(sessionKey)
-          
-          return {
-            type: 'start',
-            schedule: {
-              id: schedule.id,
-              label: schedule.label,
-              workflowId: schedule.workflow_id,
-              workflowName: (schedule as FocusScheduleWithWorkflow).workflow_name,
-              scheduledTime: nextOccurrence,
-              originalScheduleTime: DateTime.fromISO(schedule.scheduled_time)
-            }
-          }
-        }
-      }
-      
       // If the session is in 15 minutes (14-16 minutes to allow for polling interval)
-      else if (minutesUntilSession >= 14 && minutesUntilSession <= 16) {
+      if (minutesUntilSession >= 14 && minutesUntilSession <= 16) {
         if (!remindersSent.has(sessionKey)) {
           remindersSent.add(sessionKey)
           
@@ -179,7 +170,7 @@ export const checkScheduledSessionStatus = async (): Promise<ScheduledSessionAct
   }
 }
 
-// Clean up old reminder/notification tracking (call this periodically)
+// Clean up old reminder tracking (call this periodically)
 export const cleanupOldSessionTracking = (): void => {
   const oneDayAgo = DateTime.now().minus({ days: 1 })
   
@@ -196,18 +187,6 @@ export const cleanupOldSessionTracking = (): void => {
       remindersSent.delete(sessionKey)
     }
   }
-  
-  for (const sessionKey of startNotificationsSent) {
-    const scheduledTimeStr = sessionKey.split('-').slice(1).join('-')
-    try {
-      const scheduledTime = DateTime.fromISO(scheduledTimeStr)
-      if (scheduledTime < oneDayAgo) {
-        startNotificationsSent.delete(sessionKey)
-      }
-    } catch {
-      startNotificationsSent.delete(sessionKey)
-    }
-  }
 }
 
 export const ScheduledSessionExecutionApi = {
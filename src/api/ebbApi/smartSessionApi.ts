@@ -109,13 +109,13 @@ export const SmartSessionApi = {
     
     return newSession
   },
-  startSmartSessionWithWorkflow: async (workflowId: string) => {
+  startSmartSessionWithWorkflow: async (workflowId: string, focusScheduleId?: string) => {
     const workflow = await WorkflowApi.getWorkflowById(workflowId)
     if (!workflow) {
       throw new Error(`Workflow with ID ${workflowId} not found`)
     }
     
-    const newSession = await FlowSessionApi.startFlowSession(workflow.name, 'manual', workflow)
+    const newSession = await FlowSessionApi.startFlowSession(workflow.name, 'manual', workflow, focusScheduleId)
     
     return newSession
   },
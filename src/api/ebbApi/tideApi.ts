@@ -1,10 +1,8 @@
-import { QueryResult } from '@tauri-apps/plugin-sql'
+import { invoke } from '@tauri-apps/api/core'
 import {
   Tide,
   TideTemplate,
   TideRepo,
-  TideSchema,
-  TideTemplateSchema,
   TideWithTemplate
 } from '@/db/ebb/tideRepo'
 import { GraphableTimeByHourBlock, MonitorApi } from '@/api/monitorApi/monitorApi'
@@ -12,6 +10,30 @@ import { DateTime } from 'luxon'
 
 export type { Tide, TideTemplate, TideWithTemplate }
 
+// Lifecycle events forwarded from the TideManager on the "tide-event" channel
+export const TIDE_EVENT = 'tide-event'
+
+interface TideEventBase {
+  tide_id: string
+  tide_template_id: string
+  goal_amount: number
+}
+
+export type TideEvent =
+  | (TideEventBase & { type: 'created', start: string, end: string | null })
+  | (TideEventBase & { type: 'progress_updated' | 'goal_reached' | 'limit_exceeded', actual_amount: number })
+  | (TideEventBase & {
+      type: 'milestone_reached' | 'limit_approaching'
+      metrics_type: string
+      milestone: number
+      actual_amount: number
+    })
+  | (TideEventBase & {
+      type: 'expired' | 'missed'
+      status: 'completed' | 'partial' | 'missed'
+      actual_amount: number
+    })
+
 export interface TideProgress {
   current: number
   goal: number
@@ -31,17 +53,6 @@ export interface TideOverview {
 
 // Tide Template API Functions
 
-const updateTideTemplate = async (
-  id: string,
-  updates: Partial<TideTemplateSchema>
-): Promise<QueryResult> => {
-  const updatedTemplate = {
-    ...updates,
-    updated_at: new Date().toISOString(),
-  }
-  return TideRepo.updateTideTemplate(id, updatedTemplate)
-}
-
 export interface TemplateEdit {
   id: string
   goal_amount: number
@@ -51,54 +62,112 @@ export interface TemplateEdit {
 }
 
 const updateTideTemplates = async (editedTemplates: TemplateEdit[]): Promise<void> => {
-  const updatePromises = []
-  const activeTides = await getActiveTides()
-
-  // Update all templates
-  for (const editedTemplate of editedTemplates) {
-    updatePromises.push(
-      updateTideTemplate(editedTemplate.id, {
-        goal_amount: editedTemplate.goal_amount,
-        day_of_week: editedTemplate.days_of_week.length > 0
-          ? editedTemplate.days_of_week.join(',')
-          : undefined
-      })
-    )
-
-    // Update any active tides using this template
-    const activeTidesForTemplate = activeTides.filter(tide =>
-      tide.tide_template_id === editedTemplate.id
-    )
-
-    for (const activeTide of activeTidesForTemplate) { // update any tides that are currently active
-      updatePromises.push(
-        updateTide(activeTide.id, {
-          goal_amount: editedTemplate.goal_amount
-        })
-      )
-    }
-  }
+  // Goes through the backend so the template is validated and versioned before it's written.
+  // A new goal also applies to the template's running tide; past tides keep theirs.
+  await Promise.all(editedTemplates.map(editedTemplate =>
+    invoke('update_tide_template', {
+      id: editedTemplate.id,
+      goalAmount: editedTemplate.goal_amount,
+      dayOfWeek: editedTemplate.tide_frequency === 'daily' && editedTemplate.days_of_week.length > 0
+        ? editedTemplate.days_of_week.join(',')
+        : null,
+    })
+  ))
+}
 
-  await Promise.all(updatePromises)
+// Archived templates stop creating tides but their history is kept
+const archiveTideTemplate = async (id: string): Promise<void> => {
+  await invoke('archive_tide_template', { id })
 }
 
 const getTideTemplates = async (): Promise<TideTemplate[]> => {
   return TideRepo.getAllTideTemplates()
 }
 
-// Tide API Functions
+// Suggested goal from the template's recent history, null when there isn't enough of it
+export interface GoalSuggestion {
+  tide_template_id: string
+  current_goal: number
+  suggested_goal: number
+  median: number
+  percentile_amount: number
+  percentile: number
+  sample_count: number
+  hit_rate: number
+  ramped: boolean // Raised (or for limits, lowered) from a goal that is hit consistently
+}
+
+const suggestTideGoal = async (id: string): Promise<GoalSuggestion | null> => {
+  return invoke<GoalSuggestion | null>('suggest_tide_goal', { id })
+}
 
-const updateTide = async (
+// One entry per expected period of a template, including periods without a tide
+export interface TideHistoryEntry {
+  period_start: string // ISO string
+  period_end: string | null // ISO string
+  tide_id: string | null // null when no tide was recorded for the period
+  goal_amount: number
+  goal_direction: 'minimum' | 'limit'
+  actual_amount: number
+  status: 'completed' | 'partial' | 'missed' | 'excused' | 'in_progress' | 'no_tide'
+  percent: number // Actual amount as a percent of the goal, not capped at 100
+}
+
+export interface TideHistoryRollup {
+  period_start: string // ISO string
+  period_end: string // ISO string
+  periods: number
+  scored: number // Periods counted toward hit_rate and average_actual (not excused or in progress)
+  completed: number
+  excused: number
+  hit_rate: number | null
+  average_actual: number | null
+}
+
+// Dates are local "YYYY-MM-DD" strings, both inclusive
+const getTideTemplateHistory = async (
   id: string,
-  updates: Partial<TideSchema>
-): Promise<QueryResult> => {
-  const updatedTide = {
-    ...updates,
-    updated_at: new Date().toISOString(),
-  }
-  return TideRepo.updateTide(id, updatedTide)
+  startDate: string,
+  endDate: string,
+): Promise<TideHistoryEntry[]> => {
+  return invoke<TideHistoryEntry[]>('get_tide_template_history', { id, startDate, endDate })
+}
+
+const getTideTemplateRollups = async (
+  id: string,
+  startDate: string,
+  endDate: string,
+  period: 'week' | 'month',
+): Promise<TideHistoryRollup[]> => {
+  return invoke<TideHistoryRollup[]>('get_tide_template_rollups', { id, startDate, endDate, period })
+}
+
+// Streaks only count finalized tides, so a running period never breaks them
+export interface TideStreakSummary {
+  tide_template_id: string
+  current_streak: number
+  longest_streak: number
+}
+
+export interface TideStreakPeriod {
+  tide_id: string
+  start: string // ISO string
+  end: string | null // ISO string
+  goal_amount: number
+  actual_amount: number
+  status: 'hit' | 'missed' | 'pending' | 'excused'
+}
+
+const getTideStreak = async (id: string): Promise<TideStreakSummary> => {
+  return invoke<TideStreakSummary>('get_tide_streak', { id })
+}
+
+const getTideStreakHistory = async (id: string): Promise<TideStreakPeriod[]> => {
+  return invoke<TideStreakPeriod[]>('get_tide_streak_history', { id })
 }
 
+// Tide API Functions
+
 const getActiveTides = async (): Promise<Tide[]> => {
   return TideRepo.getActiveTides()
 }
@@ -301,12 +370,16 @@ const isWithinTimeRange = (checkTime: string, startTime: string, endTime?: strin
 
 export const TideApi = {
   // Template operations
-  updateTideTemplate,
   updateTideTemplates,
   getTideTemplates,
+  archiveTideTemplate,
+  suggestTideGoal,
+  getTideTemplateHistory,
+  getTideTemplateRollups,
+  getTideStreak,
+  getTideStreakHistory,
 
   // Tide operations
-  updateTide,
   getActiveTides,
 
   // Business logic
@@ -1,5 +1,5 @@
 import { useEffect, useState, useRef, useCallback, useMemo } from 'react'
-import { CheckCircle, Shield, AlertTriangle, PartyPopper, HelpCircle, Calendar, Clock } from 'lucide-react'
+import { CheckCircle, Shield, AlertTriangle, PartyPopper, HelpCircle, Calendar } from 'lucide-react'
 import { Card } from '@/components/ui/card'
 import { Hotkey } from '@/components/ui/hotkey'
 import { cn } from '@/lib/utils/tailwind.util'
@@ -16,7 +16,7 @@ import { useShortcutKeyDetection } from '../../hooks/useShortcutKeyDetection'
 import { EbbWorker } from '../../lib/ebbWorker'
 import { AnalyticsEvent } from '../../lib/analytics'
 
-export type NotificationType = 'session-start' | 'quick-start' | 'smart-start-suggestion' | 'doomscroll-start-suggestion' | 'blocked-app' | 'blocked-app-hard' | 'session-end' | 'session-warning' | 'end-session' | 'scheduled-session-reminder' | 'scheduled-session-start'  
+export type NotificationType = 'session-start' | 'quick-start' | 'smart-start-suggestion' | 'doomscroll-start-suggestion' | 'blocked-app' | 'blocked-app-hard' | 'session-end' | 'session-warning' | 'end-session' | 'scheduled-session-reminder' | 'tide-milestone'  
 
 interface NotificationPayload {
   timeCreating?: number
@@ -27,6 +27,8 @@ interface NotificationPayload {
   workflowId?: string
   workflowName?: string
   scheduledTime?: string
+  metricsType?: string
+  goalDirection?: 'minimum' | 'limit'
   [key: string]: string | number | boolean | undefined
 }
 
@@ -80,6 +82,16 @@ const getSmartStartDescription = () => {
   return potentialDescriptions[randomIndex]
 }
 
+// Tag tides measure a set of tags rather than one named metric
+const METRICS_LABELS: Record<string, string> = {
+  tags: 'tagged time',
+  app_switch_rate: 'app switch',
+  flow_session_minutes: 'focus time',
+  flow_session_count: 'focus session',
+}
+
+const metricsLabel = (metricsType?: string) => (metricsType && METRICS_LABELS[metricsType]) ?? metricsType ?? ''
+
 const createNotificationConfigs = (payload: NotificationPayload | null): Record<NotificationType, NotificationConfig> => ({
   'session-start': {
     title: 'Session Start',
@@ -202,22 +214,24 @@ const createNotificationConfigs = (payload: NotificationPayload | null): Record<
       }
     }
   },
-  'scheduled-session-start': {
-    title: 'Scheduled Session',
-    description: () => payload?.workflowName ? `Start your ${payload.workflowName} session` : 'Start your scheduled focus session',
-    icon: Clock,
-    iconColor: 'text-primary',
-    progressColor: 'bg-primary',
-    defaultDuration: 15000,
-    buttonText: 'Start',
-    buttonAction: async () => {
-      info(`starting scheduled session with workflow ID: ${payload?.workflowId}`)
-      if (payload?.workflowId) {
-        await invoke('notify_start_flow_with_workflow', { workflowId: payload.workflowId })
-      } else {
-        await invoke('notify_start_flow')
-      }
-    }
+  'tide-milestone': payload?.goalDirection === 'limit' ? {
+    title: 'Limit Warning',
+    description: () => `${payload?.percentage ?? 0}% of your ${metricsLabel(payload?.metricsType)} limit used`,
+    icon: AlertTriangle,
+    iconColor: 'text-amber-500',
+    progressColor: 'bg-amber-500',
+    defaultDuration: 8000,
+    soundFile: 'session_warning.mp3',
+  } : {
+    title: payload?.percentage && payload.percentage >= 100 ? 'Goal Reached' : 'Tide Milestone',
+    description: () => payload?.percentage && payload.percentage >= 100
+      ? `You hit your ${metricsLabel(payload.metricsType)} goal`
+      : `${payload?.percentage ?? 0}% of your ${metricsLabel(payload?.metricsType)} goal`,
+    icon: PartyPopper,
+    iconColor: 'text-green-500',
+    progressColor: 'bg-green-500',
+    defaultDuration: 8000,
+    soundFile: 'session_end.mp3',
   }
 })
 
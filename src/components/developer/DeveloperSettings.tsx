@@ -9,7 +9,7 @@ export function DeveloperSettings() {
   const [slackStatus, setSlackStatus] = useState<SlackStatusResponse['data'] | undefined>()
   const [loading, setLoading] = useState(false)
 
-  const handleTestNotification = (type: 'smart-start-suggestion' |  'session-start' | 'session-end' | 'session-warning' | 'blocked-app' | 'blocked-app-hard' | 'scheduled-session-start' | 'scheduled-session-reminder', difficulty: 'easy' | 'medium' | 'hard') => {
+  const handleTestNotification = (type: 'smart-start-suggestion' |  'session-start' | 'session-end' | 'session-warning' | 'blocked-app' | 'blocked-app-hard' | 'scheduled-session-reminder', difficulty: 'easy' | 'medium' | 'hard') => {
     info(`show_notification ${difficulty} ${type}`)
     let payload = {}
     if(type === 'session-end') {
@@ -18,12 +18,6 @@ export function DeveloperSettings() {
         description: 'You created for 51 minutes!',
       }
     }
-    else if(type === 'scheduled-session-start') {
-      payload = {
-        workflowId: '8d3501ea-4373-405a-9982-144607d5fd33',
-        workflowName: 'Lazer Focused',
-      }
-    }
     else if(type === 'scheduled-session-reminder') {
       payload = {
         workflowId: '8d3501ea-4373-405a-9982-144607d5fd33',
@@ -164,9 +158,6 @@ export function DeveloperSettings() {
             <Button onClick={() => handleTestNotification('blocked-app-hard', 'easy')}>
             Test Blocked App Hard
             </Button>
-            <Button onClick={() => handleTestNotification('scheduled-session-start', 'easy')}> 
-            Test Scheduled Session Start
-            </Button>
             <Button onClick={() => handleTestNotification('scheduled-session-reminder', 'easy')}> 
             Test Scheduled Session Reminder
             </Button>
@@ -10,6 +10,8 @@ export interface SmartFocusSettings {
 export interface DevicePreference {
   idle_sensitivity: number
   smart_focus_settings: SmartFocusSettings
+  timezone?: string // IANA name used for tide boundaries, e.g. "America/Chicago"
+  week_start_day?: number // 0=Sunday ... 6=Saturday, defaults to Monday (1)
 }
 
 export interface DeviceProfileDb {
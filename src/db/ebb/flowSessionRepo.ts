@@ -13,6 +13,7 @@ export interface FlowSessionSchema {
   duration?: number
   workflow_id?: string
   type: 'smart' | 'manual'
+  focus_schedule_id?: string // Set when the session was started by a focus schedule
 }
 
 export type FlowSession = FlowSessionSchema & {
@@ -56,8 +57,8 @@ const getFlowSessions = async (limit = 10): Promise<FlowSession[]> => {
     FROM flow_session fs
       LEFT JOIN flow_period fp ON fs.start <= fp.start_time AND fs.end >= fp.end_time
     GROUP BY fs.id, fs.objective, fs.self_score, fs.start, fs.end
-    ORDER BY start DESC LIMIT ${limit};`
-  const flowSessions = await ebbDb.select<FlowSessionSchema[]>(query) 
+    ORDER BY start DESC LIMIT ?;`
+  const flowSessions = await ebbDb.select<FlowSessionSchema[]>(query, [limit])
 
   return flowSessions.map((flowSession) => ({
     ...flowSession,
@@ -84,8 +85,9 @@ const getFlowSessionById = async (id: string): Promise<FlowSession | undefined>
       END as flow_periods
     FROM flow_session fs 
     LEFT JOIN flow_period fp ON fs.start <= fp.start_time AND fs.end >= fp.end_time
-    WHERE fs.id = ${id}
+    WHERE fs.id = ?
     GROUP BY fs.id, fs.objective, fs.self_score, fs.start, fs.end;`,
+    [id],
   )
   return flowSession
 }
@@ -14,8 +14,9 @@ export interface FocusScheduleSchema {
 }
 
 export interface RecurrenceSettings {
-  type: 'none' | 'daily' | 'weekly'
+  type: 'none' | 'daily' | 'weekly' | 'custom'
   daysOfWeek?: number[] // [0,1,2,3,4,5,6] where 0=Sunday
+  rrule?: string // For custom schedules, e.g. "RRULE:FREQ=MONTHLY;BYDAY=1MO" for the first Monday of the month
 }
 
 export type FocusSchedule = FocusScheduleSchema & {
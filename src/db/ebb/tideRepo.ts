@@ -1,5 +1,3 @@
-import { QueryResult } from '@tauri-apps/plugin-sql'
-import { update } from '@/lib/utils/sql.util'
 import { getEbbDb } from './ebbDb'
 
 export interface TideSchema {
@@ -7,28 +5,60 @@ export interface TideSchema {
   start: string // ISO string
   end?: string // ISO string, nullable for indefinite tides
   completed_at?: string // ISO string, when the tide was actually completed
-  metrics_type: string // "creating", etc.
+  status?: 'completed' | 'partial' | 'missed' | 'excused' // Set once the tide's period has ended, or 'excused' when it started during a pause
+  metrics_type: string // "creating", etc., "tags" to measure the tags in tag_ids, "app_switch_rate", "flow_session_minutes" or "flow_session_count"
   tide_frequency: string // "daily", "weekly", "monthly", "indefinite"
-  goal_amount: number // Goal in minutes
+  goal_amount: number // Goal in the metric's unit: minutes, app switches per hour or sessions
+  goal_direction: 'minimum' | 'limit' // Copied from the template
   actual_amount: number // Current progress in minutes
   tide_template_id: string
+  tag_ids?: string // Comma-separated tag ids copied from the template for "tags" tides
   created_at: string // ISO string
   updated_at: string // ISO string
 }
 
 export interface TideTemplateSchema {
   id: string
-  metrics_type: string // "creating", etc.
+  metrics_type: string // "creating", etc., "tags" to measure the tags in tag_ids, "app_switch_rate", "flow_session_minutes" or "flow_session_count"
   tide_frequency: string // "daily", "weekly", "monthly", "indefinite"
   first_tide: string // ISO string - How far back to create tides when generating
   day_of_week?: string // For daily tides: comma-separated days "0,1,2,3,4,5,6"
-  goal_amount: number // Goal in minutes
+  recurrence_rule?: string // Periods that get a tide, e.g. "RRULE:FREQ=WEEKLY;INTERVAL=2", replaces day_of_week when set
+  goal_amount: number // Goal in the metric's unit: minutes, app switches per hour or sessions
+  goal_direction: 'minimum' | 'limit' // Whether goal_amount is a minimum to reach or a limit to stay under
+  progress_milestones?: string // Comma-separated percentages of the goal to notify at, defaults to "50,90,100"
+  tag_ids?: string // For "tags" templates: comma-separated ids of the tags whose time counts toward the goal
+  skip_dates?: string // Comma-separated local dates "2025-01-06,2025-01-07" whose periods get no tide
+  version: number // Bumped when the goal, direction, days or tags change
+  archived_at?: string // ISO string, set when the template is archived instead of deleted
   created_at: string // ISO string
   updated_at: string // ISO string
 }
 
+export interface TidePauseSchema {
+  id: string
+  start_date: string // Local date "YYYY-MM-DD", first paused day
+  end_date: string // Local date "YYYY-MM-DD", last paused day (inclusive)
+  reason?: string
+  created_at: string // ISO string
+  updated_at: string // ISO string
+}
+
+export interface TideTemplateVersionSchema {
+  tide_template_id: string
+  version: number
+  goal_amount: number
+  goal_direction: 'minimum' | 'limit'
+  day_of_week?: string
+  recurrence_rule?: string
+  tag_ids?: string
+  effective_from: string // ISO string, when this version of the goal took effect
+}
+
 export type Tide = TideSchema
 export type TideTemplate = TideTemplateSchema
+export type TidePause = TidePauseSchema
+export type TideTemplateVersion = TideTemplateVersionSchema
 
 export type TideWithTemplate = Tide & {
   template?: TideTemplate
@@ -36,14 +66,6 @@ export type TideWithTemplate = Tide & {
 
 // Tide Repository Functions
 
-const updateTide = async (
-  id: string,
-  tide: Partial<TideSchema>,
-): Promise<QueryResult> => {
-  const ebbDb = await getEbbDb()
-  return update(ebbDb, 'tide', tide, id)
-}
-
 const getActiveTides = async (): Promise<Tide[]> => {
   const ebbDb = await getEbbDb()
   const now = new Date().toISOString()
@@ -74,28 +96,39 @@ const getActiveTidesForPeriod = async (evaluationTime: string): Promise<Tide[]>
 
 // Tide Template Repository Functions
 
-const updateTideTemplate = async (
-  id: string,
-  template: Partial<TideTemplateSchema>,
-): Promise<QueryResult> => {
+const getAllTideTemplates = async (): Promise<TideTemplate[]> => {
   const ebbDb = await getEbbDb()
-  return update(ebbDb, 'tide_template', template, id)
+  return await ebbDb.select<TideTemplate[]>(
+    'SELECT * FROM tide_template WHERE archived_at IS NULL ORDER BY created_at DESC'
+  )
 }
 
-const getAllTideTemplates = async (): Promise<TideTemplate[]> => {
+const getTideTemplateVersions = async (templateId: string): Promise<TideTemplateVersion[]> => {
   const ebbDb = await getEbbDb()
-  return await ebbDb.select<TideTemplate[]>(
-    'SELECT * FROM tide_template ORDER BY created_at DESC'
+  return await ebbDb.select<TideTemplateVersion[]>(
+    'SELECT * FROM tide_template_version WHERE tide_template_id = ? ORDER BY version ASC',
+    [templateId]
+  )
+}
+
+// Tide Pause Repository Functions
+
+const getAllTidePauses = async (): Promise<TidePause[]> => {
+  const ebbDb = await getEbbDb()
+  return await ebbDb.select<TidePause[]>(
+    'SELECT * FROM tide_pause ORDER BY start_date ASC'
   )
 }
 
 export const TideRepo = {
   // Tide operations
-  updateTide,
   getActiveTides,
   getActiveTidesForPeriod,
 
   // Tide template operations
-  updateTideTemplate,
   getAllTideTemplates,
+  getTideTemplateVersions,
+
+  // Tide pause operations
+  getAllTidePauses,
 }
@@ -13,6 +13,7 @@ export interface SlackSettings {
   dndEnabled: boolean
 }
 
+// Mirrored by WorkflowSettings in ebb_db's models::workflow; keys it doesn't know are kept on save
 export interface WorkflowSettings {
   typewriterMode: boolean
   hasBreathing: boolean
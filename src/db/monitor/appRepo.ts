@@ -69,15 +69,6 @@ const getAppsByIds = async (appIds: string[]): Promise<AppDb[]> => {
   return await monitorDb.select(query, [...appIds])
 }
 
-const getAppsByCategoryTags = async (tagIds: string[]): Promise<AppDb[]> => {
-  const monitorDb = await MonitorDb.getMonitorDb()
-  const query = `
-    SELECT * FROM app a
-    LEFT JOIN app_tag at ON at.app_id = a.id
-    WHERE at.tag_id IN (${tagIds.map(() => '?').join(',')})`
-  return monitorDb.select<AppDb[]>(query, [...tagIds])
-}
-
 const createApp = async (externalId: string, isBrowser: boolean, name = ''): Promise<string> => {
   const monitorDb = await MonitorDb.getMonitorDb()
   const id = crypto.randomUUID()
@@ -160,7 +151,6 @@ export const AppRepo = {
   setAppTag,
   getApps,
   getAppsByIds,
-  getAppsByCategoryTags,
   createApp,
   getRecentlyUsedApps,
   deleteApp,
@@ -8,6 +8,7 @@ import { SmartSessionApi } from '@/api/ebbApi/smartSessionApi'
 import { useFlowTimer } from '@/lib/stores/flowTimer'
 import { FlowSessionApi } from '@/api/ebbApi/flowSessionApi'
 import { AnalyticsService } from '@/lib/analytics'
+import { usePermissionsStore } from '@/lib/stores/permissionsStore'
 
 interface BlockedApp {
   app_name: string
@@ -38,12 +39,19 @@ export const useNotificationListener = () => {
 
     const setupListeners = async () => {
 
-      unlistenStartFlow = await listen('start-flow', async (event: { payload: { workflow_id?: string } }) => {
+      unlistenStartFlow = await listen('start-flow', async (event: { payload: { workflow_id?: string, schedule_id?: string } }) => {
         EbbWorker.debounceWork(async () => {
+          // Scheduled starts come from the focus scheduler in rust, which doesn't know the license
+          const scheduleId = event.payload?.schedule_id
+          if (scheduleId && !usePermissionsStore.getState().permissions.canScheduleSessions) {
+            info(`App: not starting scheduled session ${scheduleId}, scheduling requires pro`)
+            return
+          }
+
           let session
           if (event.payload?.workflow_id) {
             console.log(`Starting session with workflow ID: ${event.payload.workflow_id}`)
-            session = await SmartSessionApi.startSmartSessionWithWorkflow(event.payload.workflow_id)
+            session = await SmartSessionApi.startSmartSessionWithWorkflow(event.payload.workflow_id, scheduleId)
           } else {
             console.log('Starting smart session (no specific workflow)')
             session = await SmartSessionApi.startSmartSession()
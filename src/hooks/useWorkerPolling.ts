@@ -77,15 +77,7 @@ export const useWorkerPolling = () => {
                 payload: JSON.stringify(payload),
               })
             }
-            else if(scheduledSessionStatus.type === 'start') {
-              invoke('show_notification', {
-                notificationType: 'scheduled-session-start',
-                payload: JSON.stringify({
-                  workflowId: scheduledSessionStatus.schedule.workflowId,
-                  workflowName: scheduledSessionStatus.schedule.workflowName,
-                }),
-              })
-            }
+            // Due sessions are started by the focus scheduler in rust with "start-flow"
 
             // Clean up old scheduled session tracking periodically
             ScheduledSessionExecutionApi.cleanupOldSessionTracking()
@@ -27,7 +27,6 @@ import { DifficultyButton } from '@/components/DifficultyButton'
 import { useSpotifyInstallation } from '@/hooks/useSpotifyInstallation'
 import { logAndToastError } from '@/lib/utils/ebbError.util'
 import { Workflow, WorkflowApi } from '@/api/ebbApi/workflowApi'
-import { BlockingPreferenceApi } from '@/api/ebbApi/blockingPreferenceApi'
 import { EbbWorker } from '@/lib/ebbWorker'
 import { Timer } from './Timer'
 import { MonitorApi } from '@/api/monitorApi/monitorApi'
@@ -61,10 +60,13 @@ const startTimer = async (flowSession: FlowSession) => {
 } 
 
 const startBlocking = async (workflow: Workflow) => {
-  const blockingApps = workflow.id ? await BlockingPreferenceApi.getWorkflowBlockedApps(workflow.id) : []
-  // start blocking
+  // saved workflows resolve their blocked apps in rust
+  if (workflow.id) {
+    await invoke('start_workflow_blocking', { workflowId: workflow.id })
+    return
+  }
   const isBlockList = !workflow.settings.isAllowList
-  await invoke('start_blocking', { blockingApps, isBlockList })
+  await invoke('start_blocking', { blockingApps: [], isBlockList })
 }
 
 export const FlowPage = () => {
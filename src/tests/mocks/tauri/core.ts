@@ -14,6 +14,7 @@ export const invoke = async (cmd: string, args?: unknown): Promise<any> => {
 
   switch (cmd) {
   case 'start_blocking':
+  case 'start_workflow_blocking':
   case 'stop_blocking':
   case 'start_system_monitoring':
   case 'request_system_permissions':